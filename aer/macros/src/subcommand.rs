@@ -113,9 +113,12 @@ pub fn subcommand(input: TokenStream, panic_on_issue: bool) -> TokenStream {
         .find(|s| matches!(s.name(), n if service_name == n))
         .expect("failed to find gRPC service");
 
+    // The requests of client streaming rpcs can't be mapped from flags, so their commands
+    // are written by hand (e.g., `aer cell exec`)
     let commands: Vec<_> = service
         .method
         .iter()
+        .filter(|m| !m.client_streaming())
         .map(|m| {
             let method_name = m.name();
 
@@ -272,22 +275,14 @@ impl Command<'_> {
         let function =
             Ident::new(&method.name().to_snake_case(), Span::call_site());
 
-        let execute = match (
-            method.client_streaming(),
-            method.server_streaming(),
-        ) {
-            (true, true) => {
-                todo!("bidirectional streaming")
-            }
-            (true, false) => {
-                todo!("client streaming")
-            }
-            (false, true) => quote! {
+        let execute = if method.server_streaming() {
+            quote! {
                 crate::execute_server_streaming!(::client::#module::#client_mod::#client_ident::#function, req);
-            },
-            _ => quote! {
+            }
+        } else {
+            quote! {
                 let _ = crate::execute!(::client::#module::#client_mod::#client_ident::#function, req);
-            },
+            }
         };
 
         quote! {
@@ -380,12 +375,17 @@ fn resolve_fields<'a>(
         .flat_map(|f| {
             let field_ident = Ident::new(f.name(), span);
 
-            match FieldType::resolve(f, panic_on_issue) {
+            let field_type = FieldType::resolve(f, panic_on_issue);
+            match field_type {
                 FieldType::Primitive | FieldType::VecPrimitive => {
                     let type_ident =
                         proto_reader::helpers::to_rust_type(f.type_(), span);
 
-                    let type_ident = if f.proto3_optional() {
+                    // repeated fields can be passed multiple times (e.g., `--args a --args b`)
+                    let type_ident = if let FieldType::VecPrimitive = field_type
+                    {
+                        quote! { Vec<#type_ident> }
+                    } else if f.proto3_optional() {
                         quote! { Option<#type_ident> }
                     } else {
                         quote! { #type_ident }
@@ -436,20 +436,8 @@ fn write_mapping(
     fn write_value_from_field(
         command_field_parts: &mut VecDeque<String>,
         mapping: &mut String,
-        field: &FieldDescriptorProto,
-        panic_on_issue: bool,
     ) {
-        let field_type = FieldType::resolve(field, panic_on_issue);
-        if let FieldType::VecPrimitive = field_type {
-            mapping.push_str("vec![");
-        }
-
         mapping.push_str(&command_field_parts.iter().join("_"));
-
-        if let FieldType::VecPrimitive = field_type {
-            mapping.push(']');
-        }
-
         mapping.push(',');
     }
 
@@ -530,12 +518,7 @@ fn write_mapping(
 
         match field_type {
             FieldType::Primitive | FieldType::VecPrimitive => {
-                write_value_from_field(
-                    command_field_parts,
-                    mapping,
-                    field,
-                    panic_on_issue,
-                );
+                write_value_from_field(command_field_parts, mapping);
             }
             FieldType::Message | FieldType::VecMessage => {
                 write_value_from_type(
@@ -0,0 +1,47 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use clap::Args;
+use client::{manifest::Manifest, Client};
+use std::path::PathBuf;
+
+/// Makes the cells and executables of the node match a manifest, by applying
+/// only the changes. The changes are printed before they are applied.
+#[derive(Debug, Args)]
+pub struct ApplyCommand {
+    /// The manifest, in TOML (.toml) or JSON (.json).
+    manifest: PathBuf,
+    /// Only print the changes, without applying them.
+    #[arg(long)]
+    dry_run: bool,
+    /// Free the cells, and stop the executables, that are not in the manifest.
+    #[arg(long)]
+    prune: bool,
+}
+
+impl ApplyCommand {
+    pub async fn execute(self) -> anyhow::Result<()> {
+        let manifest = Manifest::parse_from_file(&self.manifest)?;
+        let client = Client::default().await?;
+
+        let plan = manifest.plan(&client, self.prune).await?;
+        print!("{plan}");
+
+        if !self.dry_run {
+            plan.apply(&client).await?;
+        }
+
+        Ok(())
+    }
+}
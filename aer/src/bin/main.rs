@@ -43,8 +43,9 @@
 \* -------------------------------------------------------------------------- */
 
 use aer::{
-    discovery::DiscoveryServiceCommands, grpc::HealthCommands,
-    observe::ObserveServiceCommands, runtime::CellServiceCommands,
+    apply::ApplyCommand, discovery::DiscoveryServiceCommands,
+    grpc::HealthCommands, observe::ObserveServiceCommands,
+    runtime::CellCommands,
 };
 use clap::{Parser, Subcommand};
 
@@ -57,10 +58,11 @@ struct Cli {
 
 #[derive(Debug, Subcommand)]
 enum Commands {
+    Apply(ApplyCommand),
     #[command(arg_required_else_help = true)]
     Cell {
         #[command(subcommand)]
-        command: CellServiceCommands,
+        command: CellCommands,
     },
     #[command(arg_required_else_help = true)]
     Discovery {
@@ -84,6 +86,7 @@ async fn main() {
     let args = Cli::parse();
 
     if let Err(e) = match args.command {
+        Commands::Apply(command) => command.execute().await,
         Commands::Cell { command } => command.execute().await,
         Commands::Discovery { command } => command.execute().await,
         Commands::Health { command } => command.execute().await,
@@ -70,6 +70,7 @@ unused_results
 #![warn(clippy::unwrap_used)]
 // #![warn(missing_docs)] // TODO: We want the docs from the proto
 
+pub mod apply;
 pub mod cri;
 pub mod discovery;
 pub mod grpc;
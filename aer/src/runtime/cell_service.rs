@@ -58,6 +58,12 @@ macros::subcommand!(
     Free {
         cell_name[required = true],
     },
+    List {
+        cell_name[long],
+        depth[long],
+        name_pattern[long, alias = "pattern"],
+        include_executables[long, short = 'e', default_value = "false"],
+    },
     Start {
         cell_name[required = true],
         executable_name[required = true],
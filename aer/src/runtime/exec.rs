@@ -0,0 +1,223 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use clap::Args;
+use client::{cells::cell_service::CellServiceClient, Client};
+use nix::{
+    libc,
+    sys::termios::{self, SetArg, Termios},
+};
+use proto::cells::{
+    CellServiceExecRequest, CellServiceExecStart, ExitStatus, TerminalSize,
+};
+use std::io::{IsTerminal, Read};
+use tokio::{
+    io::AsyncWriteExt,
+    signal::unix::{signal, SignalKind},
+    sync::mpsc,
+};
+
+/// Runs a command in a cell, attached to this terminal, like `docker exec`.
+/// Exits with the exit code of the command.
+#[derive(Debug, Args)]
+pub struct ExecCommand {
+    /// The cell to run the command in.
+    cell_name: String,
+    /// The command and its arguments (e.g., `aer cell exec my-cell -- ls -l`).
+    #[arg(
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true
+    )]
+    args: Vec<String>,
+    /// Forward stdin to the command.
+    #[arg(long, short)]
+    interactive: bool,
+    /// Allocate a pseudo-terminal for the command. Implies `--interactive`.
+    #[arg(long, short)]
+    tty: bool,
+    /// Environment variables for the command in the form `KEY=VALUE`.
+    #[arg(long, short)]
+    env: Vec<String>,
+    /// The absolute path of the working directory of the command.
+    #[arg(long)]
+    cwd: Option<String>,
+    #[arg(long)]
+    uid: Option<u32>,
+    #[arg(long)]
+    gid: Option<u32>,
+}
+
+impl ExecCommand {
+    pub async fn execute(self) -> anyhow::Result<()> {
+        let client = Client::default().await?;
+
+        let start = CellServiceExecStart {
+            cell_name: Some(self.cell_name),
+            args: self.args,
+            env: self.env,
+            cwd: self.cwd,
+            tty: self.tty,
+            terminal_size: if self.tty { terminal_size() } else { None },
+            uid: self.uid,
+            gid: self.gid,
+            security_profile: None,
+        };
+
+        let (tx, rx) = mpsc::channel(16);
+        tx.send(CellServiceExecRequest {
+            start: Some(start),
+            ..Default::default()
+        })
+        .await?;
+
+        // Keys are sent to the command as they are typed, and interpreted by its terminal
+        let raw_mode = if self.tty { RawMode::enable()? } else { None };
+
+        if self.interactive || self.tty {
+            forward_stdin(tx.clone());
+        }
+        if self.tty {
+            let _ignored = tokio::spawn(forward_resizes(tx.clone()));
+        }
+        // Without stdin, the requests end here, which closes the stdin of the command
+        drop(tx);
+
+        let requests = futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|request| (request, rx))
+        });
+        let mut responses = client.exec(requests).await?.into_inner();
+
+        let mut stdout = tokio::io::stdout();
+        let mut stderr = tokio::io::stderr();
+        let mut exit_status = None;
+        while let Some(res) = responses.message().await? {
+            if !res.stdout.is_empty() {
+                stdout.write_all(&res.stdout).await?;
+                stdout.flush().await?;
+            }
+            if !res.stderr.is_empty() {
+                stderr.write_all(&res.stderr).await?;
+                stderr.flush().await?;
+            }
+            if res.exit_status.is_some() {
+                exit_status = res.exit_status;
+            }
+        }
+
+        drop(raw_mode);
+
+        // Same as shells, a command terminated by a signal exits with 128 + the signal
+        let code = match exit_status {
+            Some(ExitStatus { code: Some(code), .. }) => code,
+            Some(ExitStatus { signal: Some(signal), .. }) => 128 + signal,
+            Some(ExitStatus { .. }) => 0,
+            // The stream ended early, e.g., as auraed was stopped, so the command may still run
+            None => anyhow::bail!("the command ended without an exit status"),
+        };
+        if code != 0 {
+            std::process::exit(code);
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads stdin on its own thread, as the reads block and would otherwise prevent aer from
+/// exiting once the command exits.
+fn forward_stdin(tx: mpsc::Sender<CellServiceExecRequest>) {
+    let _ = std::thread::spawn(move || {
+        let mut stdin = std::io::stdin();
+        let mut buf = vec![0; 1024];
+        loop {
+            let n = match stdin.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+
+            let request = CellServiceExecRequest {
+                stdin: buf[..n].to_vec().into(),
+                ..Default::default()
+            };
+            if tx.blocking_send(request).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Resizes the terminal of the command along with this terminal.
+async fn forward_resizes(tx: mpsc::Sender<CellServiceExecRequest>) {
+    let Ok(mut resizes) = signal(SignalKind::window_change()) else {
+        return;
+    };
+
+    while resizes.recv().await.is_some() {
+        let request = CellServiceExecRequest {
+            resize: terminal_size(),
+            ..Default::default()
+        };
+        if tx.send(request).await.is_err() {
+            break;
+        }
+    }
+}
+
+fn terminal_size() -> Option<TerminalSize> {
+    let mut size =
+        libc::winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: TIOCGWINSZ only writes to `size`
+    let res = unsafe {
+        libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size)
+    };
+
+    (res == 0).then(|| TerminalSize {
+        rows: size.ws_row.into(),
+        columns: size.ws_col.into(),
+    })
+}
+
+/// Puts this terminal in raw mode, until dropped.
+#[derive(Debug)]
+struct RawMode {
+    original: Termios,
+}
+
+impl RawMode {
+    /// Returns [None] if stdin is not a terminal.
+    fn enable() -> anyhow::Result<Option<Self>> {
+        let stdin = std::io::stdin();
+        if !stdin.is_terminal() {
+            return Ok(None);
+        }
+
+        let original = termios::tcgetattr(&stdin)?;
+        let mut raw = original.clone();
+        termios::cfmakeraw(&mut raw);
+        termios::tcsetattr(&stdin, SetArg::TCSANOW, &raw)?;
+
+        Ok(Some(Self { original }))
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = termios::tcsetattr(
+            &std::io::stdin(),
+            SetArg::TCSANOW,
+            &self.original,
+        );
+    }
+}
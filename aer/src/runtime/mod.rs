@@ -43,5 +43,25 @@
 \* -------------------------------------------------------------------------- */
 
 pub use cell_service::CellServiceCommands;
+pub use exec::ExecCommand;
 
-mod cell_service;
\ No newline at end of file
+mod cell_service;
+mod exec;
+
+/// The commands generated from the rpcs of the cell service, along with those written by hand
+/// for the rpcs that stream requests.
+#[derive(Debug, clap::Subcommand)]
+pub enum CellCommands {
+    #[command(flatten)]
+    Service(CellServiceCommands),
+    Exec(ExecCommand),
+}
+
+impl CellCommands {
+    pub async fn execute(self) -> anyhow::Result<()> {
+        match self {
+            Self::Service(command) => command.execute().await,
+            Self::Exec(command) => command.execute().await,
+        }
+    }
+}
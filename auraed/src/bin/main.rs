@@ -94,6 +94,29 @@ struct AuraedOptions {
     /// should respect this value.
     #[clap(short, long, value_parser)]
     library_dir: Option<String>,
+    /// Aurae logs path. Defaults to ${runtime_dir}/logs
+    ///
+    /// Here is where the daemon persists the stdout and stderr history
+    /// of executables, so that earlier output can still be fetched
+    /// after an executable has crashed or restarted.
+    #[clap(long, value_parser)]
+    logs_dir: Option<String>,
+    /// Aurae state path. Defaults to ${library_dir}/state
+    ///
+    /// Here is where the daemon persists its cells and executables,
+    /// so that they are adopted again after the daemon restarts.
+    #[clap(long, value_parser)]
+    state_dir: Option<String>,
+    /// Host bridge that network isolated cells are attached to. Defaults to aurae0.
+    #[clap(long, value_parser)]
+    cell_bridge: Option<String>,
+    /// Pool of IPv4 addresses for network isolated cells, in CIDR notation.
+    /// Defaults to 10.88.0.0/16.
+    ///
+    /// The first address of the pool is assigned to the bridge and is
+    /// the gateway of the cells.
+    #[clap(long, value_parser)]
+    cell_network: Option<String>,
     /// Toggle verbosity. Default false
     #[clap(short, long, alias = "ritz")]
     verbose: bool,
@@ -141,6 +164,10 @@ async fn handle_default(options: AuraedOptions) -> i32 {
         socket,
         runtime_dir,
         library_dir,
+        logs_dir,
+        state_dir,
+        cell_bridge,
+        cell_network,
         verbose,
         nested,
         subcmd: _,
@@ -154,24 +181,45 @@ async fn handle_default(options: AuraedOptions) -> i32 {
         server_key: default_server_key,
         runtime_dir: default_runtime_dir,
         library_dir: default_library_dir,
+        logs_dir: _,
+        state_dir: _,
+        cell_bridge: default_cell_bridge,
+        cell_network: default_cell_network,
     } = AuraedRuntime::default();
 
+    let runtime_dir =
+        runtime_dir.map(PathBuf::from).unwrap_or(default_runtime_dir);
+    let logs_dir =
+        logs_dir.map(PathBuf::from).unwrap_or_else(|| runtime_dir.join("logs"));
+    let library_dir =
+        library_dir.map(PathBuf::from).unwrap_or(default_library_dir);
+    let state_dir = state_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(|| library_dir.join("state"));
+    let cell_network = match cell_network.map(|x| x.parse()).transpose() {
+        Ok(cell_network) => cell_network.unwrap_or(default_cell_network),
+        Err(e) => {
+            error!("Invalid cell network: {e}");
+            return EXIT_ERROR;
+        }
+    };
+
     // Create a new runtime configuration, using provided options or defaults
     let runtime = AuraedRuntime {
         auraed: default_auraed,
         ca_crt: ca_crt.map(PathBuf::from).unwrap_or(default_ca_crt),
         server_crt: server_crt.map(PathBuf::from).unwrap_or(default_server_crt),
         server_key: server_key.map(PathBuf::from).unwrap_or(default_server_key),
-        runtime_dir: runtime_dir
-            .map(PathBuf::from)
-            .unwrap_or(default_runtime_dir),
-        library_dir: library_dir
-            .map(PathBuf::from)
-            .unwrap_or(default_library_dir),
+        runtime_dir,
+        library_dir,
+        logs_dir,
+        state_dir,
+        cell_bridge: cell_bridge.unwrap_or(default_cell_bridge),
+        cell_network,
     };
 
     // Run the auraed daemon with the configured runtime
-    if let Err(e) = run(runtime, socket, verbose, nested).await { 
+    if let Err(e) = run(runtime, socket, verbose, nested).await {
         error!("{:?}", e); // Log any errors that occur
         EXIT_ERROR // Return error exit code
     } else {
@@ -14,37 +14,69 @@
 \* -------------------------------------------------------------------------- */
 
 use super::{
-    cells::{CellName, Cells, CellsCache},
+    cells::{CellAllocation, CellName, Cells, CellsCache},
     error::CellsServiceError,
-    executables::Executables,
+    exec,
+    executables::{
+        ExecutablePhase, Executables, ExecutablesError, StopOptions,
+    },
+    state::{CellRecord, ExecutableRecord, StateStore},
     validation::{
-        ValidatedCellServiceAllocateRequest, ValidatedCellServiceFreeRequest,
-        ValidatedCellServiceStartRequest, ValidatedCellServiceStopRequest,
+        ValidatedCell, ValidatedCellServiceAllocateRequest,
+        ValidatedCellServiceExecStart, ValidatedCellServiceFreeRequest,
+        ValidatedCellServiceListExecutablesRequest,
+        ValidatedCellServiceListRequest, ValidatedCellServicePauseRequest,
+        ValidatedCellServiceResumeRequest, ValidatedCellServiceSignalRequest,
+        ValidatedCellServiceStartRequest, ValidatedCellServiceStatsRequest,
+        ValidatedCellServiceStopRequest, ValidatedCellServiceUpdateRequest,
+        ValidatedCellServiceWaitRequest, ValidatedCellServiceWatchStatsRequest,
     },
     Result,
 };
-use crate::{cells::cell_service::cells::CellsError, observe::ObserveService};
-use ::validation::ValidatedType;
+use crate::{
+    cells::cell_service::cells::CellsError, logging::log_channel::LogChannel,
+    observe::ObserveService, AURAED_RUNTIME,
+};
+use ::validation::{ValidatedField, ValidatedType, ValidationError};
 use backoff::backoff::Backoff;
-use client::{cells::cell_service::CellServiceClient, Client, ClientError};
+use client::{
+    cells::cell_service::CellServiceClient, AuraeSocket, Client, ClientError,
+};
+use nix::sys::signal::Signal;
 use proto::{
     cells::{
-        cell_service_server, Cell, CellGraphNode, CellServiceAllocateRequest,
-        CellServiceAllocateResponse, CellServiceFreeRequest,
-        CellServiceFreeResponse, CellServiceListRequest,
-        CellServiceListResponse, CellServiceStartRequest,
-        CellServiceStartResponse, CellServiceStopRequest,
-        CellServiceStopResponse, CpuController, CpusetController,
-        MemoryController,
+        cell_service_server, Capabilities, Cell, CellAllocationStatus,
+        CellGraphNode, CellServiceAllocateRequest, CellServiceAllocateResponse,
+        CellServiceExecRequest, CellServiceExecResponse, CellServiceExecStart,
+        CellServiceFreeRequest, CellServiceFreeResponse,
+        CellServiceListExecutablesRequest, CellServiceListExecutablesResponse,
+        CellServiceListRequest, CellServiceListResponse,
+        CellServicePauseRequest, CellServicePauseResponse,
+        CellServiceResumeRequest, CellServiceResumeResponse,
+        CellServiceSignalRequest, CellServiceSignalResponse,
+        CellServiceStartRequest, CellServiceStartResponse,
+        CellServiceStatsRequest, CellServiceStatsResponse,
+        CellServiceStopRequest, CellServiceStopResponse,
+        CellServiceUpdateRequest, CellServiceUpdateResponse,
+        CellServiceWaitRequest, CellServiceWaitResponse,
+        CellServiceWatchStatsRequest, CellServiceWatchStatsResponse, CellStats,
+        CellStatsNode, CpuController, CpuStats, CpusetController,
+        ExecutableState, ExecutableStatus, ExitStatus, HugetlbController,
+        IdMapping, IoController, IoDeviceStats, IoStats, MemoryController,
+        MemoryStats, Mount, MountType, PidsController, PidsStats,
+        RdmaController, RdmaLimit, SecurityProfile,
     },
     observe::LogChannelType,
 };
-use std::os::unix::fs::MetadataExt;
-use std::time::Duration;
-use std::{process::ExitStatus, sync::Arc};
-use tokio::sync::Mutex;
-use tonic::{Code, Request, Response, Status};
-use tracing::{info, trace, warn};
+use std::collections::{HashMap, HashSet};
+use std::os::unix::{fs::MetadataExt, process::ExitStatusExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, Mutex};
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use tonic::{Code, Request, Response, Status, Streaming};
+use tracing::{error, info, trace, warn};
 
 /**
  * Macro to perform an operation within a cell.
@@ -103,12 +135,22 @@ macro_rules! do_in_cell {
     }};
 }
 
+/// How long List waits for the nested auraed of a cell to list its executables.
+const LIST_EXECUTABLES_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The output of a command run with Exec, either here or by the nested auraed of a cell.
+type ExecResponseStream = futures::stream::BoxStream<
+    'static,
+    std::result::Result<CellServiceExecResponse, Status>,
+>;
+
 /// CellService struct manages the lifecycle of cells and executables.
 #[derive(Debug, Clone)]
 pub struct CellService {
     cells: Arc<Mutex<Cells>>,
     executables: Arc<Mutex<Executables>>,
     observe_service: ObserveService,
+    state: Arc<Mutex<StateStore>>,
 }
 
 impl CellService {
@@ -121,7 +163,132 @@ impl CellService {
             cells: Default::default(),
             executables: Default::default(),
             observe_service,
+            state: Default::default(),
+        }
+    }
+
+    /// Creates a new instance of CellService that persists its cells and executables in
+    /// `state_dir`, so that they survive a restart of auraed.
+    ///
+    /// The state left in `state_dir` by a previous run is reconciled first:
+    /// * Cells whose nested auraed is still running are adopted, along with their cgroup.
+    /// * What is left of the cells whose nested auraed or cgroup is gone, including the cells
+    ///   nested in them, is cleaned up.
+    /// * Running cells that still fail to be adopted are left running, but are no longer managed.
+    /// * Sockets of nested auraeds that do not belong to an adopted cell are removed.
+    /// * Executables are started again, as the output of any process that outlived the
+    ///   previous run can no longer be read. The leftover processes are killed, and the output
+    ///   of the previous run is kept.
+    ///
+    /// # Arguments
+    /// * `observe_service` - An instance of ObserveService to manage log channels.
+    /// * `state_dir` - The directory to persist the state in.
+    pub async fn with_state_dir(
+        observe_service: ObserveService,
+        state_dir: PathBuf,
+    ) -> Self {
+        let store = StateStore::open(state_dir.clone()).unwrap_or_else(|e| {
+            error!(
+                "Failed to load state from {}, starting without cells or executables: {e}",
+                state_dir.display()
+            );
+            StateStore::empty(state_dir)
+        });
+
+        let cell_service = CellService {
+            state: Arc::new(Mutex::new(store)),
+            ..Self::new(observe_service)
+        };
+
+        cell_service.reconcile().await;
+
+        cell_service
+    }
+
+    /// Adopts the cells and starts the executables of the persisted state.
+    /// See [CellService::with_state_dir].
+    #[tracing::instrument(skip(self))]
+    async fn reconcile(&self) {
+        let state = self.state.lock().await.state().clone();
+
+        let mut cells = self.cells.lock().await;
+
+        // Parents are persisted before their children, so they are adopted first
+        let mut released: Vec<(CellName, CellAllocation)> = vec![];
+        let mut sockets = HashSet::new();
+        for record in state.cells {
+            let cell_name = record.cell.name.clone();
+            let Err(e) = adopt_cell(&mut cells, &record) else {
+                let _ = sockets.insert(record.allocation.socket.clone());
+                self.observe_service
+                    .register_cell_socket(
+                        cell_name,
+                        AuraeSocket::Path(record.allocation.socket),
+                    )
+                    .await;
+                continue;
+            };
+
+            // Cells nested in a released cell are killed along with its cgroup
+            let release =
+                CellName::validate(Some(cell_name.clone()), "name", None)
+                    .ok()
+                    .filter(|name| {
+                        released.iter().any(|(parent, _)| {
+                            name.as_inner().starts_with(parent.as_inner())
+                        }) || !record.allocation.is_running(name)
+                    });
+
+            if let Some(name) = release {
+                warn!(
+                    "Failed to adopt cell '{cell_name}', cleaning it up: {e}"
+                );
+                released.push((name, record.allocation));
+            } else {
+                // Killing a running cell would take down its workloads for good
+                error!("Failed to adopt cell '{cell_name}', leaving it running: {e}");
+                let _ = sockets.insert(record.allocation.socket);
+            }
+        }
+
+        // The cgroups of children must be deleted before those of their parents
+        for (cell_name, allocation) in released.iter().rev() {
+            allocation.release(cell_name);
         }
+
+        self.save_cells(&cells).await;
+        drop(cells);
+
+        let runtime_dir = &AURAED_RUNTIME.get().expect("runtime").runtime_dir;
+        remove_stale_sockets(runtime_dir, &sockets);
+
+        for record in state.executables {
+            record.kill_orphan();
+
+            let executable_name = record.name().to_string();
+            if let Err(e) = self.start(record.request, true).await {
+                warn!(
+                    "Failed to start executable '{executable_name}' again: {e}"
+                );
+                self.state
+                    .lock()
+                    .await
+                    .remove_executable(&executable_name, Some(record.pid));
+            }
+        }
+    }
+
+    /// Persists the cells of `cells`, including nested cells.
+    async fn save_cells(&self, cells: &Cells) {
+        let records = cells
+            .get_all(cell_records)
+            .expect("cells doesn't error")
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .flatten()
+            .collect();
+
+        self.state.lock().await.set_cells(records);
     }
 
     /// Allocates a new cell based on the provided request.
@@ -147,10 +314,27 @@ impl CellService {
 
         let cell = cells.allocate(cell_name, cell_spec)?;
 
-        Ok(CellServiceAllocateResponse {
+        // Allow the observe service to proxy log requests to the cell's nested auraed
+        self.observe_service
+            .register_cell_socket(
+                cell.name().to_string(),
+                cell.client_socket()?,
+            )
+            .await;
+
+        let response = CellServiceAllocateResponse {
             cell_name: cell.name().clone().to_string(),
             cgroup_v2: cell.v2().expect("allocated cell returns `Some`"),
-        })
+            ip_addresses: cell
+                .ip_addresses()
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+        };
+
+        self.save_cells(&cells).await;
+
+        Ok(response)
     }
 
     /// Frees a cell.
@@ -173,9 +357,66 @@ impl CellService {
 
         cells.free(&cell_name)?;
 
+        self.save_cells(&cells).await;
+
+        self.observe_service
+            .unregister_cell_socket(&cell_name.to_string())
+            .await;
+
         Ok(CellServiceFreeResponse::default())
     }
 
+    #[tracing::instrument(skip(self))]
+    /// Handles an update request.
+    ///
+    /// The cgroups of nested cells are owned by this auraed, so the request is
+    /// applied through the cells cache instead of being forwarded to the parent.
+    ///
+    /// # Arguments
+    /// * `request` - A request containing CellServiceUpdateRequest.
+    ///
+    /// # Returns
+    /// A response containing CellServiceUpdateResponse or a Status error.
+    async fn update(
+        &self,
+        request: ValidatedCellServiceUpdateRequest,
+    ) -> Result<CellServiceUpdateResponse> {
+        let cell_name = request.cell_name.clone();
+
+        info!("CellService: update() cell_name={cell_name:?}");
+
+        let mut cells = self.cells.lock().await;
+
+        cells.update(&cell_name, request.into())?;
+
+        self.save_cells(&cells).await;
+
+        Ok(CellServiceUpdateResponse::default())
+    }
+
+    /// Returns true if the cells and executables are persisted, so that the next run of
+    /// auraed can take them over.
+    pub(crate) async fn persists_state(&self) -> bool {
+        self.state.lock().await.persistent()
+    }
+
+    /// Leaves the cells running and the persisted state as it is, so that the next run of
+    /// auraed adopts the cells. Executables outside of cells are stopped gracefully, as their
+    /// output can't be read by the next run, which starts them again.
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn detach_all(&self) -> Result<()> {
+        let mut cells = self.cells.lock().await;
+        let mut executables = self.executables.lock().await;
+
+        // Nothing that happens from here on is persisted
+        self.state.lock().await.close();
+
+        cells.broadcast_detach();
+        executables.broadcast_stop(StopOptions::default()).await;
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub(crate) async fn free_all(&self) -> Result<()> {
         let mut cells = self.cells.lock().await;
@@ -187,6 +428,8 @@ impl CellService {
         // Forcefully kill any remaining cells that failed to shut down
         cells.broadcast_kill();
 
+        self.save_cells(&cells).await;
+
         Ok(())
     }
 
@@ -195,19 +438,22 @@ impl CellService {
     ///
     /// # Arguments
     /// * `request` - A request containing CellServiceStartRequest.
+    /// * `keep_history` - Keep the output of an earlier run of the executable, which is started
+    ///   again after a restart of auraed.
     ///
     /// # Returns
     /// A response containing CellServiceStartResponse or a Status error.
     async fn start(
         &self,
-        request: ValidatedCellServiceStartRequest,
+        request: CellServiceStartRequest,
+        keep_history: bool,
     ) -> std::result::Result<Response<CellServiceStartResponse>, Status> {
         let ValidatedCellServiceStartRequest {
             cell_name,
             executable,
             uid,
             gid,
-        } = request;
+        } = ValidatedCellServiceStartRequest::validate(request.clone(), None)?;
 
         assert!(cell_name.is_none());
         info!("CellService: start() executable={:?}", executable);
@@ -215,42 +461,98 @@ impl CellService {
         let mut executables = self.executables.lock().await;
 
         // Start the executable and handle any errors
-        let executable = executables
-            .start(executable, uid, gid)
-            .map_err(CellsServiceError::ExecutablesError)?;
+        let executable = if keep_history {
+            executables.restore(executable, uid, gid)
+        } else {
+            executables.start(executable, uid, gid)
+        };
+        let executable =
+            executable.map_err(CellsServiceError::ExecutablesError)?;
 
         // Retrieve the process ID (PID) of the started executable
-        let pid = executable
-            .pid()
-            .map_err(CellsServiceError::Io)?
-            .expect("pid")
-            .as_raw();
-
-        // Register the stdout log channel for the executable's PID
-        if let Err(e) = self
-            .observe_service
-            .register_sub_process_channel(
-                pid,
+        let pid = executable.pid().expect("pid").as_raw();
+
+        // Persist the request while holding the lock, so that a concurrent stop is not missed
+        self.state
+            .lock()
+            .await
+            .set_executable(ExecutableRecord::new(request, pid));
+
+        // Register the log channels for the executable's PID
+        register_log_channels(
+            &self.observe_service,
+            pid,
+            &executable.stdout,
+            &executable.stderr,
+        )
+        .await;
+
+        // Register the log channels for the executable's name, which does not change on restarts
+        let executable_name = executable.name.to_string();
+        self.observe_service
+            .register_executable_channel(
+                executable_name.clone(),
                 LogChannelType::Stdout,
                 executable.stdout.clone(),
             )
-            .await
-        {
-            warn!("failed to register stdout channel for pid {pid}: {e}");
-        }
-
-        // Register the stderr log channel for the executable's PID
-        if let Err(e) = self
-            .observe_service
-            .register_sub_process_channel(
-                pid,
+            .await;
+        self.observe_service
+            .register_executable_channel(
+                executable_name,
                 LogChannelType::Stderr,
                 executable.stderr.clone(),
             )
-            .await
-        {
-            warn!("failed to register stderr channel for pid {pid}: {e}");
-        }
+            .await;
+
+        // The PID changes every time the executable is restarted, so follow the restarts and
+        // move the log channels and the persisted record to the new PID. This ends once the
+        // executable is finished, which is no longer started again after a restart of auraed.
+        let mut status = executable.subscribe().expect("started executable");
+        let observe_service = self.observe_service.clone();
+        let state = self.state.clone();
+        let executable_name = executable.name.to_string();
+        let (stdout, stderr) =
+            (executable.stdout.clone(), executable.stderr.clone());
+        let _ignored = tokio::spawn(async move {
+            let mut pid = pid;
+            while status.changed().await.is_ok() {
+                let (finished, restarted_pid) = {
+                    let status = status.borrow_and_update();
+                    (status.phase.is_finished(), status.pid.map(|x| x.as_raw()))
+                };
+
+                if finished {
+                    state
+                        .lock()
+                        .await
+                        .remove_executable(&executable_name, Some(pid));
+                    continue;
+                }
+
+                let Some(restarted_pid) = restarted_pid else {
+                    continue;
+                };
+                if restarted_pid == pid {
+                    continue;
+                }
+
+                state.lock().await.restart_executable(
+                    &executable_name,
+                    pid,
+                    restarted_pid,
+                );
+
+                unregister_log_channels(&observe_service, pid).await;
+                register_log_channels(
+                    &observe_service,
+                    restarted_pid,
+                    &stdout,
+                    &stderr,
+                )
+                .await;
+                pid = restarted_pid;
+            }
+        });
 
         let (self_uid, self_gid) =
             std::fs::metadata("/proc/self").map(|m| (m.uid(), m.gid()))?;
@@ -266,8 +568,25 @@ impl CellService {
     async fn start_in_cell(
         &self,
         cell_name: &CellName,
-        request: CellServiceStartRequest,
+        mut request: CellServiceStartRequest,
     ) -> std::result::Result<Response<CellServiceStartResponse>, Status> {
+        // The nested auraed does not know the spec of its cell, so the security profile of the
+        // cell is sent along with executables that do not have their own
+        if let Some(executable) = request.executable.as_mut() {
+            if executable.security_profile.is_none() {
+                let mut cells = self.cells.lock().await;
+                executable.security_profile = cells
+                    .get(cell_name, |cell| {
+                        Ok(cell
+                            .spec()
+                            .security_profile
+                            .as_ref()
+                            .map(|x| x.into()))
+                    })
+                    .map_err(CellsServiceError::CellsError)?;
+            }
+        }
+
         do_in_cell!(self, cell_name, start, request)
     }
 
@@ -283,46 +602,54 @@ impl CellService {
         &self,
         request: ValidatedCellServiceStopRequest,
     ) -> std::result::Result<Response<CellServiceStopResponse>, Status> {
-        let ValidatedCellServiceStopRequest { cell_name, executable_name } =
-            request;
+        let ValidatedCellServiceStopRequest {
+            cell_name,
+            executable_name,
+            signal,
+            grace_period_ms,
+        } = request;
 
         assert!(cell_name.is_none());
-        info!("CellService: stop() executable_name={:?}", executable_name,);
-
-        let mut executables = self.executables.lock().await;
-
-        // Retrieve the process ID (PID) of the executable to be stopped
-        let pid = executables
-            .get(&executable_name)
-            .map_err(CellsServiceError::ExecutablesError)?
-            .pid()
-            .map_err(CellsServiceError::Io)?
-            .expect("pid")
-            .as_raw();
+        info!(
+            "CellService: stop() executable_name={:?} signal={signal} grace_period={grace_period_ms:?}",
+            executable_name,
+        );
+
+        // Only hold the lock long enough to signal the executable, so that other requests are not
+        // blocked for the grace period.
+        let stopped = {
+            let mut executables = self.executables.lock().await;
+            let stopped = executables
+                .stop(
+                    &executable_name,
+                    StopOptions { signal, grace_period: grace_period_ms },
+                )
+                .map_err(CellsServiceError::ExecutablesError)?;
+
+            self.state
+                .lock()
+                .await
+                .remove_executable(&executable_name.to_string(), None);
+
+            // The name may be reused as soon as the lock is released
+            self.observe_service
+                .unregister_executable_channels(&executable_name.to_string())
+                .await;
+
+            stopped
+        };
 
         // Stop the executable and handle any errors
-        let _: ExitStatus = executables
-            .stop(&executable_name)
-            .await
-            .map_err(CellsServiceError::ExecutablesError)?;
+        let executable =
+            stopped.await.map_err(CellsServiceError::ExecutablesError)?;
 
-        // Remove the executable's logs from the observe service.
-        if let Err(e) = self
-            .observe_service
-            .unregister_sub_process_channel(pid, LogChannelType::Stdout)
-            .await
-        {
-            warn!("failed to unregister stdout channel for pid {pid}: {e}");
-        }
-        if let Err(e) = self
-            .observe_service
-            .unregister_sub_process_channel(pid, LogChannelType::Stderr)
-            .await
-        {
-            warn!("failed to unregister stderr channel for pid {pid}: {e}");
-        }
+        // Remove the logs of the executable's last PID from the observe service.
+        let pid = executable.pid.expect("pid").as_raw();
+        unregister_log_channels(&self.observe_service, pid).await;
 
-        Ok(Response::new(CellServiceStopResponse::default()))
+        Ok(Response::new(CellServiceStopResponse {
+            executable: Some(executable.into()),
+        }))
     }
 
     #[tracing::instrument(skip(self))]
@@ -335,169 +662,1061 @@ impl CellService {
     }
 
     #[tracing::instrument(skip(self))]
-    pub(crate) async fn stop_all(&self) -> Result<()> {
-        let mut executables = self.executables.lock().await;
-        // Broadcast a stop signal to all executables
-        executables.broadcast_stop().await;
-        Ok(())
-    }
-
-    #[tracing::instrument(skip(self))]
-    async fn list(&self) -> Result<CellServiceListResponse> {
-        let cells = self.cells.lock().await;
+    /// Handles the wait request.
+    ///
+    /// # Arguments
+    /// * `request` - A request containing CellServiceWaitRequest.
+    ///
+    /// # Returns
+    /// A response containing CellServiceWaitResponse once the executable has
+    /// exited, or a Status error.
+    async fn wait(
+        &self,
+        request: ValidatedCellServiceWaitRequest,
+    ) -> std::result::Result<Response<CellServiceWaitResponse>, Status> {
+        let ValidatedCellServiceWaitRequest { cell_name, executable_name } =
+            request;
 
-        // Retrieve all cells and convert them for returning
-        let cells = cells
-            .get_all(|x| x.try_into())
-            .expect("cells doesn't error")
-            .into_iter()
-            .filter_map(|x| x.ok())
-            .collect();
+        assert!(cell_name.is_none());
+        info!("CellService: wait() executable_name={:?}", executable_name);
+
+        // Only hold the lock long enough to get the executable's status, so that other requests,
+        // including stopping the executable we are waiting on, are not blocked.
+        let (status, wait) = {
+            let executables = self.executables.lock().await;
+            let executable = executables
+                .get(&executable_name)
+                .map_err(CellsServiceError::ExecutablesError)?;
+            (executable.status(), executable.wait())
+        };
 
-        Ok(CellServiceListResponse { cells })
+        let status = wait
+            .await
+            .map_err(|e| {
+                CellsServiceError::ExecutablesError(
+                    ExecutablesError::FailedToWaitForExecutable {
+                        executable_name,
+                        source: e,
+                    },
+                )
+            })?
+            .unwrap_or(status);
+
+        Ok(Response::new(CellServiceWaitResponse {
+            executable: Some(status.into()),
+        }))
     }
-}
 
-impl TryFrom<&super::cells::Cell> for CellGraphNode {
-    type Error = CellsError;
+    #[tracing::instrument(skip(self))]
+    async fn wait_in_cell(
+        &self,
+        cell_name: &CellName,
+        request: CellServiceWaitRequest,
+    ) -> std::result::Result<Response<CellServiceWaitResponse>, Status> {
+        do_in_cell!(self, cell_name, wait, request)
+    }
 
-    /// Converts a Cell into a CellGraphNode.
+    #[tracing::instrument(skip(self))]
+    /// Handles the signal request for an executable.
     ///
     /// # Arguments
-    /// * `value` - A reference to the Cell.
+    /// * `request` - A request containing CellServiceSignalRequest.
     ///
     /// # Returns
-    /// A result containing the CellGraphNode or an error.
-    fn try_from(
-        value: &super::cells::Cell,
-    ) -> std::result::Result<Self, Self::Error> {
-        // Extract the name and specification of the cell
-        let name = value.name();
-        let spec = value.spec();
-        // Retrieve and convert all child cells
-        let children = CellsCache::get_all(value, |x| x.try_into())?
-            .into_iter()
-            .filter_map(|x| x.ok())
-            .collect();
-
-        // Extract cgroup and isolation specifications
-        let super::cells::CellSpec { cgroup_spec, iso_ctl } = spec;
-        // Extract CPU, cpuset, and memory specifications
-        let super::cells::cgroups::CgroupSpec { cpu, cpuset, memory } =
-            cgroup_spec;
+    /// A response containing the pid of the signaled executable, or an error.
+    async fn signal(
+        &self,
+        request: ValidatedCellServiceSignalRequest,
+    ) -> Result<CellServiceSignalResponse> {
+        let ValidatedCellServiceSignalRequest {
+            cell_name,
+            executable_name,
+            signal,
+        } = request;
 
-        Ok(Self {
-            // Create a new Cell instance with the extracted specifications
-            cell: Some(Cell {
-                name: name.to_string(),
-                cpu: cpu.as_ref().map(|x| x.into()),
-                cpuset: cpuset.as_ref().map(|x| x.into()),
-                memory: memory.as_ref().map(|x| x.into()),
-                isolate_process: iso_ctl.isolate_process,
-                isolate_network: iso_ctl.isolate_network,
-            }),
-            children,
-        })
-    }
-}
+        assert!(cell_name.is_none());
+        let executable_name = executable_name.expect("executable name");
+        info!(
+            "CellService: signal() executable_name={:?} signal={signal}",
+            executable_name
+        );
 
-impl From<&super::cells::cgroups::CpuController> for CpuController {
-    fn from(value: &super::cells::cgroups::CpuController) -> Self {
-        let super::cells::cgroups::CpuController { weight, max, period } =
-            value.clone();
+        let executables = self.executables.lock().await;
+        let pid = executables.signal(&executable_name, signal)?;
 
-        Self {
-            weight: weight.map(|x| x.into_inner()),
-            max: max.map(|x| x.into_inner()),
-            period,
-        }
+        Ok(CellServiceSignalResponse { pids: vec![pid.as_raw()] })
     }
-}
-
-impl From<&super::cells::cgroups::cpuset::CpusetController>
-    for CpusetController
-{
-    fn from(value: &super::cells::cgroups::CpusetController) -> Self {
-        let super::cells::cgroups::CpusetController { cpus, mems } =
-            value.clone();
 
-        Self {
-            cpus: cpus.map(|x| x.into_inner()),
-            mems: mems.map(|x| x.into_inner()),
-        }
+    #[tracing::instrument(skip(self))]
+    async fn signal_in_cell(
+        &self,
+        cell_name: &CellName,
+        request: CellServiceSignalRequest,
+    ) -> std::result::Result<Response<CellServiceSignalResponse>, Status> {
+        do_in_cell!(self, cell_name, signal, request)
     }
-}
 
-impl From<&super::cells::cgroups::memory::MemoryController>
-    for MemoryController
-{
-    fn from(value: &super::cells::cgroups::MemoryController) -> Self {
-        let super::cells::cgroups::MemoryController { min, low, high, max } =
-            value.clone();
+    /// Sends a signal to every process in the cgroup of a cell.
+    #[tracing::instrument(skip(self))]
+    async fn signal_cell(
+        &self,
+        cell_name: &CellName,
+        signal: Signal,
+    ) -> Result<CellServiceSignalResponse> {
+        info!("CellService: signal() cell_name={cell_name:?} signal={signal}");
 
-        Self {
-            min: min.map(|x| x.into_inner()),
-            low: low.map(|x| x.into_inner()),
-            high: high.map(|x| x.into_inner()),
-            max: max.map(|x| x.into_inner()),
-        }
+        let mut cells = self.cells.lock().await;
+        let pids = cells.get(cell_name, |cell| cell.signal(signal))?;
+
+        Ok(CellServiceSignalResponse {
+            pids: pids.into_iter().map(|x| x.as_raw()).collect(),
+        })
     }
-}
 
-/// ### Mapping cgroup options to the Cell API
-///
-/// Here we *only* expose options from the CgroupBuilder
-/// as our features in Aurae need them! We do not try to
-/// "map" everything as much as we start with a few small
-/// features and add as needed.
-///
-// Example builder options can be found: https://github.com/kata-containers/cgroups-rs/blob/main/tests/builder.rs
-// Cgroup documentation: https://man7.org/linux/man-pages/man7/cgroups.7.html
-#[tonic::async_trait]
-impl cell_service_server::CellService for CellService {
-    async fn allocate(
+    /// Freezes the processes of a cell and its children.
+    ///
+    /// The cgroups of nested cells are owned by this auraed, so the cell is
+    /// paused through the cells cache instead of being forwarded to the parent.
+    #[tracing::instrument(skip(self))]
+    async fn pause(
         &self,
-        request: Request<CellServiceAllocateRequest>,
-    ) -> std::result::Result<Response<CellServiceAllocateResponse>, Status>
-    {
-        // Extract the inner request from the request
-        let request = request.into_inner();
-        // Validate the allocate request
-        let request = ValidatedCellServiceAllocateRequest::validate(
-            request.clone(),
-            None,
-        )?;
+        request: ValidatedCellServicePauseRequest,
+    ) -> Result<CellServicePauseResponse> {
+        let ValidatedCellServicePauseRequest { cell_name } = request;
 
-        // return the allocated cell
-        Ok(Response::new(self.allocate(request).await?))
-    }
+        info!("CellService: pause() cell_name={cell_name:?}");
 
-    async fn free(
-        &self,
-        request: Request<CellServiceFreeRequest>,
-    ) -> std::result::Result<Response<CellServiceFreeResponse>, Status> {
-        let request = request.into_inner();
-        // Validate the free request
-        let request =
-            ValidatedCellServiceFreeRequest::validate(request.clone(), None)?;
+        let mut cells = self.cells.lock().await;
 
-        // free the cell
-        Ok(Response::new(self.free(request).await?))
+        cells.get(&cell_name, |cell| cell.pause())?;
+
+        Ok(CellServicePauseResponse::default())
     }
 
-    async fn start(
+    /// Thaws the processes of a paused cell.
+    #[tracing::instrument(skip(self))]
+    async fn resume(
         &self,
-        request: Request<CellServiceStartRequest>,
-    ) -> std::result::Result<Response<CellServiceStartResponse>, Status> {
-        let request = request.into_inner();
+        request: ValidatedCellServiceResumeRequest,
+    ) -> Result<CellServiceResumeResponse> {
+        let ValidatedCellServiceResumeRequest { cell_name } = request;
 
-        // Execute start if cell_name is none
-        if request.cell_name.is_none() {
-            let request =
-                ValidatedCellServiceStartRequest::validate(request, None)?;
-            Ok(self.start(request).await?)
-        } else {
-            // We are in a parent cell, or validation will fail
+        info!("CellService: resume() cell_name={cell_name:?}");
+
+        let mut cells = self.cells.lock().await;
+
+        cells.get(&cell_name, |cell| cell.resume())?;
+
+        Ok(CellServiceResumeResponse::default())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn stop_all(&self) -> Result<()> {
+        let mut executables = self.executables.lock().await;
+        // Gracefully stop all executables, killing those that outlive the grace period
+        executables.broadcast_stop(StopOptions::default()).await;
+        self.state.lock().await.clear_executables();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list(
+        &self,
+        request: ValidatedCellServiceListRequest,
+    ) -> Result<CellServiceListResponse> {
+        let ValidatedCellServiceListRequest {
+            cell_name,
+            depth,
+            name_pattern,
+            include_executables,
+        } = request;
+
+        let mut cells = {
+            let mut cells = self.cells.lock().await;
+
+            // Retrieve the cells and convert them for returning
+            match cell_name {
+                Some(cell_name) => {
+                    vec![cells.get(&cell_name, |x| x.try_into())?]
+                }
+                None => cells
+                    .get_all(|x| x.try_into())
+                    .expect("cells doesn't error")
+                    .into_iter()
+                    .filter_map(|x| x.ok())
+                    .collect(),
+            }
+        };
+
+        if let Some(depth) = depth {
+            limit_depth(&mut cells, depth);
+        }
+
+        if let Some(name_pattern) = name_pattern {
+            retain_matching(&mut cells, &name_pattern);
+        }
+
+        if include_executables {
+            self.list_executables_of_cells(&mut cells).await;
+        }
+
+        Ok(CellServiceListResponse { cells })
+    }
+
+    /// Lists the executables of every cell of the graph from their nested auraeds.
+    /// The nested auraeds are queried concurrently, without holding the lock on the cells.
+    async fn list_executables_of_cells(&self, nodes: &mut [CellGraphNode]) {
+        let mut names = vec![];
+        cell_names(nodes, &mut names);
+
+        let sockets: Vec<_> = {
+            let mut cells = self.cells.lock().await;
+            names
+                .into_iter()
+                .map(|name| {
+                    let socket = cells
+                        .get(&CellName::from(name.as_str()), |cell| {
+                            cell.client_socket()
+                        })
+                        .map_err(|e| e.to_string());
+                    (name, socket)
+                })
+                .collect()
+        };
+
+        let mut executables: HashMap<_, _> = futures::future::join_all(
+            sockets.into_iter().map(|(name, socket)| async move {
+                let executables = match socket {
+                    Ok(socket) => executables_in_cell(socket).await,
+                    Err(e) => Err(e),
+                };
+                (name, executables)
+            }),
+        )
+        .await
+        .into_iter()
+        .collect();
+
+        set_executables(nodes, &mut executables);
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_executables(
+        &self,
+        request: ValidatedCellServiceListExecutablesRequest,
+    ) -> Result<CellServiceListExecutablesResponse> {
+        let ValidatedCellServiceListExecutablesRequest { cell_name } = request;
+
+        assert!(cell_name.is_none());
+
+        let executables = self.executables.lock().await;
+
+        let executables =
+            executables.list().into_iter().map(|x| x.into()).collect();
+
+        Ok(CellServiceListExecutablesResponse { executables })
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_executables_in_cell(
+        &self,
+        cell_name: &CellName,
+        request: CellServiceListExecutablesRequest,
+    ) -> std::result::Result<Response<CellServiceListExecutablesResponse>, Status>
+    {
+        do_in_cell!(self, cell_name, list_executables, request)
+    }
+
+    /// Reads the stats of every cell from their cgroups. The cgroups of nested cells are visible
+    /// from here, so the request is never forwarded to a cell.
+    #[tracing::instrument(skip(self))]
+    async fn stats(
+        &self,
+        cell_name: Option<&CellName>,
+    ) -> Result<Vec<CellStatsNode>> {
+        let mut cells = self.cells.lock().await;
+
+        let Some(cell_name) = cell_name else {
+            return Ok(cells
+                .get_all(|x| x.try_into())
+                .expect("cells doesn't error")
+                .into_iter()
+                .filter_map(|x| x.ok())
+                .collect());
+        };
+
+        Ok(vec![cells.get(cell_name, |x| x.try_into())?])
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn watch_stats(
+        &self,
+        request: ValidatedCellServiceWatchStatsRequest,
+    ) -> ReceiverStream<
+        std::result::Result<CellServiceWatchStatsResponse, Status>,
+    > {
+        let ValidatedCellServiceWatchStatsRequest { cell_name, interval_ms } =
+            request;
+
+        let (tx, rx) = mpsc::channel(1);
+        let cell_service = self.clone();
+
+        let _ignored = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval_ms);
+            interval.set_missed_tick_behavior(
+                tokio::time::MissedTickBehavior::Delay,
+            );
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = tx.closed() => break,
+                }
+
+                let collected_at = unix_nanos(SystemTime::now());
+                let res = cell_service
+                    .stats(cell_name.as_ref())
+                    .await
+                    .map(|cells| CellServiceWatchStatsResponse {
+                        cells,
+                        collected_at,
+                    })
+                    .map_err(Status::from);
+
+                let is_err = res.is_err();
+                if tx.send(res).await.is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+
+    /// Runs a one-off command in the cell of this auraed, attached to the `requests` of the
+    /// client.
+    #[tracing::instrument(skip(self, requests))]
+    async fn exec(
+        &self,
+        request: ValidatedCellServiceExecStart,
+        requests: Streaming<CellServiceExecRequest>,
+    ) -> Result<ExecResponseStream> {
+        assert!(request.cell_name.is_none());
+        info!("CellService: exec() args={:?}", request.args);
+
+        let responses = exec::spawn(request.into(), requests)?;
+
+        Ok(Box::pin(responses))
+    }
+
+    /// Forwards the requests of the client to the nested auraed of the cell, which runs the
+    /// command. The nested auraed is not retried, as the requests can't be replayed.
+    #[tracing::instrument(skip(self, start, requests))]
+    async fn exec_in_cell(
+        &self,
+        cell_name: &CellName,
+        mut start: CellServiceExecStart,
+        requests: Streaming<CellServiceExecRequest>,
+    ) -> std::result::Result<ExecResponseStream, Status> {
+        let client_socket = {
+            let mut cells = self.cells.lock().await;
+
+            // Same as for start, the security profile of the cell is sent along with commands
+            // that do not have their own
+            if start.security_profile.is_none() {
+                start.security_profile = cells
+                    .get(cell_name, |cell| {
+                        Ok(cell
+                            .spec()
+                            .security_profile
+                            .as_ref()
+                            .map(|x| x.into()))
+                    })
+                    .map_err(CellsServiceError::CellsError)?;
+            }
+
+            cells
+                .get(cell_name, |cell| cell.client_socket())
+                .map_err(CellsServiceError::CellsError)?
+        };
+
+        let client = Client::new_no_tls(client_socket)
+            .await
+            .map_err(CellsServiceError::from)?;
+
+        start.cell_name = None;
+        let start =
+            CellServiceExecRequest { start: Some(start), ..Default::default() };
+        let requests = tokio_stream::once(start)
+            .chain(requests.map_while(|request| request.ok()));
+
+        let responses = client.exec(requests).await?.into_inner();
+
+        Ok(Box::pin(responses))
+    }
+}
+
+impl TryFrom<&super::cells::Cell> for CellGraphNode {
+    type Error = CellsError;
+
+    /// Converts a Cell into a CellGraphNode.
+    ///
+    /// # Arguments
+    /// * `value` - A reference to the Cell.
+    ///
+    /// # Returns
+    /// A result containing the CellGraphNode or an error.
+    fn try_from(
+        value: &super::cells::Cell,
+    ) -> std::result::Result<Self, Self::Error> {
+        // Retrieve and convert all child cells
+        let children = CellsCache::get_all(value, |x| x.try_into())?
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .collect();
+
+        // Report how close the cell is to its pids limit, without failing the listing if the
+        // cgroup can't be read
+        let pids_stats = value.pids_stats().ok().map(|x| x.into());
+        let frozen = value.frozen().unwrap_or_default();
+
+        let allocation = value.nested_auraed_status().map(|(pid, running)| {
+            CellAllocationStatus {
+                nested_auraed_pid: pid.as_raw(),
+                nested_auraed_running: running,
+                cgroup_v2: value.v2().unwrap_or_default(),
+                ip_addresses: value
+                    .ip_addresses()
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect(),
+            }
+        });
+
+        Ok(Self {
+            cell: Some(value.into()),
+            children,
+            pids: pids_stats,
+            frozen,
+            executables: vec![],
+            executables_error: None,
+            allocation,
+        })
+    }
+}
+
+impl From<&super::cells::Cell> for Cell {
+    /// Converts the spec of a Cell into a Cell, named with its full name.
+    fn from(value: &super::cells::Cell) -> Self {
+        // Extract the name and specification of the cell
+        let name = value.name();
+        let spec = value.spec();
+
+        // Extract cgroup and isolation specifications
+        let super::cells::CellSpec { cgroup_spec, iso_ctl, security_profile } =
+            spec;
+        // Extract CPU, cpuset, memory, io, pids, hugetlb, and rdma specifications
+        let super::cells::cgroups::CgroupSpec {
+            cpu,
+            cpuset,
+            memory,
+            io,
+            pids,
+            hugetlb,
+            rdma,
+        } = cgroup_spec;
+
+        // Create a new Cell instance with the extracted specifications
+        Cell {
+            name: name.to_string(),
+            cpu: cpu.as_ref().map(|x| x.into()),
+            cpuset: cpuset.as_ref().map(|x| x.into()),
+            memory: memory.as_ref().map(|x| x.into()),
+            io: io.as_ref().map(|x| x.into()),
+            pids: pids.as_ref().map(|x| x.into()),
+            hugetlb: hugetlb.as_ref().map(|x| x.into()),
+            rdma: rdma.as_ref().map(|x| x.into()),
+            isolate_process: iso_ctl.isolate_process,
+            isolate_network: iso_ctl.isolate_network,
+            isolate_user: iso_ctl.isolate_user,
+            uid_map: iso_ctl.uid_map.iter().map(|x| x.into()).collect(),
+            gid_map: iso_ctl.gid_map.iter().map(|x| x.into()).collect(),
+            rootfs: iso_ctl
+                .rootfs
+                .as_ref()
+                .map(|x| x.to_string_lossy().to_string()),
+            mounts: iso_ctl.mounts.iter().map(|x| x.into()).collect(),
+            security_profile: security_profile.as_ref().map(|x| x.into()),
+        }
+    }
+}
+
+impl TryFrom<&super::cells::Cell> for CellStatsNode {
+    type Error = CellsError;
+
+    fn try_from(
+        value: &super::cells::Cell,
+    ) -> std::result::Result<Self, Self::Error> {
+        let stats = value.stats()?;
+
+        let children = CellsCache::get_all(value, |x| x.try_into())?
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .collect();
+
+        Ok(Self {
+            cell_name: value.name().to_string(),
+            stats: Some(stats.into()),
+            children,
+        })
+    }
+}
+
+impl From<super::cells::cgroups::CgroupStats> for CellStats {
+    fn from(value: super::cells::cgroups::CgroupStats) -> Self {
+        let super::cells::cgroups::CgroupStats { cpu, memory, io, pids } =
+            value;
+
+        let super::cells::cgroups::CpuStats {
+            usage_usec,
+            user_usec,
+            system_usec,
+            nr_periods,
+            nr_throttled,
+            throttled_usec,
+        } = cpu;
+
+        let super::cells::cgroups::MemoryStats { current, swap_current, stat } =
+            memory;
+
+        let devices = io
+            .into_iter()
+            .map(|x| {
+                let super::cells::cgroups::IoDeviceStats {
+                    major,
+                    minor,
+                    rbytes,
+                    wbytes,
+                    rios,
+                    wios,
+                } = x;
+
+                IoDeviceStats { major, minor, rbytes, wbytes, rios, wios }
+            })
+            .collect();
+
+        Self {
+            cpu: Some(CpuStats {
+                usage_usec,
+                user_usec,
+                system_usec,
+                nr_periods,
+                nr_throttled,
+                throttled_usec,
+            }),
+            memory: Some(MemoryStats { current, swap_current, stat }),
+            io: Some(IoStats { devices }),
+            pids: Some(pids.into()),
+        }
+    }
+}
+
+impl From<&super::cells::cgroups::CpuController> for CpuController {
+    fn from(value: &super::cells::cgroups::CpuController) -> Self {
+        let super::cells::cgroups::CpuController { weight, max, period } =
+            value.clone();
+
+        Self {
+            weight: weight.map(|x| x.into_inner()),
+            max: max.map(|x| x.into_inner()),
+            period,
+        }
+    }
+}
+
+impl From<&super::cells::cgroups::cpuset::CpusetController>
+    for CpusetController
+{
+    fn from(value: &super::cells::cgroups::CpusetController) -> Self {
+        let super::cells::cgroups::CpusetController { cpus, mems } =
+            value.clone();
+
+        Self {
+            cpus: cpus.map(|x| x.into_inner()),
+            mems: mems.map(|x| x.into_inner()),
+        }
+    }
+}
+
+impl From<&super::cells::cgroups::memory::MemoryController>
+    for MemoryController
+{
+    fn from(value: &super::cells::cgroups::MemoryController) -> Self {
+        let super::cells::cgroups::MemoryController {
+            min,
+            low,
+            high,
+            max,
+            swap_max,
+            swap_high,
+            oom_group,
+        } = value.clone();
+
+        Self {
+            min: min.map(|x| x.into_inner()),
+            low: low.map(|x| x.into_inner()),
+            high: high.map(|x| x.into_inner()),
+            max: max.map(|x| x.into_inner()),
+            swap_max: swap_max.map(|x| x.into_inner()),
+            swap_high: swap_high.map(|x| x.into_inner()),
+            oom_group,
+        }
+    }
+}
+
+impl From<&super::cells::cgroups::io::IoController> for IoController {
+    fn from(value: &super::cells::cgroups::IoController) -> Self {
+        let super::cells::cgroups::IoController { weight, max, latency } =
+            value;
+
+        Self {
+            weight: weight.map(|x| x.into_inner()),
+            max: max.iter().map(|x| x.to_string()).collect(),
+            latency: latency.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+}
+
+impl From<&super::cells::cgroups::pids::PidsController> for PidsController {
+    fn from(value: &super::cells::cgroups::PidsController) -> Self {
+        let super::cells::cgroups::PidsController { max } = value.clone();
+        Self { max: max.map(|x| x.into_inner()) }
+    }
+}
+
+impl From<super::cells::cgroups::PidsStats> for PidsStats {
+    fn from(value: super::cells::cgroups::PidsStats) -> Self {
+        let super::cells::cgroups::PidsStats { current, max } = value;
+        Self { current, max }
+    }
+}
+
+impl From<&super::cells::cgroups::hugetlb::HugetlbController>
+    for HugetlbController
+{
+    fn from(value: &super::cells::cgroups::HugetlbController) -> Self {
+        let super::cells::cgroups::HugetlbController { max } = value;
+        Self { max: max.iter().map(|x| x.to_string()).collect() }
+    }
+}
+
+impl From<&super::cells::cgroups::rdma::RdmaController> for RdmaController {
+    fn from(value: &super::cells::cgroups::RdmaController) -> Self {
+        let super::cells::cgroups::RdmaController { max } = value;
+        Self {
+            max: max
+                .iter()
+                .map(|x| RdmaLimit {
+                    device: x.device.clone(),
+                    hca_handle: x.hca_handle,
+                    hca_object: x.hca_object,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&super::cells::IdMap> for IdMapping {
+    fn from(value: &super::cells::IdMap) -> Self {
+        let super::cells::IdMap { inside, outside, count } = value;
+        Self { inside_id: *inside, outside_id: *outside, count: *count }
+    }
+}
+
+impl From<&super::cells::Mount> for Mount {
+    fn from(value: &super::cells::Mount) -> Self {
+        match value {
+            super::cells::Mount::Bind { source, target, readonly } => Self {
+                r#type: MountType::Bind as i32,
+                source: source.to_string_lossy().to_string(),
+                target: target.to_string_lossy().to_string(),
+                readonly: *readonly,
+                size: None,
+            },
+            super::cells::Mount::Tmpfs { target, size } => Self {
+                r#type: MountType::Tmpfs as i32,
+                source: String::new(),
+                target: target.to_string_lossy().to_string(),
+                readonly: false,
+                size: *size,
+            },
+        }
+    }
+}
+
+impl From<&super::executables::SecurityProfile> for SecurityProfile {
+    fn from(value: &super::executables::SecurityProfile) -> Self {
+        let super::executables::SecurityProfile {
+            capabilities,
+            no_new_privs,
+            seccomp,
+        } = value;
+
+        // Sorted, so that the same profile is always listed the same way
+        let names = |set: &caps::CapsHashSet| {
+            let mut names: Vec<String> =
+                set.iter().map(|x| x.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        Self {
+            capabilities: capabilities.as_ref().map(|x| Capabilities {
+                bounding: names(&x.bounding),
+                ambient: names(&x.ambient),
+            }),
+            no_new_privs: *no_new_privs,
+            seccomp: seccomp.as_ref().map(|x| x.json().to_string()),
+        }
+    }
+}
+
+impl From<super::executables::ExecutableStatus> for ExecutableStatus {
+    fn from(value: super::executables::ExecutableStatus) -> Self {
+        let super::executables::ExecutableStatus {
+            name,
+            phase,
+            pid,
+            started_at,
+            exit,
+            restart_count,
+        } = value;
+
+        let state = match phase {
+            ExecutablePhase::Init => ExecutableState::Unspecified,
+            ExecutablePhase::Running => ExecutableState::Running,
+            ExecutablePhase::Restarting => ExecutableState::Restarting,
+            ExecutablePhase::CrashLoop => ExecutableState::CrashLoop,
+            ExecutablePhase::Exited => ExecutableState::Exited,
+        };
+
+        Self {
+            name: name.to_string(),
+            pid: pid.map(|x| x.as_raw()).unwrap_or_default(),
+            state: state.into(),
+            exit_status: exit.map(|x| ExitStatus {
+                code: x.status.code(),
+                signal: x.status.signal(),
+            }),
+            started_at: started_at.map(unix_nanos).unwrap_or_default(),
+            exited_at: exit.map(|x| unix_nanos(x.exited_at)),
+            restart_count,
+        }
+    }
+}
+
+/// Adopts the cell of `record`, after a restart of auraed.
+fn adopt_cell(
+    cells: &mut Cells,
+    record: &CellRecord,
+) -> std::result::Result<(), Status> {
+    let cell = ValidatedCell::validate_persisted(record.cell.clone())?;
+    let cell_name = cell.name.clone();
+
+    let cell =
+        super::cells::Cell::adopt(cell_name, cell.into(), &record.allocation)
+            .map_err(CellsServiceError::from)?;
+    cells.adopt(cell).map_err(CellsServiceError::from)?;
+
+    Ok(())
+}
+
+/// Returns the records of a cell and its descendants, parents first.
+fn cell_records(
+    cell: &super::cells::Cell,
+) -> std::result::Result<Vec<CellRecord>, CellsError> {
+    let Some(allocation) = cell.allocation() else {
+        return Ok(vec![]);
+    };
+
+    let mut records = vec![CellRecord { cell: cell.into(), allocation }];
+    for children in CellsCache::get_all(cell, cell_records)?.into_iter() {
+        records.extend(children?);
+    }
+
+    Ok(records)
+}
+
+/// Removes the nested cells that are more than `depth` levels below `nodes`.
+fn limit_depth(nodes: &mut [CellGraphNode], depth: u32) {
+    for node in nodes {
+        if depth == 0 {
+            node.children.clear();
+        } else {
+            limit_depth(&mut node.children, depth - 1);
+        }
+    }
+}
+
+/// Keeps the cells whose name matches `pattern`, and their parents.
+fn retain_matching(nodes: &mut Vec<CellGraphNode>, pattern: &str) {
+    nodes.retain_mut(|node| {
+        retain_matching(&mut node.children, pattern);
+        !node.children.is_empty()
+            || node
+                .cell
+                .as_ref()
+                .is_some_and(|cell| matches_pattern(pattern, &cell.name))
+    });
+}
+
+/// Matches `name` against a `pattern` where `*` matches any sequence of characters, and `?`
+/// matches any single character.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // The position after the last `*` in the pattern, and where it started matching in the name
+    let mut last_star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                last_star = Some((p, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // Let the last `*` match one more character
+            _ => match last_star {
+                Some((star_p, star_n)) => {
+                    p = star_p;
+                    n = star_n + 1;
+                    last_star = Some((star_p, n));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Collects the names of the cells of the graph, parents first.
+fn cell_names(nodes: &[CellGraphNode], names: &mut Vec<String>) {
+    for node in nodes {
+        if let Some(cell) = &node.cell {
+            names.push(cell.name.clone());
+        }
+        cell_names(&node.children, names);
+    }
+}
+
+/// Sets the executables of the cells of the graph, or why they could not be listed.
+fn set_executables(
+    nodes: &mut [CellGraphNode],
+    executables: &mut HashMap<
+        String,
+        std::result::Result<Vec<ExecutableStatus>, String>,
+    >,
+) {
+    for node in nodes {
+        let res = node.cell.as_ref().and_then(|x| executables.remove(&x.name));
+        match res {
+            Some(Ok(executables)) => node.executables = executables,
+            Some(Err(e)) => node.executables_error = Some(e),
+            None => {}
+        }
+        set_executables(&mut node.children, executables);
+    }
+}
+
+/// Lists the executables of a cell from its nested auraed. Unlike `do_in_cell!`, the nested
+/// auraed is not retried, so that a cell that does not respond does not hold up the listing.
+async fn executables_in_cell(
+    client_socket: AuraeSocket,
+) -> std::result::Result<Vec<ExecutableStatus>, String> {
+    let list = async {
+        let client = Client::new_no_tls(client_socket)
+            .await
+            .map_err(|e| format!("failed to connect to nested auraed: {e}"))?;
+        let res = client
+            .list_executables(CellServiceListExecutablesRequest {
+                cell_name: None,
+            })
+            .await
+            .map_err(|e| e.message().to_string())?;
+        Ok::<_, String>(res.into_inner().executables)
+    };
+
+    tokio::time::timeout(LIST_EXECUTABLES_TIMEOUT, list).await.unwrap_or_else(
+        |_| Err("timed out listing executables of nested auraed".into()),
+    )
+}
+
+/// Removes the sockets, and socket directories, of nested auraeds in `runtime_dir` that are
+/// not in `sockets`. They are left behind by nested auraeds that were not freed.
+fn remove_stale_sockets(runtime_dir: &Path, sockets: &HashSet<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(runtime_dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|x| x.ok()) {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        // See NestedAuraed::new for the naming of the sockets
+        let Some(id) = file_name.strip_prefix("aurae-") else {
+            continue;
+        };
+        let is_dir = entry.file_type().is_ok_and(|x| x.is_dir());
+        let (id, socket) = if is_dir {
+            (id, path.join("aurae.sock"))
+        } else if let Some(id) = id.strip_suffix(".sock") {
+            (id, path.clone())
+        } else {
+            continue;
+        };
+
+        if uuid::Uuid::parse_str(id).is_err() || sockets.contains(&socket) {
+            continue;
+        }
+
+        info!("Removing stale socket {}", socket.display());
+        let res = if is_dir {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if let Err(e) = res {
+            warn!("Failed to remove stale socket {}: {e}", socket.display());
+        }
+    }
+}
+
+/// Registers the log channels of an executable with the observe service.
+async fn register_log_channels(
+    observe_service: &ObserveService,
+    pid: i32,
+    stdout: &LogChannel,
+    stderr: &LogChannel,
+) {
+    // Register the stdout log channel for the executable's PID
+    if let Err(e) = observe_service
+        .register_sub_process_channel(
+            pid,
+            LogChannelType::Stdout,
+            stdout.clone(),
+        )
+        .await
+    {
+        warn!("failed to register stdout channel for pid {pid}: {e}");
+    }
+
+    // Register the stderr log channel for the executable's PID
+    if let Err(e) = observe_service
+        .register_sub_process_channel(
+            pid,
+            LogChannelType::Stderr,
+            stderr.clone(),
+        )
+        .await
+    {
+        warn!("failed to register stderr channel for pid {pid}: {e}");
+    }
+}
+
+/// Removes the log channels of an executable from the observe service.
+async fn unregister_log_channels(observe_service: &ObserveService, pid: i32) {
+    if let Err(e) = observe_service
+        .unregister_sub_process_channel(pid, LogChannelType::Stdout)
+        .await
+    {
+        warn!("failed to unregister stdout channel for pid {pid}: {e}");
+    }
+    if let Err(e) = observe_service
+        .unregister_sub_process_channel(pid, LogChannelType::Stderr)
+        .await
+    {
+        warn!("failed to unregister stderr channel for pid {pid}: {e}");
+    }
+}
+
+/// Converts a [SystemTime] to nanoseconds since the Unix epoch.
+fn unix_nanos(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|x| x.as_nanos() as i64)
+        .unwrap_or_default()
+}
+
+/// ### Mapping cgroup options to the Cell API
+///
+/// Here we *only* expose options from the CgroupBuilder
+/// as our features in Aurae need them! We do not try to
+/// "map" everything as much as we start with a few small
+/// features and add as needed.
+///
+// Example builder options can be found: https://github.com/kata-containers/cgroups-rs/blob/main/tests/builder.rs
+// Cgroup documentation: https://man7.org/linux/man-pages/man7/cgroups.7.html
+#[tonic::async_trait]
+impl cell_service_server::CellService for CellService {
+    async fn allocate(
+        &self,
+        request: Request<CellServiceAllocateRequest>,
+    ) -> std::result::Result<Response<CellServiceAllocateResponse>, Status>
+    {
+        // Extract the inner request from the request
+        let request = request.into_inner();
+        // Validate the allocate request
+        let request = ValidatedCellServiceAllocateRequest::validate(
+            request.clone(),
+            None,
+        )?;
+
+        // return the allocated cell
+        Ok(Response::new(self.allocate(request).await?))
+    }
+
+    async fn free(
+        &self,
+        request: Request<CellServiceFreeRequest>,
+    ) -> std::result::Result<Response<CellServiceFreeResponse>, Status> {
+        let request = request.into_inner();
+        // Validate the free request
+        let request =
+            ValidatedCellServiceFreeRequest::validate(request.clone(), None)?;
+
+        // free the cell
+        Ok(Response::new(self.free(request).await?))
+    }
+
+    async fn update(
+        &self,
+        request: Request<CellServiceUpdateRequest>,
+    ) -> std::result::Result<Response<CellServiceUpdateResponse>, Status> {
+        let request = request.into_inner();
+        // Validate the update request
+        let request =
+            ValidatedCellServiceUpdateRequest::validate(request.clone(), None)?;
+
+        // update the cell
+        Ok(Response::new(self.update(request).await?))
+    }
+
+    async fn start(
+        &self,
+        request: Request<CellServiceStartRequest>,
+    ) -> std::result::Result<Response<CellServiceStartResponse>, Status> {
+        let request = request.into_inner();
+
+        // Execute start if cell_name is none
+        if request.cell_name.is_none() {
+            self.start(request, false).await
+        } else {
+            // We are in a parent cell, or validation will fail
             let validated = ValidatedCellServiceStartRequest::validate(
                 request.clone(),
                 None,
@@ -541,18 +1760,199 @@ impl cell_service_server::CellService for CellService {
         }
     }
 
+    async fn wait(
+        &self,
+        request: Request<CellServiceWaitRequest>,
+    ) -> std::result::Result<Response<CellServiceWaitResponse>, Status> {
+        let request = request.into_inner();
+
+        // Execute wait if cell_name is none
+        if request.cell_name.is_none() {
+            let request =
+                ValidatedCellServiceWaitRequest::validate(request, None)?;
+            Ok(self.wait(request).await?)
+        } else {
+            // Validate the request is valid
+            let validated = ValidatedCellServiceWaitRequest::validate(
+                request.clone(),
+                None,
+            )?;
+
+            // Validation has succeeded, so we can make assumptions about the request and use expect
+            let cell_name = validated.cell_name.expect("cell name");
+            let mut request = request;
+            request.cell_name = None;
+
+            // wait in the cell
+            self.wait_in_cell(&cell_name, request).await
+        }
+    }
+
+    async fn signal(
+        &self,
+        request: Request<CellServiceSignalRequest>,
+    ) -> std::result::Result<Response<CellServiceSignalResponse>, Status> {
+        let request = request.into_inner();
+
+        // Signal a local executable if cell_name is none
+        if request.cell_name.is_none() {
+            let request =
+                ValidatedCellServiceSignalRequest::validate(request, None)?;
+            Ok(Response::new(self.signal(request).await?))
+        } else {
+            // Validate the request is valid
+            let validated = ValidatedCellServiceSignalRequest::validate(
+                request.clone(),
+                None,
+            )?;
+
+            // Validation has succeeded, so we can make assumptions about the request and use expect
+            let cell_name = validated.cell_name.expect("cell name");
+
+            // Without an executable, every process in the cell is signaled from here, where the
+            // cgroup of the cell is managed
+            if validated.executable_name.is_none() {
+                return Ok(Response::new(
+                    self.signal_cell(&cell_name, validated.signal).await?,
+                ));
+            }
+
+            let mut request = request;
+            request.cell_name = None;
+
+            // signal the executable in the cell
+            self.signal_in_cell(&cell_name, request).await
+        }
+    }
+
+    async fn pause(
+        &self,
+        request: Request<CellServicePauseRequest>,
+    ) -> std::result::Result<Response<CellServicePauseResponse>, Status> {
+        let request = request.into_inner();
+        // Validate the pause request
+        let request =
+            ValidatedCellServicePauseRequest::validate(request.clone(), None)?;
+
+        // pause the cell
+        Ok(Response::new(self.pause(request).await?))
+    }
+
+    async fn resume(
+        &self,
+        request: Request<CellServiceResumeRequest>,
+    ) -> std::result::Result<Response<CellServiceResumeResponse>, Status> {
+        let request = request.into_inner();
+        // Validate the resume request
+        let request =
+            ValidatedCellServiceResumeRequest::validate(request.clone(), None)?;
+
+        // resume the cell
+        Ok(Response::new(self.resume(request).await?))
+    }
+
     /// Response with a list of cells
     ///
     /// # Arguments
-    /// * `_request` - A request containing CellServiceListRequest.
+    /// * `request` - A request containing CellServiceListRequest.
     ///
     /// # Returns
     /// A response containing CellServiceListResponse or a Status error.
     async fn list(
         &self,
-        _request: Request<CellServiceListRequest>,
+        request: Request<CellServiceListRequest>,
     ) -> std::result::Result<Response<CellServiceListResponse>, Status> {
-        Ok(Response::new(self.list().await?))
+        let request = request.into_inner();
+        let request = ValidatedCellServiceListRequest::validate(request, None)?;
+
+        Ok(Response::new(self.list(request).await?))
+    }
+
+    async fn list_executables(
+        &self,
+        request: Request<CellServiceListExecutablesRequest>,
+    ) -> std::result::Result<Response<CellServiceListExecutablesResponse>, Status>
+    {
+        let request = request.into_inner();
+
+        // List local executables if cell_name is none
+        if request.cell_name.is_none() {
+            let request = ValidatedCellServiceListExecutablesRequest::validate(
+                request, None,
+            )?;
+            Ok(Response::new(self.list_executables(request).await?))
+        } else {
+            let validated =
+                ValidatedCellServiceListExecutablesRequest::validate(
+                    request.clone(),
+                    None,
+                )?;
+
+            // Validation has succeeded, so we can make assumptions about the request and use expect
+            let cell_name = validated.cell_name.expect("cell name");
+            let mut request = request;
+            request.cell_name = None;
+
+            self.list_executables_in_cell(&cell_name, request).await
+        }
+    }
+
+    async fn stats(
+        &self,
+        request: Request<CellServiceStatsRequest>,
+    ) -> std::result::Result<Response<CellServiceStatsResponse>, Status> {
+        let request = request.into_inner();
+        let ValidatedCellServiceStatsRequest { cell_name } =
+            ValidatedCellServiceStatsRequest::validate(request, None)?;
+
+        let cells = self.stats(cell_name.as_ref()).await?;
+        Ok(Response::new(CellServiceStatsResponse { cells }))
+    }
+
+    type WatchStatsStream = ReceiverStream<
+        std::result::Result<CellServiceWatchStatsResponse, Status>,
+    >;
+
+    async fn watch_stats(
+        &self,
+        request: Request<CellServiceWatchStatsRequest>,
+    ) -> std::result::Result<Response<Self::WatchStatsStream>, Status> {
+        let request = request.into_inner();
+        let request =
+            ValidatedCellServiceWatchStatsRequest::validate(request, None)?;
+
+        // Fail early rather than in the stream if the cell can't be read
+        let _ = self.stats(request.cell_name.as_ref()).await?;
+
+        Ok(Response::new(self.watch_stats(request).await))
+    }
+
+    type ExecStream = ExecResponseStream;
+
+    async fn exec(
+        &self,
+        request: Request<Streaming<CellServiceExecRequest>>,
+    ) -> std::result::Result<Response<Self::ExecStream>, Status> {
+        let mut requests = request.into_inner();
+
+        // The first request starts the command
+        let start =
+            requests.message().await?.and_then(|x| x.start).ok_or_else(
+                || ValidationError::Required { field: "start".into() },
+            )?;
+
+        let validated = ValidatedCellServiceExecStart::validate(
+            start.clone(),
+            Some("start"),
+        )?;
+
+        // Run the command here if cell_name is none
+        match validated.cell_name {
+            None => Ok(Response::new(self.exec(validated, requests).await?)),
+            Some(cell_name) => Ok(Response::new(
+                self.exec_in_cell(&cell_name, start, requests).await?,
+            )),
+        }
     }
 }
 
@@ -562,7 +1962,7 @@ mod tests {
     use crate::{
         cells::cell_service::validation::{
             ValidatedCell, ValidatedCpuController, ValidatedCpusetController,
-            ValidatedMemoryController,
+            ValidatedIoController, ValidatedMemoryController,
         },
         logging::log_channel::LogChannel,
     };
@@ -609,7 +2009,14 @@ mod tests {
             .is_ok());
 
         // List all cells and verify the result
-        let result = service.list().await;
+        let result = service
+            .list(ValidatedCellServiceListRequest {
+                cell_name: None,
+                depth: None,
+                name_pattern: None,
+                include_executables: false,
+            })
+            .await;
         assert!(result.is_ok());
 
         let list = result.unwrap();
@@ -645,6 +2052,79 @@ mod tests {
         assert_eq!(actual_nested_cell_names, expected_nested_cell_names);
     }
 
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("app", "app"));
+        assert!(!matches_pattern("app", "app/web"));
+        assert!(matches_pattern("app/*", "app/web"));
+        assert!(matches_pattern("*/web-*", "app/nested/web-1"));
+        assert!(matches_pattern("a?p*", "app"));
+        assert!(matches_pattern("*", ""));
+        assert!(!matches_pattern("?", ""));
+        assert!(!matches_pattern("*/web", "app/web-1"));
+    }
+
+    #[test]
+    fn test_list_filters() {
+        fn node(name: &str, children: Vec<CellGraphNode>) -> CellGraphNode {
+            CellGraphNode {
+                cell: Some(Cell { name: name.into(), ..Default::default() }),
+                children,
+                ..Default::default()
+            }
+        }
+
+        fn names(nodes: &[CellGraphNode]) -> Vec<String> {
+            let mut names = vec![];
+            cell_names(nodes, &mut names);
+            names
+        }
+
+        let cells = vec![
+            node(
+                "app",
+                vec![node("app/web", vec![node("app/web/sidecar", vec![])])],
+            ),
+            node("db", vec![]),
+        ];
+
+        let mut limited = cells.clone();
+        limit_depth(&mut limited, 1);
+        assert_eq!(names(&limited), ["app", "app/web", "db"]);
+
+        limit_depth(&mut limited, 0);
+        assert_eq!(names(&limited), ["app", "db"]);
+
+        // Parents of matching cells are kept
+        let mut matching = cells.clone();
+        retain_matching(&mut matching, "*/sidecar");
+        assert_eq!(names(&matching), ["app", "app/web", "app/web/sidecar"]);
+
+        let mut matching = cells;
+        retain_matching(&mut matching, "d?");
+        assert_eq!(names(&matching), ["db"]);
+    }
+
+    /// Test that an io controller is listed the way it was allocated.
+    #[test]
+    fn test_io_controller_round_trip() {
+        let io = IoController {
+            weight: Some(50),
+            max: vec![
+                "8:0 rbps=1048576 wiops=120".into(),
+                "8:16 riops=10".into(),
+            ],
+            latency: vec!["8:0 target=75".into()],
+        };
+
+        let validated = ValidatedIoController::validate(io.clone(), None)
+            .expect("valid io controller");
+        let controller: crate::cells::cell_service::cells::cgroups::IoController =
+            validated.into();
+
+        assert_eq!(IoController::from(&controller), io);
+    }
+
     /// Helper function to create a ValidatedCellServiceAllocateRequest.
     ///
     /// # Arguments
@@ -669,9 +2149,22 @@ mod tests {
                 low: None,
                 high: None,
                 max: None,
+                swap_max: None,
+                swap_high: None,
+                oom_group: None,
             }),
+            io: None,
+            pids: None,
+            hugetlb: None,
+            rdma: None,
             isolate_process: false,
             isolate_network: false,
+            isolate_user: false,
+            uid_map: vec![],
+            gid_map: vec![],
+            rootfs: None,
+            mounts: vec![],
+            security_profile: None,
         };
         // Return the validated allocate request
         ValidatedCellServiceAllocateRequest { cell }
@@ -14,10 +14,21 @@
 \* -------------------------------------------------------------------------- */
 
 use super::{
-    cgroups::Cgroup, nested_auraed::NestedAuraed, CellName, CellSpec, Cells,
-    CellsCache, CellsError, Result,
+    cgroups::{Cgroup, CgroupStats, PidsStats},
+    nested_auraed::NestedAuraed,
+    network::CellNetwork,
+    CellAllocation, CellName, CellSpec, Cells, CellsCache, CellsError,
+    CgroupSpec, Result,
 };
+use crate::AURAED_RUNTIME;
+use anyhow::anyhow;
 use client::AuraeSocket;
+use ipnetwork::IpNetwork;
+use nix::{
+    errno::Errno,
+    sys::signal::{self, Signal},
+    unistd::Pid,
+};
 use tracing::info;
 
 // TODO https://github.com/aurae-runtime/aurae/issues/199 &&
@@ -30,8 +41,12 @@ macro_rules! do_free {
         $nested_auraed_call:ident($($nested_auraed_call_arg:ident),*),
         $($children_call:ident($($children_call_arg:ident),*)),*
     ) => {{
-        if let CellState::Allocated { cgroup, nested_auraed, children } =
-            &mut $self.state
+        if let CellState::Allocated {
+            cgroup,
+            nested_auraed,
+            children,
+            network,
+        } = &mut $self.state
         {
             $(children.$children_call($($children_call_arg),*));*;
 
@@ -44,6 +59,10 @@ macro_rules! do_free {
                     }
                 })?;
 
+            if let Some(network) = network.take() {
+                let _best_effort = network.delete();
+            }
+
             cgroup.delete().map_err(|e| CellsError::FailedToFreeCell {
                 cell_name: $self.cell_name.clone(),
                 source: e,
@@ -57,8 +76,9 @@ macro_rules! do_free {
     }};
 }
 
-// We should not be able to change a cell after it has been created.
-// You must free the cell and create a new one if you want to change anything about the cell.
+// We should not be able to change a cell after it has been created, other than
+// updating its cgroup (see [Cell::update]).
+// You must free the cell and create a new one if you want to change anything else about the cell.
 // In order to facilitate that immutability:
 // NEVER MAKE THE FIELDS PUB (OF ANY KIND)
 #[derive(Debug)]
@@ -72,7 +92,12 @@ pub struct Cell {
 #[derive(Debug)]
 enum CellState {
     Unallocated,
-    Allocated { cgroup: Cgroup, nested_auraed: NestedAuraed, children: Cells },
+    Allocated {
+        cgroup: Cgroup,
+        nested_auraed: NestedAuraed,
+        children: Cells,
+        network: Option<CellNetwork>,
+    },
     Freed,
 }
 
@@ -91,11 +116,20 @@ impl Cell {
 
         let name = self.cell_name.leaf().to_string();
 
-        let mut auraed = NestedAuraed::new(name, self.spec.iso_ctl.clone())
-            .map_err(|e| CellsError::FailedToAllocateCell {
-                cell_name: self.cell_name.clone(),
-                source: e,
-            })?;
+        // Each nested auraed keeps the logs of its executables in its own directory
+        let logs_dir = AURAED_RUNTIME
+            .get()
+            .expect("runtime")
+            .logs_dir
+            .join("cells")
+            .join(self.cell_name.as_inner());
+
+        let mut auraed =
+            NestedAuraed::new(name, logs_dir, self.spec.iso_ctl.clone())
+                .map_err(|e| CellsError::FailedToAllocateCell {
+                    cell_name: self.cell_name.clone(),
+                    source: e,
+                })?;
 
         let pid = auraed.pid();
 
@@ -126,15 +160,84 @@ impl Cell {
 
         info!("Attach nested Auraed pid {} to cgroup {}", pid, self.cell_name);
 
+        let network = if self.spec.iso_ctl.isolate_network {
+            match CellNetwork::new(&self.cell_name, pid) {
+                Ok(network) => Some(network),
+                Err(e) => {
+                    let _best_effort = auraed.kill();
+                    let _best_effort = cgroup.delete();
+
+                    return Err(CellsError::FailedToSetUpCellNetwork {
+                        cell_name: self.cell_name.clone(),
+                        source: e,
+                    });
+                }
+            }
+        } else {
+            None
+        };
+
         self.state = CellState::Allocated {
             cgroup,
             nested_auraed: auraed,
             children: Cells::new(self.cell_name.clone()),
+            network,
         };
 
         Ok(())
     }
 
+    /// Takes ownership of a [Cell] allocated by a previous run of auraed, whose [NestedAuraed]
+    /// and cgroup outlived it. The [Cell] is adopted without its child cells, which are adopted
+    /// separately.
+    pub fn adopt(
+        cell_name: CellName,
+        cell_spec: CellSpec,
+        allocation: &CellAllocation,
+    ) -> Result<Self> {
+        let Some(cgroup) = Cgroup::open(cell_name.clone()) else {
+            return Err(CellsError::FailedToAdoptCell {
+                cell_name,
+                source: anyhow!("cgroup not found"),
+            });
+        };
+
+        let nested_auraed = NestedAuraed::adopt(
+            allocation.nested_auraed_pid,
+            allocation.nested_auraed_start_time,
+            AuraeSocket::Path(allocation.socket.clone()),
+            cell_spec.iso_ctl.clone(),
+        )
+        .map_err(|e| CellsError::FailedToAdoptCell {
+            cell_name: cell_name.clone(),
+            source: e.into(),
+        })?;
+
+        let network = allocation.network(&cell_name).map_err(|e| {
+            CellsError::FailedToAdoptCell {
+                cell_name: cell_name.clone(),
+                source: e,
+            }
+        })?;
+
+        info!(
+            "Adopted cell {} with nested Auraed pid {}",
+            cell_name,
+            nested_auraed.pid()
+        );
+
+        Ok(Self {
+            cell_name: cell_name.clone(),
+            spec: cell_spec,
+            state: CellState::Allocated {
+                cgroup,
+                nested_auraed,
+                children: Cells::new(cell_name),
+                network,
+            },
+        })
+    }
+
     /// Broadcasts a graceful shutdown signal to all [NestedAuraed] and
     /// deletes the underlying cgroup and all descendants.
     ///
@@ -142,6 +245,11 @@ impl Cell {
     ///
     /// A [Cell] should never be reused once in the [CellState::Freed] state.
     pub fn free(&mut self) -> Result<()> {
+        // A frozen nested auraed can't shut down gracefully
+        if self.frozen().unwrap_or(false) {
+            self.resume()?;
+        }
+
         do_free!(self, shutdown(), broadcast_free())
     }
 
@@ -152,6 +260,155 @@ impl Cell {
         do_free!(self, kill(), broadcast_kill())
     }
 
+    /// Leaves the [NestedAuraed] and the cgroup of the [Cell] and its descendants running, so
+    /// that the next run of auraed can adopt them.
+    /// The [Cell::state] will be set to [CellState::Freed], so that dropping the [Cell] does not
+    /// kill it.
+    pub fn detach(&mut self) {
+        if let CellState::Allocated { children, .. } = &mut self.state {
+            children.broadcast_detach();
+        }
+
+        self.state = CellState::Freed;
+    }
+
+    /// Replaces the cgroup controllers of the [Cell] with those of `cgroup_spec`.
+    /// Controllers and values that are not set in `cgroup_spec` are reset to the kernel defaults.
+    /// The isolation of the [Cell] can not be updated.
+    pub fn update(&mut self, cgroup_spec: CgroupSpec) -> Result<()> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        cgroup.update(&self.spec.cgroup_spec, cgroup_spec.clone()).map_err(
+            |e| CellsError::FailedToUpdateCell {
+                cell_name: self.cell_name.clone(),
+                source: e,
+            },
+        )?;
+
+        self.spec.cgroup_spec = cgroup_spec;
+
+        Ok(())
+    }
+
+    /// Freezes every process in the [Cell], including the [NestedAuraed] and the processes of
+    /// any child cells. The processes keep their memory until the [Cell] is resumed.
+    pub fn pause(&self) -> Result<()> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        cgroup.freeze().map_err(|e| CellsError::FailedToPauseCell {
+            cell_name: self.cell_name.clone(),
+            source: e,
+        })
+    }
+
+    /// Thaws the processes of a paused [Cell].
+    /// Fails if the [Cell] is still frozen afterwards, as it is paused by a parent cell.
+    pub fn resume(&self) -> Result<()> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        cgroup.thaw().map_err(|e| CellsError::FailedToResumeCell {
+            cell_name: self.cell_name.clone(),
+            source: e,
+        })?;
+
+        // A thawed cgroup stays frozen while one of its ancestors is frozen.
+        if self.frozen()? {
+            return Err(CellsError::CellPaused {
+                cell_name: self.cell_name.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the [Cell] or one of its parent cells is paused.
+    /// Returns false if the [Cell] is not allocated.
+    pub fn frozen(&self) -> Result<bool> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Ok(false);
+        };
+
+        cgroup.frozen().map_err(|e| CellsError::FailedToReadCellEvents {
+            cell_name: self.cell_name.clone(),
+            source: e,
+        })
+    }
+
+    /// Sends `signal` to every process in the cgroup of the [Cell], including the [NestedAuraed]
+    /// and the processes of any child cells. Returns the [Pid]s of the signaled processes.
+    pub fn signal(&self, signal: Signal) -> Result<Vec<Pid>> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        let pids =
+            cgroup.pids().map_err(|e| CellsError::FailedToSignalCell {
+                cell_name: self.cell_name.clone(),
+                source: e.into(),
+            })?;
+
+        let mut signaled = Vec::with_capacity(pids.len());
+        for pid in pids {
+            match signal::kill(pid, signal) {
+                Ok(()) => signaled.push(pid),
+                // ESRCH means the process exited since the pids were read
+                Err(Errno::ESRCH) => {}
+                Err(e) => {
+                    return Err(CellsError::FailedToSignalCell {
+                        cell_name: self.cell_name.clone(),
+                        source: e.into(),
+                    })
+                }
+            }
+        }
+
+        Ok(signaled)
+    }
+
+    /// Returns the resource usage of the [Cell], including that of its child cells.
+    pub fn stats(&self) -> Result<CgroupStats> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        cgroup.stats().map_err(|e| CellsError::FailedToReadCellStats {
+            cell_name: self.cell_name.clone(),
+            source: e,
+        })
+    }
+
+    /// Returns the number of processes in the [Cell] and its limit, including its child cells.
+    pub fn pids_stats(&self) -> Result<PidsStats> {
+        let CellState::Allocated { cgroup, .. } = &self.state else {
+            return Err(CellsError::CellNotAllocated {
+                cell_name: self.cell_name.clone(),
+            });
+        };
+
+        cgroup.pids_stats().map_err(|e| CellsError::FailedToReadCellStats {
+            cell_name: self.cell_name.clone(),
+            source: e,
+        })
+    }
+
+    /// Returns the socket of the [NestedAuraed].
+    /// Fails if the [Cell] is paused, as the [NestedAuraed] can't respond while frozen.
     pub fn client_socket(&self) -> Result<AuraeSocket> {
         let CellState::Allocated { nested_auraed, .. } = &self.state else {
             return Err(CellsError::CellNotAllocated {
@@ -159,9 +416,57 @@ impl Cell {
             })
         };
 
+        if self.frozen()? {
+            return Err(CellsError::CellPaused {
+                cell_name: self.cell_name.clone(),
+            });
+        }
+
         Ok(nested_auraed.client_socket.clone())
     }
 
+    /// Returns the addresses of the [Cell] on the cell bridge.
+    /// Empty if the [Cell] does not isolate its network or is not allocated.
+    pub fn ip_addresses(&self) -> Vec<IpNetwork> {
+        let CellState::Allocated { network: Some(network), .. } = &self.state
+        else {
+            return vec![];
+        };
+
+        network.addresses()
+    }
+
+    /// Returns the [Pid] of the [NestedAuraed], and whether it is still running.
+    /// Returns [None] if the [Cell] is not allocated.
+    pub fn nested_auraed_status(&self) -> Option<(Pid, bool)> {
+        let CellState::Allocated { nested_auraed, .. } = &self.state else {
+            return None;
+        };
+
+        Some((nested_auraed.pid(), nested_auraed.running()))
+    }
+
+    /// Returns what is needed to adopt the [Cell] after a restart of auraed.
+    /// Returns [None] if the [Cell] is not allocated.
+    pub fn allocation(&self) -> Option<CellAllocation> {
+        let CellState::Allocated { nested_auraed, network, .. } = &self.state
+        else {
+            return None;
+        };
+
+        let AuraeSocket::Path(socket) = &nested_auraed.client_socket else {
+            return None;
+        };
+
+        Some(CellAllocation {
+            nested_auraed_pid: nested_auraed.pid().as_raw(),
+            nested_auraed_start_time: nested_auraed.start_time().ok()?,
+            socket: socket.clone(),
+            host_iface: network.as_ref().map(|x| x.host_iface().to_string()),
+            address: network.as_ref().map(|x| x.address().to_string()),
+        })
+    }
+
     /// Returns the [CellName] of the [Cell]
     pub fn name(&self) -> &CellName {
         &self.cell_name
@@ -194,6 +499,14 @@ impl CellsCache for Cell {
         children.allocate(cell_name, cell_spec)
     }
 
+    fn adopt(&mut self, cell: Cell) -> Result<()> {
+        let CellState::Allocated { children, .. } = &mut self.state else {
+            return Err(CellsError::CellNotAllocated { cell_name: self.cell_name.clone() })
+        };
+
+        children.adopt(cell)
+    }
+
     fn free(&mut self, cell_name: &CellName) -> Result<()> {
         let CellState::Allocated { children, .. } = &mut self.state else {
             return Err(CellsError::CellNotAllocated { cell_name: self.cell_name.clone() })
@@ -202,6 +515,18 @@ impl CellsCache for Cell {
         children.free(cell_name)
     }
 
+    fn update(
+        &mut self,
+        cell_name: &CellName,
+        cgroup_spec: CgroupSpec,
+    ) -> Result<()> {
+        let CellState::Allocated { children, .. } = &mut self.state else {
+            return Err(CellsError::CellNotAllocated { cell_name: self.cell_name.clone() })
+        };
+
+        children.update(cell_name, cgroup_spec)
+    }
+
     fn get<F, R>(&mut self, cell_name: &CellName, f: F) -> Result<R>
     where
         F: Fn(&Cell) -> Result<R>,
@@ -239,6 +564,14 @@ impl CellsCache for Cell {
 
         children.broadcast_kill()
     }
+
+    fn broadcast_detach(&mut self) {
+        let CellState::Allocated { children, .. } = &mut self.state else {
+            return;
+        };
+
+        children.broadcast_detach()
+    }
 }
 
 impl Drop for Cell {
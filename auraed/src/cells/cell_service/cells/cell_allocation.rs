@@ -0,0 +1,97 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use super::{cgroups::Cgroup, network::CellNetwork, CellName};
+use ipnetwork::Ipv4Network;
+use nix::{
+    sys::signal::{self, Signal},
+    unistd::Pid,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The processes and host resources of an allocated [Cell], which outlive a restart of
+/// auraed. It is persisted so that the [Cell] can be adopted again, see [Cell::adopt].
+///
+/// [Cell]: super::Cell
+/// [Cell::adopt]: super::Cell::adopt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellAllocation {
+    /// The host pid of the nested auraed.
+    pub nested_auraed_pid: i32,
+    /// When the nested auraed started after system boot, in clock ticks.
+    /// It tells the nested auraed apart from a later process reusing its pid.
+    pub nested_auraed_start_time: u64,
+    /// The socket the nested auraed listens on.
+    pub socket: PathBuf,
+    /// The host end of the veth pair of a network isolated cell.
+    pub host_iface: Option<String>,
+    /// The address leased to a network isolated cell.
+    pub address: Option<String>,
+}
+
+impl CellAllocation {
+    /// Returns the network of the cell, if it isolates its network.
+    pub(super) fn network(
+        &self,
+        cell_name: &CellName,
+    ) -> anyhow::Result<Option<CellNetwork>> {
+        let (Some(host_iface), Some(address)) =
+            (&self.host_iface, &self.address)
+        else {
+            return Ok(None);
+        };
+
+        let address: Ipv4Network = address.parse()?;
+        CellNetwork::adopt(cell_name, host_iface.clone(), address).map(Some)
+    }
+
+    /// Returns true if both the nested auraed and the cgroup of the cell are still there.
+    /// A cell without either of them can't be adopted, and is only left to be released.
+    pub fn is_running(&self, cell_name: &CellName) -> bool {
+        self.nested_auraed_running()
+            && Cgroup::open(cell_name.clone()).is_some()
+    }
+
+    /// Cleans up what is left of a cell that could not be adopted.
+    /// The nested auraed is killed if it is still running, the network of the cell is deleted,
+    /// and its cgroup is deleted. Errors are ignored, as any of them may already be gone.
+    ///
+    /// The cgroups of child cells must be released first.
+    pub fn release(&self, cell_name: &CellName) {
+        if self.nested_auraed_running() {
+            let _best_effort = signal::kill(
+                Pid::from_raw(self.nested_auraed_pid),
+                Signal::SIGKILL,
+            );
+        }
+
+        if let Ok(Some(network)) = self.network(cell_name) {
+            let _best_effort = network.delete();
+        }
+
+        // libcgroups kills any processes left in the cgroup
+        if let Some(cgroup) = Cgroup::open(cell_name.clone()) {
+            let _best_effort = cgroup.delete();
+        }
+    }
+
+    /// Returns true if the nested auraed is running, and its pid has not been reused since.
+    fn nested_auraed_running(&self) -> bool {
+        procfs::process::Process::new(self.nested_auraed_pid)
+            .and_then(|process| process.stat())
+            .is_ok_and(|stat| stat.starttime == self.nested_auraed_start_time)
+    }
+}
@@ -13,7 +13,9 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
-use super::{cgroups::Cgroup, Cell, CellName, CellSpec, CellsError, Result};
+use super::{
+    cgroups::Cgroup, Cell, CellName, CellSpec, CellsError, CgroupSpec, Result,
+};
 use crate::cells::cell_service::cells::cells_cache::CellsCache;
 use std::collections::HashMap;
 use tracing::warn;
@@ -97,6 +99,20 @@ impl Cells {
         })
     }
 
+    fn adopt(&mut self, cell: Cell) -> Result<()> {
+        let cell_name = cell.name().clone();
+
+        proxy_if_needed!(self, cell_name, adopt(cell), {
+            if self.cache.contains_key(&cell_name) {
+                return Err(CellsError::CellExists { cell_name });
+            }
+
+            let _ = self.cache.insert(cell_name, cell);
+
+            Ok(())
+        })
+    }
+
     fn free(&mut self, cell_name: &CellName) -> Result<()> {
         proxy_if_needed!(self, cell_name, free(cell_name), {
             self.handle_cgroup_does_not_exist(cell_name)?;
@@ -106,6 +122,16 @@ impl Cells {
         })
     }
 
+    fn update(
+        &mut self,
+        cell_name: &CellName,
+        cgroup_spec: CgroupSpec,
+    ) -> Result<()> {
+        proxy_if_needed!(self, cell_name, update(cell_name, cgroup_spec), {
+            self.get_mut(cell_name, |cell| cell.update(cgroup_spec))
+        })
+    }
+
     fn get<F, R>(&mut self, cell_name: &CellName, f: F) -> Result<R>
     where
         F: Fn(&Cell) -> Result<R>,
@@ -209,6 +235,12 @@ impl Cells {
         }
     }
 
+    fn broadcast_detach(&mut self) {
+        for (_, mut cell) in self.cache.drain() {
+            cell.detach();
+        }
+    }
+
     fn do_broadcast<F>(&mut self, f: F) -> Vec<CellName>
     where
         F: Fn(&mut Cell) -> Result<()>,
@@ -238,10 +270,22 @@ impl CellsCache for Cells {
         self.allocate(cell_name, cell_spec)
     }
 
+    fn adopt(&mut self, cell: Cell) -> Result<()> {
+        self.adopt(cell)
+    }
+
     fn free(&mut self, cell_name: &CellName) -> Result<()> {
         self.free(cell_name)
     }
 
+    fn update(
+        &mut self,
+        cell_name: &CellName,
+        cgroup_spec: CgroupSpec,
+    ) -> Result<()> {
+        self.update(cell_name, cgroup_spec)
+    }
+
     fn get<F, R>(&mut self, cell_name: &CellName, f: F) -> Result<R>
     where
         F: Fn(&Cell) -> Result<R>,
@@ -263,6 +307,10 @@ impl CellsCache for Cells {
     fn broadcast_kill(&mut self) {
         self.broadcast_kill()
     }
+
+    fn broadcast_detach(&mut self) {
+        self.broadcast_detach()
+    }
 }
 
 #[cfg(test)]
@@ -42,7 +42,7 @@
  *                                                                            *
 \* -------------------------------------------------------------------------- */
 
-use super::{Cell, CellName, CellSpec, Result};
+use super::{Cell, CellName, CellSpec, CgroupSpec, Result};
 
 pub trait CellsCache {
     /// Calls [Cell::allocate] on a new [Cell] and adds it to it's cache with key [CellName].
@@ -57,6 +57,13 @@ pub trait CellsCache {
         cell_spec: CellSpec,
     ) -> Result<&Cell>;
 
+    /// Adds a [Cell] adopted with [Cell::adopt] to the cache of its parent.
+    ///
+    /// # Errors
+    /// * If a parent of the cell is not cached -> [CellsError::CellNotFound]
+    /// * If cell exists -> [CellsError::CellExists]
+    fn adopt(&mut self, cell: Cell) -> Result<()>;
+
     /// Calls [Cell::free] on a [Cell] and removes it from the cache.
     ///
     /// # Errors
@@ -67,6 +74,20 @@ pub trait CellsCache {
     /// * If cell fails to free (see [Cell::free])
     fn free(&mut self, cell_name: &CellName) -> Result<()>;
 
+    /// Calls [Cell::update] on a [Cell], replacing its cgroup controllers.
+    ///
+    /// # Errors
+    /// * If cell is not cached and cgroup does not exist -> [CellsError::CellNotFound]
+    /// * If cell is cached and cgroup does not exist -> [CellsError::CgroupNotFound]
+    ///     - note: cell will be removed from cache
+    /// * If cell is not cached and cgroup exists on fs -> [CellsError::CgroupIsNotACell]
+    /// * If cell fails to update (see [Cell::update])
+    fn update(
+        &mut self,
+        cell_name: &CellName,
+        cgroup_spec: CgroupSpec,
+    ) -> Result<()>;
+
     fn get<F, R>(&mut self, cell_name: &CellName, f: F) -> Result<R>
     where
         F: Fn(&Cell) -> Result<R>;
@@ -81,4 +102,7 @@ pub trait CellsCache {
 
     /// Sends a [SIGKILL] to all Cells, ignoring any errors.
     fn broadcast_kill(&mut self);
+
+    /// Calls [Cell::detach] on all cells in the cache, which are removed from the cache.
+    fn broadcast_detach(&mut self);
 }
\ No newline at end of file
@@ -14,21 +14,31 @@
 \* -------------------------------------------------------------------------- */
 
 use crate::cells::cell_service::cells::{
-    cgroups::{CpuController, CpusetController, MemoryController},
+    cgroups::{
+        hugetlb::HugetlbLimit, CgroupStats, CpuController, CpusetController,
+        HugetlbController, IoController, MemoryController, PidsController,
+        PidsStats, RdmaController,
+    },
     CellName, CgroupSpec,
 };
-use libcgroups::common::{CgroupManager, ControllerOpt, DEFAULT_CGROUP_ROOT};
-use libcgroups::stats::Stats;
+use libcgroups::common::{
+    CgroupManager, ControllerOpt, FreezerState, DEFAULT_CGROUP_ROOT,
+};
 use libcgroups::v2;
 use nix::unistd::Pid;
 use oci_spec::runtime::{
-    LinuxCpuBuilder, LinuxMemoryBuilder, LinuxResourcesBuilder,
+    LinuxCpuBuilder, LinuxHugepageLimitBuilder, LinuxMemoryBuilder,
+    LinuxPidsBuilder, LinuxResourcesBuilder,
 };
+use std::fs;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use super::error::{CgroupsError, Result};
 
+/// The period of cpu.max, in microseconds, when none is set.
+const DEFAULT_CPU_PERIOD: u64 = 100_000;
+
 #[derive(Debug)]
 pub struct Cgroup {
     cell_name: CellName,
@@ -40,8 +50,6 @@ impl Cgroup {
         spec: CgroupSpec,
         nested_auraed_pid: Pid,
     ) -> Result<Self> {
-        let CgroupSpec { cpu, cpuset, memory } = spec;
-
         // Note: Cgroups v2 "no internal processes" rule.
         // Docs: https://man7.org/linux/man-pages/man7/cgroups.7.html
         // TLDR: "...with the exception of the root cgroup, processes may reside only
@@ -71,101 +79,59 @@ impl Cgroup {
             });
         }
 
-        let builder = LinuxResourcesBuilder::default();
-
-        // oci_spec, which libcgroups uses, combines the cpu and cpuset controllers
-        let builder = if cpu.is_some() || cpuset.is_some() || memory.is_some() {
-            let cpu_builder = LinuxCpuBuilder::default();
-
-            // cpu controller
-            let cpu_builder =
-                if let Some(CpuController { weight, max, period }) = cpu {
-                    let mut cpu_builder = if let Some(weight) = weight {
-                        cpu_builder.shares(weight.into_inner())
-                    } else {
-                        cpu_builder
-                    };
-
-                    cpu_builder = if let Some(max) = max {
-                        cpu_builder.quota(max.into_inner())
-                    } else {
-                        cpu_builder
-                    };
-
-                    if let Some(period) = period {
-                        cpu_builder.period(period)
-                    } else {
-                        cpu_builder
-                    }
-                } else {
-                    cpu_builder
-                };
+        if let Err(e) = apply(&non_leaf, &cell_name, spec) {
+            // try to remove, but ignore the error as the original error is more appropriate to return
+            // libcgroups takes care of killing any processes it finds
+            let _ = leaf.remove();
+            let _ = non_leaf.remove();
+            return Err(CgroupsError::CreateCgroup { cell_name, source: e });
+        }
 
-            // cpuset controller
-            let cpu_builder =
-                if let Some(CpusetController { cpus, mems }) = cpuset {
-                    let cpu_builder = if let Some(cpus) = cpus {
-                        cpu_builder.cpus(cpus.into_inner())
-                    } else {
-                        cpu_builder
-                    };
-
-                    if let Some(mems) = mems {
-                        cpu_builder.mems(mems.into_inner())
-                    } else {
-                        cpu_builder
-                    }
-                } else {
-                    cpu_builder
-                };
+        Ok(Self { cell_name })
+    }
 
-            let memory_builder = LinuxMemoryBuilder::default();
-            let memory_builder =
-                if let Some(MemoryController { min: _, low, high: _, max }) =
-                    memory
-                {
-                    let memory_builder = if let Some(low) = low {
-                        memory_builder.reservation(low.into_inner())
-                    } else {
-                        memory_builder
-                    };
-
-                    if let Some(max) = max {
-                        memory_builder.limit(max.into_inner())
-                    } else {
-                        memory_builder
-                    }
-                } else {
-                    memory_builder
-                };
+    /// Opens the existing cgroup of a cell, such as one created by a previous run of auraed.
+    /// Returns [None] if the cgroup does not exist.
+    pub fn open(cell_name: CellName) -> Option<Self> {
+        if !Self::exists(&cell_name) {
+            return None;
+        }
 
-            let cpu = cpu_builder.build().expect("valid cpu builder");
-            let memory = memory_builder.build().expect("valid memory builder");
-            builder.cpu(cpu).memory(memory)
-        } else {
-            builder
+        Some(Self { cell_name })
+    }
+
+    /// Replaces the controllers of the cgroup, currently set to `current`, with those of `spec`.
+    /// Values of `current` that are not set in `spec` are reset to the kernel defaults.
+    /// The values of `spec` are applied before the others are reset, so that limits are never
+    /// lifted in between. On failure, the cgroup is rolled back to `current`.
+    pub fn update(&self, current: &CgroupSpec, spec: CgroupSpec) -> Result<()> {
+        let non_leaf = v2::manager::Manager::new(
+            DEFAULT_CGROUP_ROOT.into(),
+            self.cell_name.clone().into_inner(),
+        )
+        .expect("valid cgroup");
+
+        let replace = |from: &CgroupSpec, to: &CgroupSpec| {
+            apply(&non_leaf, &self.cell_name, to.clone()).and_then(|_| {
+                reset(&self.cell_name, from, to).map_err(anyhow::Error::from)
+            })
         };
 
-        let options = builder.build().expect("valid options");
-        let options = ControllerOpt {
-            resources: &options,
-            disable_oom_killer: false,
-            oom_score_adj: None,
-            freezer_state: None,
+        let Err(e) = replace(current, &spec) else {
+            return Ok(());
         };
 
-        if let Err(e) = non_leaf.apply(&options) {
-            // try to remove, but ignore the error as the original error is more appropriate to return
-            // libcgroups takes care of killing any processes it finds
-            let _ = leaf.remove();
-            let _ = non_leaf.remove();
-            return Err(CgroupsError::CreateCgroup {
-                cell_name,
-                source: e.into(),
-            });
-        }
+        let source = match replace(&spec, current) {
+            Ok(()) => e,
+            Err(rollback) => anyhow::anyhow!(
+                "{e}, and rolling back to the previous values failed: {rollback}"
+            ),
+        };
 
-        Ok(Self { cell_name })
+        Err(CgroupsError::UpdateCgroup {
+            cell_name: self.cell_name.clone(),
+            source,
+        })
     }
 
     pub fn add_task(&self, pid: Pid) -> Result<()> {
@@ -205,6 +171,53 @@ impl Cgroup {
         })
     }
 
+    /// Freezes every process in the cgroup, including those in descendant cgroups.
+    /// Returns once the processes are frozen.
+    pub fn freeze(&self) -> Result<()> {
+        let non_leaf = v2::manager::Manager::new(
+            DEFAULT_CGROUP_ROOT.into(),
+            self.cell_name.clone().into_inner(),
+        )
+        .expect("valid cgroup");
+
+        non_leaf.freeze(FreezerState::Frozen).map_err(|e| {
+            CgroupsError::FreezeCgroup {
+                cell_name: self.cell_name.clone(),
+                source: e.into(),
+            }
+        })
+    }
+
+    /// Thaws the processes of a frozen cgroup. Processes stay frozen while an ancestor cgroup
+    /// is frozen.
+    pub fn thaw(&self) -> Result<()> {
+        let non_leaf = v2::manager::Manager::new(
+            DEFAULT_CGROUP_ROOT.into(),
+            self.cell_name.clone().into_inner(),
+        )
+        .expect("valid cgroup");
+
+        non_leaf.freeze(FreezerState::Thawed).map_err(|e| {
+            CgroupsError::ThawCgroup {
+                cell_name: self.cell_name.clone(),
+                source: e.into(),
+            }
+        })
+    }
+
+    /// Returns true if the cgroup is frozen, either by itself or by an ancestor cgroup.
+    pub fn frozen(&self) -> Result<bool> {
+        let path = get_path(&self.cell_name).join("cgroup.events");
+
+        let events =
+            fs::read_to_string(path).map_err(|e| CgroupsError::ReadEvents {
+                cell_name: self.cell_name.clone(),
+                source: e.into(),
+            })?;
+
+        Ok(events.lines().any(|line| line == "frozen 1"))
+    }
+
     pub fn v2(&self) -> bool {
         // Auraed will assume the V2 cgroup hierarchy by default.
         // For now, we do not change this, albeit in theory we could
@@ -214,16 +227,35 @@ impl Cgroup {
         true
     }
 
-    // TODO: use this
-    #[allow(unused)]
-    pub fn stats(&self) -> Result<Stats> {
+    /// Returns the resource usage of the cgroup, including that of descendant cgroups.
+    pub fn stats(&self) -> Result<CgroupStats> {
+        let path = get_path(&self.cell_name);
+
+        CgroupStats::read(&path).map_err(|e| CgroupsError::ReadStats {
+            cell_name: self.cell_name.clone(),
+            source: e.into(),
+        })
+    }
+
+    /// Returns the number of processes in the cgroup and its limit, including descendant cgroups.
+    pub fn pids_stats(&self) -> Result<PidsStats> {
+        let path = get_path(&self.cell_name);
+
+        PidsStats::read(&path).map_err(|e| CgroupsError::ReadStats {
+            cell_name: self.cell_name.clone(),
+            source: e.into(),
+        })
+    }
+
+    /// Returns the pids of every process in the cgroup, including those in descendant cgroups.
+    pub fn pids(&self) -> Result<Vec<Pid>> {
         let non_leaf = v2::manager::Manager::new(
             DEFAULT_CGROUP_ROOT.into(),
             self.cell_name.clone().into_inner(),
         )
         .expect("valid cgroup");
 
-        non_leaf.stats().map_err(|e| CgroupsError::ReadStats {
+        non_leaf.get_all_pids().map_err(|e| CgroupsError::ReadPids {
             cell_name: self.cell_name.clone(),
             source: e.into(),
         })
@@ -237,6 +269,420 @@ impl Cgroup {
     }
 }
 
+/// Applies the controllers of `spec` to the cgroup. Controllers and values that are not set
+/// are left unchanged.
+fn apply(
+    non_leaf: &v2::manager::Manager,
+    cell_name: &CellName,
+    spec: CgroupSpec,
+) -> anyhow::Result<()> {
+    let CgroupSpec { cpu, cpuset, memory, io, pids, hugetlb, rdma } = spec;
+
+    let builder = LinuxResourcesBuilder::default();
+
+    // oci_spec, which libcgroups uses, combines the cpu and cpuset controllers
+    let builder = if cpu.is_some() || cpuset.is_some() || memory.is_some() {
+        let cpu_builder = LinuxCpuBuilder::default();
+
+        // cpu controller
+        let cpu_builder =
+            if let Some(CpuController { weight, max, period }) = cpu {
+                let mut cpu_builder = if let Some(weight) = weight {
+                    cpu_builder.shares(weight.into_inner())
+                } else {
+                    cpu_builder
+                };
+
+                cpu_builder = if let Some(max) = max {
+                    cpu_builder.quota(max.into_inner())
+                } else {
+                    cpu_builder
+                };
+
+                if let Some(period) = period {
+                    cpu_builder.period(period)
+                } else {
+                    cpu_builder
+                }
+            } else {
+                cpu_builder
+            };
+
+        // cpuset controller
+        let cpu_builder = if let Some(CpusetController { cpus, mems }) = cpuset
+        {
+            let cpu_builder = if let Some(cpus) = cpus {
+                cpu_builder.cpus(cpus.into_inner())
+            } else {
+                cpu_builder
+            };
+
+            if let Some(mems) = mems {
+                cpu_builder.mems(mems.into_inner())
+            } else {
+                cpu_builder
+            }
+        } else {
+            cpu_builder
+        };
+
+        let memory_builder = LinuxMemoryBuilder::default();
+        let memory_builder =
+            if let Some(MemoryController { low, max, .. }) = &memory {
+                let memory_builder = if let Some(low) = low {
+                    memory_builder.reservation(low.into_inner())
+                } else {
+                    memory_builder
+                };
+
+                if let Some(max) = max {
+                    memory_builder.limit(max.into_inner())
+                } else {
+                    memory_builder
+                }
+            } else {
+                memory_builder
+            };
+
+        let cpu = cpu_builder.build().expect("valid cpu builder");
+        let memory = memory_builder.build().expect("valid memory builder");
+        builder.cpu(cpu).memory(memory)
+    } else {
+        builder
+    };
+
+    let builder = if let Some(PidsController { max: Some(max) }) = pids {
+        let pids = LinuxPidsBuilder::default()
+            .limit(max.into_inner())
+            .build()
+            .expect("valid pids builder");
+        builder.pids(pids)
+    } else {
+        builder
+    };
+
+    let builder = if let Some(HugetlbController { max }) = hugetlb {
+        let limits = max
+            .into_iter()
+            .map(|HugetlbLimit { page_size, max }| {
+                LinuxHugepageLimitBuilder::default()
+                    .page_size(page_size)
+                    .limit(max.into_inner())
+                    .build()
+                    .expect("valid hugepage limit builder")
+            })
+            .collect::<Vec<_>>();
+        builder.hugepage_limits(limits)
+    } else {
+        builder
+    };
+
+    let options = builder.build().expect("valid options");
+    let options = ControllerOpt {
+        resources: &options,
+        disable_oom_killer: false,
+        oom_score_adj: None,
+        freezer_state: None,
+    };
+
+    non_leaf.apply(&options)?;
+
+    // oci_spec has no equivalent of io.latency, memory.min, memory.high, memory.swap.high,
+    // or memory.oom.group, and its io weight and swap use the cgroups v1 semantics,
+    // so those files are written directly.
+    apply_memory(cell_name, memory.as_ref())?;
+    apply_io(cell_name, io.as_ref())?;
+    // libcgroups does not support the rdma controller
+    apply_rdma(cell_name, rdma.as_ref())?;
+
+    Ok(())
+}
+
+/// Resets the values of `current` that are not set in `spec` to the kernel defaults.
+/// Must be called after applying `spec`, as values it shares with `current` are not reset.
+fn reset(
+    cell_name: &CellName,
+    current: &CgroupSpec,
+    spec: &CgroupSpec,
+) -> std::io::Result<()> {
+    let path = get_path(cell_name);
+
+    // Writes `default` to `file` if the value was set, but no longer is
+    let reset_if_unset =
+        |file: &str, was_set: bool, is_set: bool, default: &str| {
+            if was_set && !is_set {
+                fs::write(path.join(file), default)
+            } else {
+                Ok(())
+            }
+        };
+
+    if let Some(CpuController { weight, max, period }) = &current.cpu {
+        let cpu = spec.cpu.as_ref();
+        reset_if_unset(
+            "cpu.weight",
+            weight.is_some(),
+            cpu.is_some_and(|x| x.weight.is_some()),
+            "100",
+        )?;
+        // cpu.max holds both the max and the period, and is written whole with the values of
+        // `spec`, so that the max is not lifted while resetting the period
+        let (spec_max, spec_period) =
+            cpu.map(|x| (x.max, x.period)).unwrap_or_default();
+        let cpu_max = format!(
+            "{} {}",
+            spec_max
+                .filter(|x| **x > 0)
+                .map_or_else(|| "max".into(), |x| x.to_string()),
+            spec_period.unwrap_or(DEFAULT_CPU_PERIOD)
+        );
+        reset_if_unset(
+            "cpu.max",
+            max.is_some() || period.is_some(),
+            cpu.is_some_and(|x| {
+                (max.is_none() || x.max.is_some())
+                    && (period.is_none() || x.period.is_some())
+            }),
+            &cpu_max,
+        )?;
+    }
+
+    if let Some(CpusetController { cpus, mems }) = &current.cpuset {
+        let cpuset = spec.cpuset.as_ref();
+        // An empty cpuset uses that of the parent
+        reset_if_unset(
+            "cpuset.cpus",
+            cpus.is_some(),
+            cpuset.is_some_and(|x| x.cpus.is_some()),
+            "\n",
+        )?;
+        reset_if_unset(
+            "cpuset.mems",
+            mems.is_some(),
+            cpuset.is_some_and(|x| x.mems.is_some()),
+            "\n",
+        )?;
+    }
+
+    if let Some(MemoryController {
+        min,
+        low,
+        high,
+        max,
+        swap_max,
+        swap_high,
+        oom_group,
+    }) = &current.memory
+    {
+        let memory = spec.memory.as_ref();
+        for (file, was_set, is_set, default) in [
+            (
+                "memory.min",
+                min.is_some(),
+                memory.is_some_and(|x| x.min.is_some()),
+                "0",
+            ),
+            (
+                "memory.low",
+                low.is_some(),
+                memory.is_some_and(|x| x.low.is_some()),
+                "0",
+            ),
+            (
+                "memory.high",
+                high.is_some(),
+                memory.is_some_and(|x| x.high.is_some()),
+                "max",
+            ),
+            (
+                "memory.max",
+                max.is_some(),
+                memory.is_some_and(|x| x.max.is_some()),
+                "max",
+            ),
+            (
+                "memory.swap.max",
+                swap_max.is_some(),
+                memory.is_some_and(|x| x.swap_max.is_some()),
+                "max",
+            ),
+            (
+                "memory.swap.high",
+                swap_high.is_some(),
+                memory.is_some_and(|x| x.swap_high.is_some()),
+                "max",
+            ),
+            (
+                "memory.oom.group",
+                oom_group.is_some(),
+                memory.is_some_and(|x| x.oom_group.is_some()),
+                "0",
+            ),
+        ] {
+            reset_if_unset(file, was_set, is_set, default)?;
+        }
+    }
+
+    if let Some(IoController { weight, max, latency }) = &current.io {
+        let io = spec.io.as_ref();
+        reset_if_unset(
+            "io.weight",
+            weight.is_some(),
+            io.is_some_and(|x| x.weight.is_some()),
+            "default 100",
+        )?;
+
+        // The limits of devices that are still set were replaced when applying `spec`
+        for max in max {
+            let is_set = io
+                .is_some_and(|x| x.max.iter().any(|x| x.device == max.device));
+            let default =
+                format!("{} rbps=max wbps=max riops=max wiops=max", max.device);
+            reset_if_unset("io.max", true, is_set, &default)?;
+        }
+
+        for latency in latency {
+            let is_set = io.is_some_and(|x| {
+                x.latency.iter().any(|x| x.device == latency.device)
+            });
+            let default = format!("{} target=0", latency.device);
+            reset_if_unset("io.latency", true, is_set, &default)?;
+        }
+    }
+
+    if let Some(PidsController { max: Some(_) }) = &current.pids {
+        let is_set =
+            matches!(&spec.pids, Some(PidsController { max: Some(_) }));
+        reset_if_unset("pids.max", true, is_set, "max")?;
+    }
+
+    if let Some(HugetlbController { max }) = &current.hugetlb {
+        for max in max {
+            let is_set = spec.hugetlb.as_ref().is_some_and(|x| {
+                x.max.iter().any(|x| x.page_size == max.page_size)
+            });
+            let file = format!("hugetlb.{}.max", max.page_size);
+            reset_if_unset(&file, true, is_set, "max")?;
+        }
+    }
+
+    if let Some(RdmaController { max }) = &current.rdma {
+        for max in max {
+            let is_set = spec
+                .rdma
+                .as_ref()
+                .is_some_and(|x| x.max.iter().any(|x| x.device == max.device));
+            let default =
+                format!("{} hca_handle=max hca_object=max", max.device);
+            reset_if_unset("rdma.max", true, is_set, &default)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_memory(
+    cell_name: &CellName,
+    memory: Option<&MemoryController>,
+) -> std::io::Result<()> {
+    let Some(MemoryController {
+        min,
+        low: _,
+        high,
+        max: _,
+        swap_max,
+        swap_high,
+        oom_group,
+    }) = memory
+    else {
+        return Ok(());
+    };
+
+    let path = get_path(cell_name);
+
+    if let Some(min) = min {
+        fs::write(path.join("memory.min"), min.to_string())?;
+    }
+
+    if let Some(high) = high {
+        fs::write(path.join("memory.high"), high.to_string())?;
+    }
+
+    if let Some(swap_max) = swap_max {
+        fs::write(path.join("memory.swap.max"), swap_max.to_string())?;
+    }
+
+    if let Some(swap_high) = swap_high {
+        fs::write(path.join("memory.swap.high"), swap_high.to_string())?;
+    }
+
+    if let Some(oom_group) = oom_group {
+        let oom_group = if *oom_group { "1" } else { "0" };
+        fs::write(path.join("memory.oom.group"), oom_group)?;
+    }
+
+    Ok(())
+}
+
+fn apply_io(
+    cell_name: &CellName,
+    io: Option<&IoController>,
+) -> std::io::Result<()> {
+    let Some(IoController { weight, max, latency }) = io else {
+        return Ok(());
+    };
+
+    let path = get_path(cell_name);
+
+    if let Some(weight) = weight {
+        fs::write(path.join("io.weight"), format!("default {weight}"))?;
+    }
+
+    // Each write to io.max and io.latency sets the values of a single device
+    for max in max {
+        fs::write(path.join("io.max"), max.to_line())?;
+    }
+
+    for latency in latency {
+        fs::write(path.join("io.latency"), latency.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn apply_rdma(
+    cell_name: &CellName,
+    rdma: Option<&RdmaController>,
+) -> std::io::Result<()> {
+    let Some(RdmaController { max }) = rdma else {
+        return Ok(());
+    };
+
+    if max.is_empty() {
+        return Ok(());
+    }
+
+    let path = get_path(cell_name);
+
+    // libcgroups only enables the controllers it supports for the children of a cgroup
+    if let Some(parent) = path.parent() {
+        fs::write(parent.join("cgroup.subtree_control"), "+rdma")?;
+    }
+
+    // Each write to rdma.max sets the limits of a single device
+    for max in max {
+        fs::write(path.join("rdma.max"), max.to_string())?;
+    }
+
+    Ok(())
+}
+
+fn get_path(cell_name: &CellName) -> PathBuf {
+    let mut path = PathBuf::from_str(DEFAULT_CGROUP_ROOT).expect("valid path");
+    path.push(cell_name.as_inner());
+    path
+}
+
 fn get_leaf_path(cell_name: &CellName) -> PathBuf {
     // '_' is an invalid character in CellName, making it safe to use
     cell_name.as_inner().join("_")
@@ -22,10 +22,20 @@ pub type Result<T> = std::result::Result<T, CgroupsError>;
 pub enum CgroupsError {
     #[error("cgroup '{cell_name}' creation failed: {source}")]
     CreateCgroup { cell_name: CellName, source: anyhow::Error },
+    #[error("cgroup '{cell_name}' update failed: {source}")]
+    UpdateCgroup { cell_name: CellName, source: anyhow::Error },
     #[error("cgroup '{cell_name}' failed to add task: {source}")]
     AddTaskToCgroup { cell_name: CellName, source: anyhow::Error },
     #[error("cgroup '{cell_name}' deletion failed: {source}")]
     DeleteCgroup { cell_name: CellName, source: anyhow::Error },
+    #[error("cgroup '{cell_name}' failed to freeze: {source}")]
+    FreezeCgroup { cell_name: CellName, source: anyhow::Error },
+    #[error("cgroup '{cell_name}' failed to thaw: {source}")]
+    ThawCgroup { cell_name: CellName, source: anyhow::Error },
+    #[error("cgroup '{cell_name}' failed to read events: {source}")]
+    ReadEvents { cell_name: CellName, source: anyhow::Error },
     #[error("cgroup '{cell_name}' failed to read stats: {source}")]
     ReadStats { cell_name: CellName, source: anyhow::Error },
+    #[error("cgroup '{cell_name}' failed to read pids: {source}")]
+    ReadPids { cell_name: CellName, source: anyhow::Error },
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use super::Limit;
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+const HUGEPAGES_DIR: &str = "/sys/kernel/mm/hugepages";
+
+#[derive(Debug, Clone)]
+pub struct HugetlbController {
+    pub max: Vec<HugetlbLimit>,
+}
+
+/// A limit of `hugetlb.<page_size>.max`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HugetlbLimit {
+    /// The page size in the format of the hugetlb controller (e.g., "2MB").
+    pub page_size: String,
+    /// The limit in bytes.
+    pub max: Limit,
+}
+
+impl Display for HugetlbLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.page_size, self.max)
+    }
+}
+
+/// Returns the hugepage sizes supported by the host, in the format of the hugetlb controller.
+pub fn page_sizes() -> io::Result<Vec<String>> {
+    page_sizes_in(Path::new(HUGEPAGES_DIR))
+}
+
+fn page_sizes_in(dir: &Path) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // The kernel has no hugetlb support
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut page_sizes = vec![];
+    for entry in entries {
+        let name = entry?.file_name();
+
+        // e.g., hugepages-2048kB
+        let Some(size_kb) = name
+            .to_str()
+            .and_then(|x| x.strip_prefix("hugepages-"))
+            .and_then(|x| x.strip_suffix("kB"))
+            .and_then(|x| x.parse().ok())
+        else {
+            continue;
+        };
+
+        page_sizes.push(format_page_size(size_kb));
+    }
+
+    page_sizes.sort();
+    Ok(page_sizes)
+}
+
+/// Formats a page size the way the kernel names the hugetlb controller files.
+fn format_page_size(size_kb: u64) -> String {
+    const KB_PER_MB: u64 = 1024;
+    const KB_PER_GB: u64 = 1024 * 1024;
+
+    if size_kb >= KB_PER_GB {
+        format!("{}GB", size_kb / KB_PER_GB)
+    } else if size_kb >= KB_PER_MB {
+        format!("{}MB", size_kb / KB_PER_MB)
+    } else {
+        format!("{size_kb}KB")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_page_size() {
+        assert_eq!(format_page_size(64), "64KB");
+        assert_eq!(format_page_size(2048), "2MB");
+        assert_eq!(format_page_size(1048576), "1GB");
+    }
+
+    #[test]
+    fn test_page_sizes_in() {
+        let dir = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("hugepages-2048kB")).unwrap();
+        fs::create_dir_all(dir.join("hugepages-1048576kB")).unwrap();
+        fs::create_dir_all(dir.join("not-hugepages")).unwrap();
+
+        assert_eq!(page_sizes_in(&dir).unwrap(), vec!["1GB", "2MB"]);
+    }
+
+    #[test]
+    fn test_page_sizes_in_missing_dir() {
+        let dir = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+
+        assert!(page_sizes_in(&dir).unwrap().is_empty());
+    }
+}
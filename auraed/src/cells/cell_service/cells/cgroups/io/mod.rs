@@ -0,0 +1,124 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use super::Weight;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone)]
+pub struct IoController {
+    pub weight: Option<Weight>,
+    pub max: Vec<IoMax>,
+    pub latency: Vec<IoLatency>,
+}
+
+/// A block device, identified by its major and minor numbers.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct IoDevice {
+    pub major: u64,
+    pub minor: u64,
+}
+
+impl Display for IoDevice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.major, self.minor)
+    }
+}
+
+/// A line of `io.max`. Limits that are [None] are left unlimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoMax {
+    pub device: IoDevice,
+    pub rbps: Option<u64>,
+    pub wbps: Option<u64>,
+    pub riops: Option<u64>,
+    pub wiops: Option<u64>,
+}
+
+impl Display for IoMax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self { device, rbps, wbps, riops, wiops } = self;
+
+        write!(f, "{device}")?;
+        for (key, value) in
+            [("rbps", rbps), ("wbps", wbps), ("riops", riops), ("wiops", wiops)]
+        {
+            if let Some(value) = value {
+                write!(f, " {key}={value}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl IoMax {
+    /// Returns the line of `io.max` that sets every limit of the device, with those that are
+    /// [None] set to "max", so that it replaces any limits previously set.
+    pub fn to_line(&self) -> String {
+        let Self { device, rbps, wbps, riops, wiops } = self;
+        let limit =
+            |x: &Option<u64>| x.map_or_else(|| "max".into(), |x| x.to_string());
+
+        format!(
+            "{device} rbps={} wbps={} riops={} wiops={}",
+            limit(rbps),
+            limit(wbps),
+            limit(riops),
+            limit(wiops)
+        )
+    }
+}
+
+/// A line of `io.latency`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoLatency {
+    pub device: IoDevice,
+    /// The latency target in microseconds.
+    pub target: u64,
+}
+
+impl Display for IoLatency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} target={}", self.device, self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_max_display() {
+        let max = IoMax {
+            device: IoDevice { major: 8, minor: 16 },
+            rbps: Some(2097152),
+            wbps: None,
+            riops: None,
+            wiops: Some(120),
+        };
+        assert_eq!(max.to_string(), "8:16 rbps=2097152 wiops=120");
+        assert_eq!(
+            max.to_line(),
+            "8:16 rbps=2097152 wbps=max riops=max wiops=120"
+        );
+    }
+
+    #[test]
+    fn test_io_latency_display() {
+        let latency =
+            IoLatency { device: IoDevice { major: 8, minor: 0 }, target: 75 };
+        assert_eq!(latency.to_string(), "8:0 target=75");
+    }
+}
@@ -21,4 +21,7 @@ pub struct MemoryController {
     pub low: Option<Protection>,
     pub high: Option<Limit>,
     pub max: Option<Limit>,
+    pub swap_max: Option<Limit>,
+    pub swap_high: Option<Limit>,
+    pub oom_group: Option<bool>,
 }
\ No newline at end of file
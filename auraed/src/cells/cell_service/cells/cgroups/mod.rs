@@ -16,20 +16,30 @@
 pub use cgroup::Cgroup;
 pub use cpu::CpuController;
 pub use cpuset::CpusetController;
+pub use hugetlb::HugetlbController;
+pub use io::IoController;
 pub use limit::Limit;
 pub use memory::MemoryController;
+pub use pids::PidsController;
 pub use protection::Protection;
+pub use rdma::RdmaController;
+pub use stats::{CgroupStats, CpuStats, IoDeviceStats, MemoryStats, PidsStats};
 pub use weight::Weight;
 
 pub mod cpu;
 pub mod cpuset;
 pub mod error;
+pub mod hugetlb;
+pub mod io;
 pub mod memory;
+pub mod pids;
+pub mod rdma;
 
 mod allocation;
 mod cgroup;
 mod limit;
 mod protection;
+mod stats;
 mod weight;
 
 #[derive(Debug, Clone)]
@@ -37,4 +47,8 @@ pub struct CgroupSpec {
     pub cpu: Option<CpuController>,
     pub cpuset: Option<CpusetController>,
     pub memory: Option<MemoryController>,
+    pub io: Option<IoController>,
+    pub pids: Option<PidsController>,
+    pub hugetlb: Option<HugetlbController>,
+    pub rdma: Option<RdmaController>,
 }
\ No newline at end of file
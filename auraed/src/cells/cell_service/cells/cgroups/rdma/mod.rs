@@ -0,0 +1,110 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+const INFINIBAND_DIR: &str = "/sys/class/infiniband";
+
+#[derive(Debug, Clone)]
+pub struct RdmaController {
+    pub max: Vec<RdmaMax>,
+}
+
+/// A line of `rdma.max`. Limits that are [None] are unlimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RdmaMax {
+    /// The name of the RDMA device (e.g., "mlx4_0").
+    pub device: String,
+    pub hca_handle: Option<u32>,
+    pub hca_object: Option<u32>,
+}
+
+impl Display for RdmaMax {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self { device, hca_handle, hca_object } = self;
+
+        write!(f, "{device}")?;
+        for (key, value) in
+            [("hca_handle", hca_handle), ("hca_object", hca_object)]
+        {
+            match value {
+                Some(value) => write!(f, " {key}={value}")?,
+                None => write!(f, " {key}=max")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the names of the RDMA devices of the host.
+pub fn devices() -> io::Result<Vec<String>> {
+    devices_in(Path::new(INFINIBAND_DIR))
+}
+
+fn devices_in(dir: &Path) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        // The host has no RDMA devices
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut devices = vec![];
+    for entry in entries {
+        if let Some(name) = entry?.file_name().to_str() {
+            devices.push(name.to_string());
+        }
+    }
+
+    devices.sort();
+    Ok(devices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rdma_max_display() {
+        let max = RdmaMax {
+            device: "mlx4_0".into(),
+            hca_handle: Some(2),
+            hca_object: None,
+        };
+        assert_eq!(max.to_string(), "mlx4_0 hca_handle=2 hca_object=max");
+    }
+
+    #[test]
+    fn test_devices_in() {
+        let dir = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("mlx4_0")).unwrap();
+        fs::create_dir_all(dir.join("irdma0")).unwrap();
+
+        assert_eq!(devices_in(&dir).unwrap(), vec!["irdma0", "mlx4_0"]);
+    }
+
+    #[test]
+    fn test_devices_in_missing_dir() {
+        let dir = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+
+        assert!(devices_in(&dir).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+//! Resource usage of a cgroup, read from the cgroup v2 interface files.
+//!
+//! Docs: https://docs.kernel.org/admin-guide/cgroup-v2.html
+//!
+//! Interface files of controllers that are not enabled for the cgroup do not
+//! exist. The corresponding stats are left at their defaults.
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CgroupStats {
+    pub cpu: CpuStats,
+    pub memory: MemoryStats,
+    pub io: Vec<IoDeviceStats>,
+    pub pids: PidsStats,
+}
+
+/// Read from `cpu.stat`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuStats {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+/// Read from `memory.current`, `memory.swap.current` and `memory.stat`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    pub current: u64,
+    pub swap_current: u64,
+    /// The breakdown of `current` (e.g., anon, file, kernel).
+    pub stat: HashMap<String, u64>,
+}
+
+/// A line of `io.stat`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IoDeviceStats {
+    pub major: u64,
+    pub minor: u64,
+    pub rbytes: u64,
+    pub wbytes: u64,
+    pub rios: u64,
+    pub wios: u64,
+}
+
+/// Read from `pids.current` and `pids.max`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PidsStats {
+    pub current: u64,
+    /// [None] if unlimited.
+    pub max: Option<u64>,
+}
+
+impl CgroupStats {
+    /// Reads the stats of the cgroup at `path`, including all of its descendants.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        if !path.is_dir() {
+            return Err(not_found(path));
+        }
+
+        let read = |file: &str| read_optional(&path.join(file));
+
+        let cpu = read("cpu.stat")?
+            .map(|x| {
+                let stat = parse_flat_keyed(&x);
+                let get = |key: &str| stat.get(key).copied().unwrap_or(0);
+                CpuStats {
+                    usage_usec: get("usage_usec"),
+                    user_usec: get("user_usec"),
+                    system_usec: get("system_usec"),
+                    nr_periods: get("nr_periods"),
+                    nr_throttled: get("nr_throttled"),
+                    throttled_usec: get("throttled_usec"),
+                }
+            })
+            .unwrap_or_default();
+
+        let memory = MemoryStats {
+            current: read("memory.current")?
+                .and_then(|x| parse_single(&x))
+                .unwrap_or(0),
+            swap_current: read("memory.swap.current")?
+                .and_then(|x| parse_single(&x))
+                .unwrap_or(0),
+            stat: read("memory.stat")?
+                .map(|x| parse_flat_keyed(&x))
+                .unwrap_or_default(),
+        };
+
+        let io =
+            read("io.stat")?.map(|x| parse_io_stat(&x)).unwrap_or_default();
+
+        let pids = PidsStats::read(path)?;
+
+        Ok(Self { cpu, memory, io, pids })
+    }
+}
+
+impl PidsStats {
+    /// Reads the pids stats of the cgroup at `path`, including all of its descendants.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        if !path.is_dir() {
+            return Err(not_found(path));
+        }
+
+        Ok(Self {
+            current: read_optional(&path.join("pids.current"))?
+                .and_then(|x| parse_single(&x))
+                .unwrap_or(0),
+            max: read_optional(&path.join("pids.max"))?
+                .and_then(|x| parse_single(&x)),
+        })
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("cgroup '{}' does not exist", path.display()),
+    )
+}
+
+fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Parses a file with a single value. Returns [None] for "max".
+fn parse_single(content: &str) -> Option<u64> {
+    content.trim().parse().ok()
+}
+
+/// Parses a file with a "key value" pair on each line.
+fn parse_flat_keyed(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Parses `io.stat`, which has a "major:minor key=value..." line for each device.
+fn parse_io_stat(content: &str) -> Vec<IoDeviceStats> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (major, minor) = fields.next()?.split_once(':')?;
+
+            let mut stats = IoDeviceStats {
+                major: major.parse().ok()?,
+                minor: minor.parse().ok()?,
+                ..Default::default()
+            };
+
+            for field in fields {
+                let Some((key, value)) = field.split_once('=') else {
+                    continue;
+                };
+                let Ok(value) = value.parse() else {
+                    continue;
+                };
+                match key {
+                    "rbytes" => stats.rbytes = value,
+                    "wbytes" => stats.wbytes = value,
+                    "rios" => stats.rios = value,
+                    "wios" => stats.wios = value,
+                    _ => {}
+                }
+            }
+
+            Some(stats)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single() {
+        assert_eq!(parse_single("1234\n"), Some(1234));
+        assert_eq!(parse_single("max\n"), None);
+    }
+
+    #[test]
+    fn test_parse_flat_keyed() {
+        let stat = parse_flat_keyed(
+            "usage_usec 1000\nuser_usec 600\nsystem_usec 400\nnr_periods 0\n",
+        );
+        assert_eq!(stat.len(), 4);
+        assert_eq!(stat["usage_usec"], 1000);
+        assert_eq!(stat["system_usec"], 400);
+    }
+
+    #[test]
+    fn test_parse_io_stat() {
+        let io = parse_io_stat(
+            "8:0 rbytes=1024 wbytes=2048 rios=1 wios=2 dbytes=0 dios=0\n\
+             253:1 rbytes=4096 wbytes=0 rios=3 wios=0 dbytes=0 dios=0\n",
+        );
+        assert_eq!(
+            io,
+            vec![
+                IoDeviceStats {
+                    major: 8,
+                    minor: 0,
+                    rbytes: 1024,
+                    wbytes: 2048,
+                    rios: 1,
+                    wios: 2,
+                },
+                IoDeviceStats {
+                    major: 253,
+                    minor: 1,
+                    rbytes: 4096,
+                    wbytes: 0,
+                    rios: 3,
+                    wios: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_missing_cgroup() {
+        let path = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+        let err = CgroupStats::read(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_read_without_controllers() {
+        let path = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("cpu.stat"), "usage_usec 10\n").unwrap();
+        fs::write(path.join("pids.current"), "2\n").unwrap();
+        fs::write(path.join("pids.max"), "max\n").unwrap();
+
+        let stats = CgroupStats::read(&path).unwrap();
+        assert_eq!(stats.cpu.usage_usec, 10);
+        assert_eq!(stats.memory, MemoryStats::default());
+        assert!(stats.io.is_empty());
+        assert_eq!(stats.pids, PidsStats { current: 2, max: None });
+    }
+}
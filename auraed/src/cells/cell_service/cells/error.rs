@@ -28,14 +28,32 @@ pub enum CellsError {
     CellNotFound { cell_name: CellName },
     #[error("cell '{cell_name}' is not allocated")]
     CellNotAllocated { cell_name: CellName },
+    #[error("cell '{cell_name}' is paused")]
+    CellPaused { cell_name: CellName },
     #[error("cell '{cell_name}' could not be allocated: {source}")]
     FailedToAllocateCell { cell_name: CellName, source: io::Error },
+    #[error("cell '{cell_name}' could not be adopted: {source}")]
+    FailedToAdoptCell { cell_name: CellName, source: anyhow::Error },
     #[error("cell '{cell_name}' allocation was aborted: {source}")]
     AbortedAllocateCell { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' network could not be set up: {source}")]
+    FailedToSetUpCellNetwork { cell_name: CellName, source: anyhow::Error },
     #[error("cell '{cell_name}' could not kill children: {source}")]
     FailedToKillCellChildren { cell_name: CellName, source: io::Error },
     #[error("cell '{cell_name}' could not be freed: {source}")]
     FailedToFreeCell { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' could not be signaled: {source}")]
+    FailedToSignalCell { cell_name: CellName, source: anyhow::Error },
+    #[error("cell '{cell_name}' could not be updated: {source}")]
+    FailedToUpdateCell { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' could not be paused: {source}")]
+    FailedToPauseCell { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' could not be resumed: {source}")]
+    FailedToResumeCell { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' could not read events: {source}")]
+    FailedToReadCellEvents { cell_name: CellName, source: CgroupsError },
+    #[error("cell '{cell_name}' could not read stats: {source}")]
+    FailedToReadCellStats { cell_name: CellName, source: CgroupsError },
     #[error(
         "cgroup '{cell_name}' exists on host, but is not controlled by auraed"
     )]
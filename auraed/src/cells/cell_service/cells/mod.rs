@@ -13,15 +13,20 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
+use super::executables::SecurityProfile;
 pub use cell::Cell;
+pub use cell_allocation::CellAllocation;
 pub use cell_name::CellName;
 pub use cells::Cells;
 pub use cells_cache::CellsCache;
 use cgroups::CgroupSpec;
 pub use error::{CellsError, Result};
-pub use nested_auraed::IsolationControls;
+pub use nested_auraed::{
+    IdMap, IsolationControls, Mount, SubordinateIds, SUBGID_PATH, SUBUID_PATH,
+};
 
 mod cell;
+mod cell_allocation;
 mod cell_name;
 #[allow(clippy::module_inception)]
 mod cells;
@@ -29,11 +34,14 @@ mod cells_cache;
 pub mod cgroups;
 mod error;
 mod nested_auraed;
+mod network;
 
 #[derive(Debug, Clone)]
 pub struct CellSpec {
     pub cgroup_spec: CgroupSpec,
     pub iso_ctl: IsolationControls,
+    /// The default security profile of the executables started in the cell.
+    pub security_profile: Option<SecurityProfile>,
 }
 
 impl CellSpec {
@@ -56,12 +64,25 @@ impl CellSpec {
                     low: None,
                     high: None,
                     max: Some(Limit::new(1000000)),
+                    swap_max: None,
+                    swap_high: None,
+                    oom_group: None,
                 }),
+                io: None,
+                pids: None,
+                hugetlb: None,
+                rdma: None,
             },
             iso_ctl: IsolationControls {
                 isolate_network: false,
                 isolate_process: false,
+                isolate_user: false,
+                uid_map: vec![],
+                gid_map: vec![],
+                rootfs: None,
+                mounts: vec![],
             },
+            security_profile: None,
         }
     }
 }
\ No newline at end of file
@@ -13,15 +13,27 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
+use super::mount::Mount;
+use super::user_namespace::IdMap;
 use libc::c_char;
+use nix::mount::MntFlags;
 use std::io::{self};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 #[derive(Debug, Clone, Default)]
 pub struct IsolationControls {
     pub isolate_process: bool,
     pub isolate_network: bool,
+    pub isolate_user: bool,
+    /// Mappings of the user ids of the cell's user namespace to host user ids.
+    pub uid_map: Vec<IdMap>,
+    /// Mappings of the group ids of the cell's user namespace to host group ids.
+    pub gid_map: Vec<IdMap>,
+    /// Host directory that becomes the root of the cell's mount namespace.
+    pub rootfs: Option<PathBuf>,
+    /// Filesystems mounted into the cell's mount namespace.
+    pub mounts: Vec<Mount>,
 }
 
 #[derive(Default)]
@@ -54,6 +66,54 @@ impl Isolation {
         Ok(())
     }
 
+    /// Applies the mounts of the cell and, if a rootfs is set, pivots into it.
+    /// `auraed_mounts` are the host paths the nested auraed needs to run inside the rootfs.
+    pub fn isolate_filesystem(
+        &mut self,
+        iso_ctl: &IsolationControls,
+        auraed_mounts: &[Mount],
+    ) -> io::Result<()> {
+        if !iso_ctl.isolate_process {
+            return Ok(());
+        }
+
+        let Some(rootfs) = &iso_ctl.rootfs else {
+            // We are in a new private mount namespace, so the mounts are not visible to the host
+            for mount in &iso_ctl.mounts {
+                mount.mount(Path::new("/"))?;
+            }
+            return Ok(());
+        };
+
+        // pivot_root requires the new root to be a mount point
+        nix::mount::mount(
+            Some(rootfs),
+            rootfs,
+            None::<&str>,
+            nix::mount::MsFlags::MS_BIND | nix::mount::MsFlags::MS_REC,
+            None::<&str>,
+        )
+        .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+
+        for mount in auraed_mounts.iter().chain(&iso_ctl.mounts) {
+            mount.mount(rootfs)?;
+        }
+
+        // Mount point for the proc mount of isolate_process
+        std::fs::create_dir_all(rootfs.join("proc"))?;
+
+        // Stack the new root on top of the old root and detach the old root (see pivot_root(2))
+        nix::unistd::chdir(rootfs)
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        nix::unistd::pivot_root(".", ".")
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        nix::mount::umount2(".", MntFlags::MNT_DETACH)
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        nix::unistd::chdir("/")
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        Ok(())
+    }
+
     pub fn isolate_process(
         &mut self,
         iso_ctl: &IsolationControls,
@@ -110,4 +170,27 @@ impl Isolation {
         // Insert pre_exec network logic here
         Ok(())
     }
+
+    /// Becomes root of the user namespace, which is only possible once the parent
+    /// has written the uid and gid maps. Must run last, as it drops the host privileges.
+    pub fn isolate_user(
+        &mut self,
+        iso_ctl: &IsolationControls,
+    ) -> io::Result<()> {
+        if !iso_ctl.isolate_user {
+            return Ok(());
+        }
+
+        let root_uid = nix::unistd::Uid::from_raw(0);
+        let root_gid = nix::unistd::Gid::from_raw(0);
+
+        nix::unistd::setresgid(root_gid, root_gid, root_gid)
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        // Drop the supplementary groups of the host
+        nix::unistd::setgroups(&[])
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        nix::unistd::setresuid(root_uid, root_uid, root_uid)
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+        Ok(())
+    }
 }
\ No newline at end of file
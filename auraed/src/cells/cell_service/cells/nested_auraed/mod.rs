@@ -14,8 +14,12 @@
 \* -------------------------------------------------------------------------- */
 
 pub use isolation_controls::IsolationControls;
+pub use mount::Mount;
 pub use nested_auraed::NestedAuraed;
+pub use user_namespace::{IdMap, SubordinateIds, SUBGID_PATH, SUBUID_PATH};
 
 mod isolation_controls;
+mod mount;
 #[allow(clippy::module_inception)]
-mod nested_auraed;
\ No newline at end of file
+mod nested_auraed;
+mod user_namespace;
\ No newline at end of file
@@ -0,0 +1,144 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A filesystem mounted into the mount namespace of an isolated cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mount {
+    /// Makes `source` from the host available at `target` in the cell.
+    Bind { source: PathBuf, target: PathBuf, readonly: bool },
+    /// A memory backed filesystem at `target`, optionally limited to `size` bytes.
+    Tmpfs { target: PathBuf, size: Option<u64> },
+}
+
+impl Mount {
+    pub fn target(&self) -> &Path {
+        match self {
+            Mount::Bind { target, .. } | Mount::Tmpfs { target, .. } => target,
+        }
+    }
+
+    /// Mounts the filesystem at its target below `root`, creating the mount point if needed.
+    pub(crate) fn mount(&self, root: &Path) -> io::Result<()> {
+        let target = self.target();
+        let target = root.join(target.strip_prefix("/").unwrap_or(target));
+
+        match self {
+            Mount::Bind { source, readonly, .. } => {
+                // A file can only be bind mounted onto a file
+                if source.is_dir() {
+                    fs::create_dir_all(&target)?;
+                } else if !target.exists() {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    let _ = fs::File::create(&target)?;
+                }
+
+                nix::mount::mount(
+                    Some(source),
+                    &target,
+                    None::<&str>,
+                    nix::mount::MsFlags::MS_BIND | nix::mount::MsFlags::MS_REC,
+                    None::<&str>,
+                )
+                .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+
+                if *readonly {
+                    // MS_RDONLY is ignored when creating a bind mount, so we remount
+                    nix::mount::mount(
+                        None::<&str>,
+                        &target,
+                        None::<&str>,
+                        nix::mount::MsFlags::MS_BIND
+                            | nix::mount::MsFlags::MS_REMOUNT
+                            | nix::mount::MsFlags::MS_RDONLY,
+                        None::<&str>,
+                    )
+                    .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+                }
+            }
+            Mount::Tmpfs { size, .. } => {
+                fs::create_dir_all(&target)?;
+
+                let data = size.map(|size| format!("size={size}"));
+                nix::mount::mount(
+                    Some("tmpfs"),
+                    &target,
+                    Some("tmpfs"),
+                    nix::mount::MsFlags::empty(),
+                    data.as_deref(),
+                )
+                .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Mount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mount::Bind { source, target, readonly } => {
+                write!(
+                    f,
+                    "type=bind,source={},target={}",
+                    source.display(),
+                    target.display()
+                )?;
+                if *readonly {
+                    write!(f, ",readonly")?;
+                }
+                Ok(())
+            }
+            Mount::Tmpfs { target, size } => {
+                write!(f, "type=tmpfs,target={}", target.display())?;
+                if let Some(size) = size {
+                    write!(f, ",size={size}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let mount = Mount::Bind {
+            source: "/data".into(),
+            target: "/mnt/data".into(),
+            readonly: true,
+        };
+        assert_eq!(
+            mount.to_string(),
+            "type=bind,source=/data,target=/mnt/data,readonly"
+        );
+
+        let mount = Mount::Tmpfs { target: "/tmp".into(), size: Some(1024) };
+        assert_eq!(mount.to_string(), "type=tmpfs,target=/tmp,size=1024");
+
+        let mount = Mount::Tmpfs { target: "/tmp".into(), size: None };
+        assert_eq!(mount.to_string(), "type=tmpfs,target=/tmp");
+    }
+}
@@ -14,10 +14,13 @@
 \* -------------------------------------------------------------------------- */
 
 use super::isolation_controls::{Isolation, IsolationControls};
+use super::mount::Mount;
+use super::user_namespace::{self, chown_to_root};
 use crate::AURAED_RUNTIME;
 use client::AuraeSocket;
 use clone3::Flags;
 use nix::{
+    fcntl::OFlag,
     libc::SIGCHLD,
     sys::signal::{Signal, Signal::SIGKILL, Signal::SIGTERM},
     unistd::Pid,
@@ -33,7 +36,6 @@ use tracing::{error, info, trace};
 #[derive(Debug)]
 pub struct NestedAuraed {
     process: procfs::process::Process,
-    #[allow(unused)]
     pidfd: i32,
     #[allow(unused)]
     iso_ctl: IsolationControls,
@@ -41,24 +43,40 @@ pub struct NestedAuraed {
 }
 
 impl NestedAuraed {
-    pub fn new(name: String, iso_ctl: IsolationControls) -> io::Result<Self> {
+    pub fn new(
+        name: String,
+        logs_dir: PathBuf,
+        iso_ctl: IsolationControls,
+    ) -> io::Result<Self> {
         // Here we launch a nested auraed with the --nested flag
         // which is used our way of "hooking" into the newly created
         // aurae isolation zone.
 
         let auraed_runtime = AURAED_RUNTIME.get().expect("runtime");
 
-        let socket_path = format!(
-            "{}/aurae-{}.sock",
-            auraed_runtime.runtime_dir.to_string_lossy(),
-            uuid::Uuid::new_v4(),
-        );
+        // Each nested auraed creates its socket in a directory of its own, which is the only
+        // part of the runtime directory that is made available to a cell with a rootfs.
+        let socket_dir = auraed_runtime
+            .runtime_dir
+            .join(format!("aurae-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&socket_dir)?;
+        std::fs::create_dir_all(&logs_dir)?;
+
+        if iso_ctl.isolate_user {
+            // Root of the user namespace is not root on the host, so the nested auraed
+            // must own the directories it writes to.
+            chown_to_root(&socket_dir, &iso_ctl.uid_map, &iso_ctl.gid_map)?;
+            chown_to_root(&logs_dir, &iso_ctl.uid_map, &iso_ctl.gid_map)?;
+        }
+
+        let socket_path =
+            format!("{}/aurae.sock", socket_dir.to_string_lossy());
 
         let client_socket = AuraeSocket::Path(socket_path.clone().into());
 
         let auraed_path: PathBuf =
             auraed_runtime.auraed.clone().try_into().expect("path to auraed");
-        let mut command = Command::new(auraed_path);
+        let mut command = Command::new(&auraed_path);
 
         let _ = command.args([
             "--socket",
@@ -74,13 +92,15 @@ impl NestedAuraed {
             &auraed_runtime.runtime_dir.to_string_lossy(),
             "--library-dir",
             &auraed_runtime.library_dir.to_string_lossy(),
+            "--logs-dir",
+            &logs_dir.to_string_lossy(),
         ]);
 
         // We have a concern that the "command" API make change/break in the future and this
         // test is intended to help safeguard against that!
         // We check that the command we kept has the expected number of args following the call
         // to command.args, whose return value we ignored above.
-        assert_eq!(command.get_args().len(), 13);
+        assert_eq!(command.get_args().len(), 15);
 
         // *****************************************************************
         // ██████╗██╗      ██████╗ ███╗   ██╗███████╗██████╗
@@ -107,8 +127,18 @@ impl NestedAuraed {
         // We check that the clone we kept has set the first flag we set above.
         assert_eq!(clone.as_clone_args().flags, Flags::PIDFD.bits());
 
-        // Freeze the parent until the child calls execvp
-        let _ = clone.flag_vfork();
+        // Freeze the parent until the child calls execvp.
+        // With a user namespace, the parent must instead write the id maps of the child
+        // while the child waits on a pipe before calling execvp.
+        let user_ns_ready = if iso_ctl.isolate_user {
+            Some(
+                nix::unistd::pipe2(OFlag::O_CLOEXEC)
+                    .map_err(|e| io::Error::from_raw_os_error(e as i32))?,
+            )
+        } else {
+            let _ = clone.flag_vfork();
+            None
+        };
 
         // Manage SIGCHLD for the nested process
         // Define SIGCHLD for signal handler
@@ -116,6 +146,37 @@ impl NestedAuraed {
 
         // [ Namespaces and Isolation ]
 
+        // The nested auraed runs inside the rootfs, so we make the host paths it uses
+        // available at the same location. A nested auraed serves without TLS and loads no
+        // eBPF probes, so the key of the server and the library directory are left out.
+        // Only the socket directory and the logs directory of this cell are writable,
+        // which keeps the sockets and logs of other cells out of reach.
+        let auraed_mounts = match &iso_ctl.rootfs {
+            Some(_) => {
+                let read_only = [std::fs::canonicalize(&auraed_path)?];
+                let mut read_write = vec![socket_dir.clone(), logs_dir.clone()];
+                if !iso_ctl.isolate_network {
+                    // Cells of the nested auraed are attached to the host bridge, so their
+                    // addresses are leased from the host pool.
+                    let ipam_dir = auraed_runtime.runtime_dir.join("ipam");
+                    std::fs::create_dir_all(&ipam_dir)?;
+                    read_write.push(ipam_dir);
+                }
+
+                read_only
+                    .into_iter()
+                    .map(|path| (path, true))
+                    .chain(read_write.into_iter().map(|path| (path, false)))
+                    .map(|(path, readonly)| Mount::Bind {
+                        source: path.clone(),
+                        target: path,
+                        readonly,
+                    })
+                    .collect()
+            }
+            None => vec![],
+        };
+
         let mut isolation = Isolation::new(name);
         isolation.setup(&iso_ctl)?;
 
@@ -127,6 +188,11 @@ impl NestedAuraed {
             let _ = clone.flag_newnet();
         }
 
+        // Isolate User
+        if iso_ctl.isolate_user {
+            let _ = clone.flag_newuser();
+        }
+
         // Isolate Process
         if iso_ctl.isolate_process {
             let _ = clone.flag_newpid();
@@ -141,11 +207,19 @@ impl NestedAuraed {
         {
             0 => {
                 // child
+                // Close our write end, so the child stops waiting if the parent fails
+                let user_ns_ready = user_ns_ready.map(|(rx, _tx)| rx);
                 let command = {
                     unsafe {
                         command.pre_exec(move || {
+                            if let Some(ready) = &user_ns_ready {
+                                user_namespace::wait_for_id_maps(ready)?;
+                            }
+                            isolation
+                                .isolate_filesystem(&iso_ctl, &auraed_mounts)?;
                             isolation.isolate_process(&iso_ctl)?;
                             isolation.isolate_network(&iso_ctl)?;
+                            isolation.isolate_user(&iso_ctl)?;
                             Ok(())
                         })
                     }
@@ -157,6 +231,28 @@ impl NestedAuraed {
             }
             pid => {
                 // parent
+                if let Some((_rx, tx)) = user_ns_ready {
+                    let res = user_namespace::write_id_maps(
+                        Pid::from_raw(pid),
+                        &iso_ctl.uid_map,
+                        &iso_ctl.gid_map,
+                    )
+                    .and_then(|_| {
+                        nix::unistd::write(&tx, &[0])
+                            .map_err(|e| io::Error::from_raw_os_error(e as i32))
+                    });
+
+                    if let Err(e) = res {
+                        // Don't leave the child waiting on the pipe
+                        let _ =
+                            nix::sys::signal::kill(Pid::from_raw(pid), SIGKILL);
+                        let _ = unsafe {
+                            libc::waitpid(pid, std::ptr::null_mut(), 0)
+                        };
+                        return Err(e);
+                    }
+                }
+
                 info!("Nested auraed running with host pid {}", pid.clone());
                 let process = procfs::process::Process::new(pid)
                     .map_err(|e| io::Error::new(ErrorKind::Other, e))?;
@@ -166,6 +262,47 @@ impl NestedAuraed {
         }
     }
 
+    /// Takes over a nested auraed started by a previous run of auraed, which kept running after
+    /// its parent exited. The process must still have the same `start_time`, so that a reused
+    /// pid is never adopted, and its socket must still exist.
+    pub fn adopt(
+        pid: i32,
+        start_time: u64,
+        client_socket: AuraeSocket,
+        iso_ctl: IsolationControls,
+    ) -> io::Result<Self> {
+        let process = procfs::process::Process::new(pid)
+            .map_err(|e| io::Error::new(ErrorKind::NotFound, e))?;
+
+        let stat =
+            process.stat().map_err(|e| io::Error::new(ErrorKind::Other, e))?;
+        if stat.starttime != start_time {
+            return Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("pid {pid} is no longer the nested auraed"),
+            ));
+        }
+
+        if let AuraeSocket::Path(path) = &client_socket {
+            if !path.exists() {
+                return Err(io::Error::new(
+                    ErrorKind::NotFound,
+                    format!("socket {} not found", path.display()),
+                ));
+            }
+        }
+
+        // The adopted process is not our child, so we wait for it with a pidfd
+        let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+        if pidfd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        info!("Adopted nested auraed running with host pid {pid}");
+
+        Ok(Self { process, pidfd: pidfd as i32, iso_ctl, client_socket })
+    }
+
     /// Sends a graceful shutdown signal to the nested process.
     pub fn shutdown(&mut self) -> io::Result<ExitStatus> {
         // TODO: Here, SIGTERM works when using auraescript, but hangs(?) during unit tests.
@@ -202,8 +339,10 @@ impl NestedAuraed {
 
             if res == -1 {
                 let err = io::Error::last_os_error();
-                match err.kind() {
-                    ErrorKind::Interrupted => continue,
+                match err.raw_os_error() {
+                    Some(libc::EINTR) => continue,
+                    // An adopted nested auraed is not our child
+                    Some(libc::ECHILD) => return self.wait_adopted(),
                     _ => break Err(err),
                 }
             }
@@ -218,7 +357,50 @@ impl NestedAuraed {
         Ok(exit_status)
     }
 
+    /// Waits for a process that is not our child to exit. Only the parent of a process can
+    /// read its exit status, so the process is reported to have exited successfully.
+    fn wait_adopted(&mut self) -> io::Result<ExitStatus> {
+        let mut pollfd =
+            libc::pollfd { fd: self.pidfd, events: libc::POLLIN, revents: 0 };
+
+        loop {
+            let res = unsafe { libc::poll(&mut pollfd, 1, -1) };
+
+            if res == -1 {
+                let err = io::Error::last_os_error();
+                match err.kind() {
+                    ErrorKind::Interrupted => continue,
+                    _ => return Err(err),
+                }
+            }
+
+            break;
+        }
+
+        trace!("Adopted pid {} exited", self.process.pid);
+
+        Ok(ExitStatus::from_raw(0))
+    }
+
     pub fn pid(&self) -> Pid {
         Pid::from_raw(self.process.pid)
     }
+
+    /// Returns false once the process has exited, even if it was not waited for yet.
+    pub fn running(&self) -> bool {
+        // The pidfd becomes readable when the process exits
+        let mut pollfd =
+            libc::pollfd { fd: self.pidfd, events: libc::POLLIN, revents: 0 };
+        let res = unsafe { libc::poll(&mut pollfd, 1, 0) };
+        res != 1
+    }
+
+    /// The time the process started after system boot, in clock ticks.
+    /// Along with the [Pid], it identifies the process across restarts of auraed.
+    pub fn start_time(&self) -> io::Result<u64> {
+        self.process
+            .stat()
+            .map(|stat| stat.starttime)
+            .map_err(|e| io::Error::new(ErrorKind::Other, e))
+    }
 }
\ No newline at end of file
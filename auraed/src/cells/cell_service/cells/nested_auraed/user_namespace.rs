@@ -0,0 +1,214 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use nix::unistd::Pid;
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    ops::Range,
+    os::fd::{AsRawFd, OwnedFd},
+    path::Path,
+};
+
+pub const SUBUID_PATH: &str = "/etc/subuid";
+pub const SUBGID_PATH: &str = "/etc/subgid";
+
+/// A range of `count` ids starting at `inside` in the user namespace of a cell,
+/// mapped to the ids starting at `outside` on the host (see user_namespaces(7)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdMap {
+    pub inside: u32,
+    pub outside: u32,
+    pub count: u32,
+}
+
+impl IdMap {
+    pub fn inside_range(&self) -> Range<u64> {
+        self.inside as u64..self.inside as u64 + self.count as u64
+    }
+
+    pub fn outside_range(&self) -> Range<u64> {
+        self.outside as u64..self.outside as u64 + self.count as u64
+    }
+
+    /// Returns the host id of `id` in the user namespace, if it is mapped.
+    pub fn to_outside(maps: &[IdMap], id: u32) -> Option<u32> {
+        maps.iter()
+            .find(|map| map.inside_range().contains(&(id as u64)))
+            .map(|map| map.outside + (id - map.inside))
+    }
+}
+
+impl Display for IdMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.inside, self.outside, self.count)
+    }
+}
+
+/// The ranges of subordinate ids delegated to a user in /etc/subuid or /etc/subgid (see subuid(5)).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubordinateIds(Vec<Range<u64>>);
+
+impl SubordinateIds {
+    /// Reads the ranges delegated to the user running auraed. A missing file delegates no ids.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(e),
+        };
+
+        let uid = nix::unistd::geteuid();
+        let user = nix::unistd::User::from_uid(uid)
+            .map_err(|e| io::Error::from_raw_os_error(e as i32))?
+            .map(|user| user.name);
+
+        Ok(Self::parse(&contents, user.as_deref(), uid.as_raw()))
+    }
+
+    /// Delegates every id, for maps that were already checked against the host.
+    pub fn all() -> Self {
+        Self(vec![0..1 << 32])
+    }
+
+    /// Entries are `<user name or id>:<first id>:<count>`, one per line.
+    pub(crate) fn parse(contents: &str, user: Option<&str>, uid: u32) -> Self {
+        let uid = uid.to_string();
+
+        let ranges = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.trim().splitn(3, ':');
+                let owner = fields.next()?;
+                if Some(owner) != user && owner != uid {
+                    return None;
+                }
+
+                let start: u64 = fields.next()?.parse().ok()?;
+                let count: u64 = fields.next()?.parse().ok()?;
+                Some(start..start + count)
+            })
+            .collect();
+
+        Self(ranges)
+    }
+
+    /// True if all the host ids of the map are delegated to the user.
+    pub fn contains(&self, map: &IdMap) -> bool {
+        let outside = map.outside_range();
+        self.0.iter().any(|range| {
+            range.start <= outside.start && outside.end <= range.end
+        })
+    }
+}
+
+/// Writes the uid and gid maps of the user namespace of the process `pid`.
+/// Each file can only be written once, with all the mappings in a single write.
+pub(crate) fn write_id_maps(
+    pid: Pid,
+    uid_map: &[IdMap],
+    gid_map: &[IdMap],
+) -> io::Result<()> {
+    let to_string = |maps: &[IdMap]| {
+        maps.iter().map(|map| format!("{map}\n")).collect::<String>()
+    };
+
+    fs::write(format!("/proc/{pid}/uid_map"), to_string(uid_map))?;
+    fs::write(format!("/proc/{pid}/gid_map"), to_string(gid_map))?;
+    Ok(())
+}
+
+/// Blocks the child until the parent signals on `ready` that the id maps are written.
+pub(crate) fn wait_for_id_maps(ready: &OwnedFd) -> io::Result<()> {
+    let mut buf = [0; 1];
+    let read = nix::unistd::read(ready.as_raw_fd(), &mut buf)
+        .map_err(|e| io::Error::from_raw_os_error(e as i32))?;
+
+    // The parent closed the pipe without signaling
+    if read == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            "id maps of the user namespace were not written",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Gives the root of the user namespace ownership of `path`, so the nested auraed can write to it.
+pub(crate) fn chown_to_root(
+    path: &Path,
+    uid_map: &[IdMap],
+    gid_map: &[IdMap],
+) -> io::Result<()> {
+    let (Some(uid), Some(gid)) =
+        (IdMap::to_outside(uid_map, 0), IdMap::to_outside(gid_map, 0))
+    else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "root of the user namespace is not mapped",
+        ));
+    };
+
+    std::os::unix::fs::chown(path, Some(uid), Some(gid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subordinate_ids_parse() {
+        let contents = "alice:100000:65536\n0:200000:65536\nroot:300000:1000\n";
+
+        let ids = SubordinateIds::parse(contents, Some("root"), 0);
+        assert_eq!(ids, SubordinateIds(vec![200000..265536, 300000..301000]));
+
+        let ids = SubordinateIds::parse(contents, Some("bob"), 1000);
+        assert_eq!(ids, SubordinateIds::default());
+    }
+
+    #[test]
+    fn test_subordinate_ids_contains() {
+        let ids = SubordinateIds(vec![100000..165536]);
+
+        assert!(ids.contains(&IdMap {
+            inside: 0,
+            outside: 100000,
+            count: 65536
+        }));
+        assert!(ids.contains(&IdMap { inside: 0, outside: 165535, count: 1 }));
+        assert!(!ids.contains(&IdMap {
+            inside: 0,
+            outside: 100000,
+            count: 65537
+        }));
+        assert!(!ids.contains(&IdMap { inside: 0, outside: 0, count: 1 }));
+    }
+
+    #[test]
+    fn test_id_map_to_outside() {
+        let maps = [
+            IdMap { inside: 0, outside: 100000, count: 1000 },
+            IdMap { inside: 1000, outside: 5000, count: 1 },
+        ];
+
+        assert_eq!(IdMap::to_outside(&maps, 0), Some(100000));
+        assert_eq!(IdMap::to_outside(&maps, 999), Some(100999));
+        assert_eq!(IdMap::to_outside(&maps, 1000), Some(5000));
+        assert_eq!(IdMap::to_outside(&maps, 1001), None);
+    }
+}
@@ -0,0 +1,183 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use super::super::CellName;
+use ipnetwork::Ipv4Network;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, ErrorKind, Write},
+    path::PathBuf,
+};
+
+/// Hands out the addresses of a pool, recording each lease as a file named after the
+/// address in `leases_dir`. Creating the file is atomic, so an address is never leased twice,
+/// and the leases outlive a restart of auraed.
+#[derive(Debug)]
+pub(crate) struct Ipam {
+    network: Ipv4Network,
+    leases_dir: PathBuf,
+}
+
+impl Ipam {
+    pub fn new(network: Ipv4Network, leases_dir: PathBuf) -> Self {
+        Self { network, leases_dir }
+    }
+
+    /// The first address of the pool, which is assigned to the bridge.
+    pub fn gateway(&self) -> Ipv4Network {
+        Ipv4Network::new(
+            self.network.nth(1).expect("pool has a gateway"),
+            self.network.prefix(),
+        )
+        .expect("valid prefix")
+    }
+
+    /// Leases the first free address of the pool to `cell_name`.
+    pub fn lease(&self, cell_name: &CellName) -> io::Result<Lease> {
+        fs::create_dir_all(&self.leases_dir)?;
+
+        let broadcast = self.network.broadcast();
+        // Skip the network address and the gateway
+        for ip in self.network.iter().skip(2).filter(|ip| *ip != broadcast) {
+            let path = self.leases_dir.join(ip.to_string());
+            let mut file = match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => file,
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            };
+
+            let lease = Lease {
+                address: Ipv4Network::new(ip, self.network.prefix())
+                    .expect("valid prefix"),
+                path,
+            };
+
+            if let Err(e) = writeln!(file, "{cell_name}") {
+                let _best_effort = lease.release();
+                return Err(e);
+            }
+
+            return Ok(lease);
+        }
+
+        Err(io::Error::new(
+            ErrorKind::Other,
+            format!("no free address in {}", self.network),
+        ))
+    }
+
+    /// Returns the existing lease of `address` to `cell_name`, such as one taken by a
+    /// previous run of auraed.
+    pub fn leased(
+        &self,
+        cell_name: &CellName,
+        address: Ipv4Network,
+    ) -> io::Result<Lease> {
+        let path = self.leases_dir.join(address.ip().to_string());
+
+        let leased_to = fs::read_to_string(&path)?;
+        if leased_to.trim_end() != cell_name.to_string() {
+            return Err(io::Error::new(
+                ErrorKind::Other,
+                format!("{} is not leased to {cell_name}", address.ip()),
+            ));
+        }
+
+        Ok(Lease { address, path })
+    }
+}
+
+/// An address leased to a cell, until it is released.
+#[derive(Debug)]
+pub(crate) struct Lease {
+    address: Ipv4Network,
+    path: PathBuf,
+}
+
+impl Lease {
+    /// The address, with the prefix of the pool.
+    pub fn address(&self) -> Ipv4Network {
+        self.address
+    }
+
+    pub fn release(&self) -> io::Result<()> {
+        fs::remove_file(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipam(network: &str) -> Ipam {
+        let leases_dir = std::env::temp_dir()
+            .join(format!("aurae-ipam-{}", uuid::Uuid::new_v4()));
+        Ipam::new(network.parse().expect("valid network"), leases_dir)
+    }
+
+    #[test]
+    fn test_lease_skips_leased_addresses() {
+        let ipam = ipam("10.88.0.0/29");
+        let cell_name = CellName::random_for_tests();
+        assert_eq!(ipam.gateway().to_string(), "10.88.0.1/29");
+
+        let first = ipam.lease(&cell_name).expect("lease");
+        let second = ipam.lease(&cell_name).expect("lease");
+        assert_eq!(first.address().to_string(), "10.88.0.2/29");
+        assert_eq!(second.address().to_string(), "10.88.0.3/29");
+
+        // A released address is leased again
+        first.release().expect("release");
+        let third = ipam.lease(&cell_name).expect("lease");
+        assert_eq!(third.address().to_string(), "10.88.0.2/29");
+
+        let _ = fs::remove_dir_all(&ipam.leases_dir);
+    }
+
+    #[test]
+    fn test_lease_fails_when_pool_is_exhausted() {
+        // Network, gateway, one cell address and broadcast
+        let ipam = ipam("10.88.0.0/30");
+        let cell_name = CellName::random_for_tests();
+
+        let lease = ipam.lease(&cell_name).expect("lease");
+        assert_eq!(lease.address().to_string(), "10.88.0.2/30");
+        assert!(ipam.lease(&cell_name).is_err());
+
+        let _ = fs::remove_dir_all(&ipam.leases_dir);
+    }
+
+    #[test]
+    fn test_leased_returns_lease_of_cell() {
+        let ipam = ipam("10.88.0.0/29");
+        let cell_name = CellName::random_for_tests();
+        let other_cell_name = CellName::random_for_tests();
+
+        let lease = ipam.lease(&cell_name).expect("lease");
+
+        let leased = ipam.leased(&cell_name, lease.address()).expect("leased");
+        assert_eq!(leased.address(), lease.address());
+        assert!(ipam.leased(&other_cell_name, lease.address()).is_err());
+
+        // An address that was released is no longer leased
+        lease.release().expect("release");
+        assert!(ipam.leased(&cell_name, lease.address()).is_err());
+
+        let _ = fs::remove_dir_all(&ipam.leases_dir);
+    }
+}
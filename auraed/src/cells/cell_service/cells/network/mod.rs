@@ -0,0 +1,154 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use self::ipam::{Ipam, Lease};
+use super::CellName;
+use crate::{init::network::Network, AURAED_RUNTIME};
+use anyhow::{anyhow, Context};
+use ipnetwork::{IpNetwork, Ipv4Network};
+use nix::{sched::CloneFlags, unistd::Pid};
+use std::{fs::File, future::Future};
+use tracing::info;
+
+mod ipam;
+
+/// Name of the cell's end of the veth pair, inside its network namespace.
+const CELL_IFACE: &str = "eth0";
+
+/// Connects the network namespace of a cell to the host, with a veth pair
+/// attached to the cell bridge and an address leased from the cell network.
+#[derive(Debug)]
+pub struct CellNetwork {
+    host_iface: String,
+    lease: Lease,
+}
+
+impl CellNetwork {
+    /// Creates the veth pair of the cell whose nested auraed is `pid`.
+    /// The bridge is created on first use, with the gateway address of the pool.
+    pub fn new(cell_name: &CellName, pid: Pid) -> anyhow::Result<Self> {
+        let runtime = AURAED_RUNTIME.get().expect("runtime");
+        let ipam =
+            Ipam::new(runtime.cell_network, runtime.runtime_dir.join("ipam"));
+        let lease = ipam.lease(cell_name)?;
+
+        // Interface names are limited to 15 bytes
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        let host_iface = format!("ae-{}", &id[..8]);
+        let peer_iface = format!("{host_iface}-c");
+
+        let res = block_on(async {
+            let host = Network::connect()?;
+            host.ensure_bridge(&runtime.cell_bridge, ipam.gateway()).await?;
+            host.add_veth_to_netns(
+                &host_iface,
+                &peer_iface,
+                &runtime.cell_bridge,
+                pid.as_raw() as u32,
+            )
+            .await?;
+
+            // Netlink sockets act on the network namespace they are created in
+            let netns = File::open(format!("/proc/{pid}/ns/net"))?;
+            nix::sched::setns(netns, CloneFlags::CLONE_NEWNET)?;
+
+            let cell = Network::connect()?;
+            cell.configure_veth(
+                &peer_iface,
+                CELL_IFACE,
+                lease.address(),
+                ipam.gateway().ip(),
+            )
+            .await?;
+
+            Ok::<_, anyhow::Error>(())
+        });
+
+        let network = Self { host_iface, lease };
+        if let Err(e) = res {
+            let _best_effort = network.delete();
+            return Err(e.context(format!(
+                "failed to connect cell '{cell_name}' to bridge '{}'",
+                runtime.cell_bridge
+            )));
+        }
+
+        info!(
+            "Connected cell {cell_name} to {} with address {}",
+            runtime.cell_bridge,
+            network.lease.address()
+        );
+
+        Ok(network)
+    }
+
+    /// Takes over the veth pair and lease of a cell connected by a previous run of auraed.
+    pub fn adopt(
+        cell_name: &CellName,
+        host_iface: String,
+        address: Ipv4Network,
+    ) -> anyhow::Result<Self> {
+        let runtime = AURAED_RUNTIME.get().expect("runtime");
+        let ipam =
+            Ipam::new(runtime.cell_network, runtime.runtime_dir.join("ipam"));
+        let lease = ipam.leased(cell_name, address).with_context(|| {
+            format!("failed to find the lease of cell '{cell_name}'")
+        })?;
+
+        Ok(Self { host_iface, lease })
+    }
+
+    pub fn addresses(&self) -> Vec<IpNetwork> {
+        vec![self.lease.address().into()]
+    }
+
+    /// The name of the host end of the veth pair.
+    pub fn host_iface(&self) -> &str {
+        &self.host_iface
+    }
+
+    /// The address leased to the cell.
+    pub fn address(&self) -> Ipv4Network {
+        self.lease.address()
+    }
+
+    /// Deletes the veth pair, if it was not already deleted with the network namespace
+    /// of the cell, and releases the address of the cell.
+    pub fn delete(self) -> anyhow::Result<()> {
+        let _already_deleted = block_on(async {
+            Network::connect()?.delete_link(&self.host_iface).await?;
+            Ok::<_, anyhow::Error>(())
+        });
+
+        self.lease.release().context("failed to release address")
+    }
+}
+
+/// Runs the netlink requests on their own thread, as they may enter the network
+/// namespace of a cell, and the cells are managed from synchronous code.
+fn block_on<F>(future: F) -> anyhow::Result<()>
+where
+    F: Future<Output = anyhow::Result<()>> + Send,
+{
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(future)
+        })
+        .join()
+        .map_err(|_| anyhow!("netlink thread panicked"))?
+    })
+}
@@ -42,7 +42,8 @@ impl From<CellsServiceError> for Status {
         error!("{msg}");
         match err {
             CellsServiceError::CellsError(e) => match e {
-                CellsError::CgroupIsNotACell { .. } => {
+                CellsError::CgroupIsNotACell { .. }
+                | CellsError::CellPaused { .. } => {
                     Status::failed_precondition(msg)
                 }
                 CellsError::CellExists { .. } => Status::already_exists(msg),
@@ -50,8 +51,18 @@ impl From<CellsServiceError> for Status {
                 | CellsError::CgroupNotFound { .. } => Status::not_found(msg),
                 CellsError::FailedToAllocateCell { .. }
                 | CellsError::AbortedAllocateCell { .. }
+                | CellsError::FailedToAdoptCell { .. }
+                | CellsError::FailedToSetUpCellNetwork { .. }
                 | CellsError::FailedToKillCellChildren { .. }
-                | CellsError::FailedToFreeCell { .. } => Status::internal(msg),
+                | CellsError::FailedToFreeCell { .. }
+                | CellsError::FailedToSignalCell { .. }
+                | CellsError::FailedToUpdateCell { .. }
+                | CellsError::FailedToPauseCell { .. }
+                | CellsError::FailedToResumeCell { .. }
+                | CellsError::FailedToReadCellEvents { .. }
+                | CellsError::FailedToReadCellStats { .. } => {
+                    Status::internal(msg)
+                }
                 CellsError::CellNotAllocated { cell_name } => {
                     CellsServiceError::CellsError(CellsError::CellNotFound {
                         cell_name,
@@ -66,8 +77,14 @@ impl From<CellsServiceError> for Status {
                 ExecutablesError::ExecutableNotFound { .. } => {
                     Status::not_found(msg)
                 }
+                ExecutablesError::ExecutableNotRunning { .. }
+                | ExecutablesError::ExecutableStopping { .. } => {
+                    Status::failed_precondition(msg)
+                }
                 ExecutablesError::FailedToStartExecutable { .. }
-                | ExecutablesError::FailedToStopExecutable { .. } => {
+                | ExecutablesError::FailedToStopExecutable { .. }
+                | ExecutablesError::FailedToSignalExecutable { .. }
+                | ExecutablesError::FailedToWaitForExecutable { .. } => {
                     Status::internal(msg)
                 }
             },
@@ -79,4 +96,4 @@ impl From<CellsServiceError> for Status {
             CellsServiceError::ObserveServiceError(e) => e.into(),
         }
     }
-}
\ No newline at end of file
+}
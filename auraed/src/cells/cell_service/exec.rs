@@ -0,0 +1,269 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use super::executables::SecurityProfile;
+use bytes::Bytes;
+use proto::cells::{
+    CellServiceExecRequest, CellServiceExecResponse, ExitStatus, TerminalSize,
+};
+use std::{
+    fs::File,
+    io,
+    os::{
+        fd::{AsRawFd, OwnedFd},
+        unix::process::ExitStatusExt,
+    },
+    process::Stdio,
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    process::Command,
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Status, Streaming};
+
+/// The largest chunk of output sent in a single response.
+const OUTPUT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// How long to wait for the output of an exited command to be sent. Commands that leave their
+/// output open (e.g., by leaving a background job running) would otherwise never get to send
+/// their exit status.
+const OUTPUT_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+type ExecResponseSender =
+    mpsc::Sender<std::result::Result<CellServiceExecResponse, Status>>;
+
+/// A one-off command run with `CellService.Exec`. Unlike an executable, it is not supervised,
+/// and it only runs while the client is attached.
+#[derive(Debug)]
+pub struct ExecSpec {
+    pub command: Command,
+    /// The size of the pseudo-terminal allocated for the command, if any.
+    pub tty: Option<TerminalSize>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub security_profile: Option<SecurityProfile>,
+}
+
+/// Spawns the command, and forwards the stdin and terminal resizes of `requests` to it.
+/// The returned stream sends the output of the command, followed by its exit status.
+/// The command is killed if the stream is dropped before the command exits.
+pub fn spawn(
+    spec: ExecSpec,
+    requests: Streaming<CellServiceExecRequest>,
+) -> io::Result<
+    ReceiverStream<std::result::Result<CellServiceExecResponse, Status>>,
+> {
+    let ExecSpec { mut command, tty, uid, gid, security_profile } = spec;
+
+    let _ = command.kill_on_drop(true);
+
+    let terminal = match tty {
+        Some(size) => {
+            let pty = nix::pty::openpty(Some(&winsize(&size)), None)?;
+            let _ = command
+                .stdin(Stdio::from(pty.slave.try_clone()?))
+                .stdout(Stdio::from(pty.slave.try_clone()?))
+                .stderr(Stdio::from(pty.slave));
+
+            // SAFETY: setsid and ioctl are async-signal-safe and do not allocate
+            unsafe {
+                let _ = command.pre_exec(|| {
+                    // The terminal becomes the controlling terminal of a new session, so that
+                    // the command gets the signals of the terminal (e.g., SIGINT on Ctrl-C)
+                    let _ = nix::unistd::setsid()?;
+                    if libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+
+            Some(pty.master)
+        }
+        None => {
+            let _ = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            None
+        }
+    };
+
+    // Same as for executables, but the security profile is applied after the session is set up
+    if let Some(security_profile) = security_profile {
+        let pre_exec = security_profile.pre_exec(uid, gid)?;
+        // SAFETY: the profile is prepared before forking, so applying it only makes syscalls
+        unsafe {
+            let _ = command.pre_exec(pre_exec);
+        }
+    } else {
+        if let Some(uid) = uid {
+            let _ = command.uid(uid);
+        }
+        if let Some(gid) = gid {
+            let _ = command.gid(gid);
+        }
+    }
+
+    let mut child = command.spawn()?;
+    // The command holds copies of the terminal, which must be closed for reads of the terminal
+    // to end once the process exits
+    drop(command);
+
+    let (tx, rx) = mpsc::channel(16);
+
+    let mut outputs = match terminal {
+        Some(terminal) => {
+            // Reads of the terminal block, so they are made on a blocking thread by tokio
+            let output =
+                tokio::fs::File::from_std(File::from(terminal.try_clone()?));
+            let input =
+                tokio::fs::File::from_std(File::from(terminal.try_clone()?));
+
+            let _ignored =
+                tokio::spawn(forward_input(requests, input, Some(terminal)));
+
+            vec![forward_output(output, tx.clone(), |stdout| {
+                CellServiceExecResponse { stdout, ..Default::default() }
+            })]
+        }
+        None => {
+            let stdin = child.stdin.take().expect("piped stdin");
+            let stdout = child.stdout.take().expect("piped stdout");
+            let stderr = child.stderr.take().expect("piped stderr");
+
+            let _ignored = tokio::spawn(forward_input(requests, stdin, None));
+
+            vec![
+                forward_output(stdout, tx.clone(), |stdout| {
+                    CellServiceExecResponse { stdout, ..Default::default() }
+                }),
+                forward_output(stderr, tx.clone(), |stderr| {
+                    CellServiceExecResponse { stderr, ..Default::default() }
+                }),
+            ]
+        }
+    };
+
+    let _ignored = tokio::spawn(async move {
+        let status = tokio::select! {
+            status = child.wait() => status,
+            // The client went away, so the child is dropped, which kills the command
+            _ = tx.closed() => return,
+        };
+
+        // The exit status is sent last, once all the output has been sent
+        let _ = tokio::time::timeout(OUTPUT_FLUSH_TIMEOUT, async {
+            for output in outputs.iter_mut() {
+                let _ = output.await;
+            }
+        })
+        .await;
+
+        // Output that is still open is not sent after the exit status
+        for output in outputs {
+            output.abort();
+        }
+
+        let res = status
+            .map(|status| CellServiceExecResponse {
+                exit_status: Some(ExitStatus {
+                    code: status.code(),
+                    signal: status.signal(),
+                }),
+                ..Default::default()
+            })
+            .map_err(|e| {
+                Status::internal(format!("failed to wait for command: {e}"))
+            });
+
+        let _ = tx.send(res).await;
+    });
+
+    Ok(ReceiverStream::new(rx))
+}
+
+/// Writes the stdin of the requests to `input`, and resizes the `terminal`, if any.
+/// `input` is closed once the client is done sending, so that the command sees the end of
+/// its input.
+async fn forward_input<W>(
+    mut requests: Streaming<CellServiceExecRequest>,
+    mut input: W,
+    terminal: Option<OwnedFd>,
+) where
+    W: AsyncWrite + Unpin,
+{
+    while let Ok(Some(request)) = requests.message().await {
+        if let (Some(size), Some(terminal)) = (&request.resize, &terminal) {
+            // SAFETY: TIOCSWINSZ only reads the size
+            let _best_effort = unsafe {
+                libc::ioctl(
+                    terminal.as_raw_fd(),
+                    libc::TIOCSWINSZ,
+                    &winsize(size),
+                )
+            };
+        }
+
+        if request.stdin.is_empty() {
+            continue;
+        }
+
+        if input.write_all(&request.stdin).await.is_err()
+            || input.flush().await.is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Sends what is read from `output` until it is closed, or the client goes away.
+fn forward_output<R>(
+    mut output: R,
+    tx: ExecResponseSender,
+    response: fn(Bytes) -> CellServiceExecResponse,
+) -> JoinHandle<()>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+{
+    tokio::spawn(async move {
+        let mut buf = vec![0; OUTPUT_CHUNK_SIZE];
+        loop {
+            // Reading a terminal fails with EIO once every process using it has exited
+            let n = match output.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+
+            let res = response(Bytes::copy_from_slice(&buf[..n]));
+            if tx.send(Ok(res)).await.is_err() {
+                break;
+            }
+        }
+    })
+}
+
+fn winsize(size: &TerminalSize) -> libc::winsize {
+    libc::winsize {
+        ws_row: size.rows.try_into().unwrap_or(u16::MAX),
+        ws_col: size.columns.try_into().unwrap_or(u16::MAX),
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
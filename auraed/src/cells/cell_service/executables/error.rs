@@ -23,8 +23,12 @@ pub type Result<T> = std::result::Result<T, ExecutablesError>;
 pub enum ExecutablesError {
     #[error("executable '{executable_name}' exists")]
     ExecutableExists { executable_name: ExecutableName },
+    #[error("executable '{executable_name}' is still stopping")]
+    ExecutableStopping { executable_name: ExecutableName },
     #[error("executable '{executable_name}' not found")]
     ExecutableNotFound { executable_name: ExecutableName },
+    #[error("executable '{executable_name}' is not running")]
+    ExecutableNotRunning { executable_name: ExecutableName },
     #[error("executable '{executable_name}' failed to start: {source}")]
     FailedToStartExecutable {
         executable_name: ExecutableName,
@@ -35,4 +39,14 @@ pub enum ExecutablesError {
         executable_name: ExecutableName,
         source: io::Error,
     },
-}
\ No newline at end of file
+    #[error("executable '{executable_name}' failed to be signaled: {source}")]
+    FailedToSignalExecutable {
+        executable_name: ExecutableName,
+        source: io::Error,
+    },
+    #[error("failed to wait for executable '{executable_name}': {source}")]
+    FailedToWaitForExecutable {
+        executable_name: ExecutableName,
+        source: io::Error,
+    },
+}
@@ -12,18 +12,26 @@
  * Copyright 2022 - 2024, the aurae contributors                              *
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
-use super::{ExecutableName, ExecutableSpec};
-use crate::logging::log_channel::LogChannel;
-use nix::unistd::Pid;
+use super::{
+    pidfd::PidFd, supervisor::Supervisor, ExecutableName, ExecutableSpec,
+    RestartPolicy, SecurityProfile,
+};
+use crate::{
+    logging::{log_channel::LogChannel, log_store::LogStore},
+    AURAED_RUNTIME,
+};
+use nix::{errno::Errno, sys::signal::Signal, unistd::Pid};
+use proto::observe::LogChannelType;
 use std::{
     ffi::OsString,
+    future::Future,
     io,
     process::{ExitStatus, Stdio},
+    time::{Duration, SystemTime},
 };
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
-use tokio::task::JoinHandle;
-use tracing::info_span;
+use tokio::process::Command;
+use tokio::sync::{oneshot, watch};
+use tracing::warn;
 
 // TODO: decide if we're going to use the description or not.  Remove if not.
 #[allow(dead_code)]
@@ -36,130 +44,342 @@ pub struct Executable {
     state: ExecutableState,
 }
 
+/// How and when a started [Executable] exited.
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutableExit {
+    pub status: ExitStatus,
+    pub exited_at: SystemTime,
+}
+
+/// How to stop the process of an [Executable].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopOptions {
+    /// The signal sent to ask the process to exit.
+    pub signal: Signal,
+    /// How long the process has to exit after receiving `signal`, before it is sent [SIGKILL].
+    ///
+    /// [SIGKILL]: Signal::SIGKILL
+    pub grace_period: Duration,
+}
+
+impl StopOptions {
+    pub const DEFAULT_SIGNAL: Signal = Signal::SIGTERM;
+    pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+}
+
+impl Default for StopOptions {
+    fn default() -> Self {
+        Self {
+            signal: Self::DEFAULT_SIGNAL,
+            grace_period: Self::DEFAULT_GRACE_PERIOD,
+        }
+    }
+}
+
+/// Where in its lifecycle an [Executable] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutablePhase {
+    /// The executable has not been started.
+    Init,
+    /// The process of the executable is running.
+    Running,
+    /// The process exited and will be restarted once its backoff has elapsed.
+    Restarting,
+    /// The process exited after too many consecutive restarts and will not be restarted.
+    CrashLoop,
+    /// The process exited, or was stopped, and will not be restarted.
+    Exited,
+}
+
+impl ExecutablePhase {
+    /// Returns true if the executable will not run again.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::CrashLoop | Self::Exited)
+    }
+}
+
+/// A snapshot of the lifecycle of an [Executable].
+#[derive(Debug, Clone)]
+pub struct ExecutableStatus {
+    pub name: ExecutableName,
+    pub phase: ExecutablePhase,
+    /// The pid of the most recent run of the process.
+    pub pid: Option<Pid>,
+    /// When the most recent run of the process started.
+    pub started_at: Option<SystemTime>,
+    /// How the most recent run of the process exited.
+    pub exit: Option<ExecutableExit>,
+    /// The number of times the process has been restarted.
+    pub restart_count: u32,
+}
+
+impl ExecutableStatus {
+    fn new(name: ExecutableName) -> Self {
+        Self {
+            name,
+            phase: ExecutablePhase::Init,
+            pid: None,
+            started_at: None,
+            exit: None,
+            restart_count: 0,
+        }
+    }
+}
+
 #[derive(Debug)]
 enum ExecutableState {
     Init {
         command: Command,
+        restart_policy: RestartPolicy,
+        security_profile: Option<SecurityProfile>,
     },
     Started {
         #[allow(unused)]
         program: OsString,
         #[allow(unused)]
         args: Vec<OsString>,
-        child: Child,
-        stdout: JoinHandle<()>,
-        stderr: JoinHandle<()>,
+        status: watch::Receiver<ExecutableStatus>,
+        pidfd: watch::Receiver<Option<PidFd>>,
+        /// Taken when the executable is stopped.
+        /// Dropping the [Executable] drops the sender, which kills the process without a
+        /// grace period.
+        stop: Option<oneshot::Sender<StopOptions>>,
     },
-    Stopped(ExitStatus),
 }
 
 impl Executable {
+    /// Creates an [Executable], removing any output left behind by an earlier executable with
+    /// the same name.
     pub fn new<T: Into<ExecutableSpec>>(spec: T) -> Self {
-        let ExecutableSpec { name, description, command } = spec.into();
-        let state = ExecutableState::Init { command };
-        let stdout = LogChannel::new(format!("{name}::stdout"));
-        let stderr = LogChannel::new(format!("{name}::stderr"));
+        Self::create(spec.into(), false)
+    }
+
+    /// Creates an [Executable] that keeps the output of an earlier run, e.g., before auraed
+    /// restarted.
+    pub fn restore<T: Into<ExecutableSpec>>(spec: T) -> Self {
+        Self::create(spec.into(), true)
+    }
+
+    fn create(spec: ExecutableSpec, keep_history: bool) -> Self {
+        let ExecutableSpec {
+            name,
+            description,
+            command,
+            restart_policy,
+            security_profile,
+        } = spec;
+        let state =
+            ExecutableState::Init { command, restart_policy, security_profile };
+        let stdout = log_channel(&name, LogChannelType::Stdout, keep_history);
+        let stderr = log_channel(&name, LogChannelType::Stderr, keep_history);
         Self { name, description, stdout, stderr, state }
     }
 
-    /// Starts the underlying process.
-    /// Does nothing if [Executable] has previously been started.
+    /// Starts the underlying process, and supervises it according to its [RestartPolicy].
+    /// Returns the [Executable] unchanged if it has previously been started.
     pub fn start(
-        &mut self,
+        mut self,
         uid: Option<u32>,
         gid: Option<u32>,
-    ) -> io::Result<()> {
-        let ExecutableState::Init { command } = &mut self.state else {
-            return Ok(());
+    ) -> io::Result<Self> {
+        let ExecutableState::Init {
+            mut command,
+            restart_policy,
+            security_profile,
+        } = self.state
+        else {
+            return Ok(self);
         };
 
-        let mut command = command
+        let _ = command
             .kill_on_drop(true)
-            .current_dir("/")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
-        if uid.is_some() {
-            command = command.uid(uid.expect("uid"));
-        }
-        if gid.is_some() {
-            command = command.gid(gid.expect("gid"));
-        }
-        let mut child = command.spawn()?;
-
-        let log_channel = self.stdout.clone();
-        let stdout = child.stdout.take().expect("stdout");
-        let span = info_span!("running process", name = ?self.name);
-        let stdout = tokio::spawn(async move {
-            let log_channel = log_channel;
-            let mut span = Some(span);
-            let mut stdout = BufReader::new(stdout).lines();
-            while let Ok(Some(line)) = stdout.next_line().await {
-                let entered_span = span.take().expect("span").entered();
-                //info!(level = "info", channel = log_channel.name, line);
-                // if std::env::var("AER").is_ok() {
-                //     println!("{line}");
-                // }
-                log_channel.send(line);
-                span = Some(entered_span.exit());
+        if let Some(security_profile) = security_profile {
+            // The profile switches to the uid and gid itself, as it needs privileges to be applied
+            let pre_exec = security_profile.pre_exec(uid, gid)?;
+            // SAFETY: the profile is prepared before forking, so applying it only makes syscalls
+            unsafe {
+                let _ = command.pre_exec(pre_exec);
+            }
+        } else {
+            if let Some(uid) = uid {
+                let _ = command.uid(uid);
             }
-        });
-
-        let log_channel = self.stderr.clone();
-        let stderr = child.stderr.take().expect("stderr");
-        let span = info_span!("running process", name = ?self.name);
-        let stderr = tokio::spawn(async move {
-            let log_channel = log_channel;
-            let mut span = Some(span);
-            let mut stderr = BufReader::new(stderr).lines();
-            while let Ok(Some(line)) = stderr.next_line().await {
-                let entered_span = span.take().expect("span").entered();
-                // info!(level = "error", channel = log_channel.name, line);
-                // if std::env::var("AER").is_ok() {
-                //     println!("{line}");
-                // }
-                log_channel.send(line);
-                span = Some(entered_span.exit());
+            if let Some(gid) = gid {
+                let _ = command.gid(gid);
             }
-        });
+        }
+
+        let program = command.as_std().get_program().to_os_string();
+        let args =
+            command.as_std().get_args().map(|arg| arg.to_os_string()).collect();
+
+        let (status_tx, status) =
+            watch::channel(ExecutableStatus::new(self.name.clone()));
+        let (pidfd_tx, pidfd) = watch::channel(None);
+        let mut supervisor = Supervisor {
+            name: self.name.clone(),
+            command,
+            restart_policy,
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+            status: status_tx,
+            pidfd: pidfd_tx,
+        };
+
+        // The first run is spawned here, so that failing to start is reported to the caller
+        let run = supervisor.spawn()?;
+        // The supervisor publishes its progress to `status`, so it is not joined
+        let (stop, stop_rx) = oneshot::channel();
+        let _ignored = tokio::spawn(supervisor.run(run, stop_rx));
 
         self.state = ExecutableState::Started {
-            program: command.as_std().get_program().to_os_string(),
-            args: command
-                .as_std()
-                .get_args()
-                .map(|arg| arg.to_os_string())
-                .collect(),
-            child,
-            stdout,
-            stderr,
+            program,
+            args,
+            status,
+            pidfd,
+            stop: Some(stop),
         };
 
-        Ok(())
+        Ok(self)
     }
 
-    /// Stops the executable and returns the [ExitStatus].
-    /// If the executable has never been started, returns [None].
-    pub async fn kill(&mut self) -> io::Result<Option<ExitStatus>> {
-        Ok(match &mut self.state {
-            ExecutableState::Init { .. } => None,
-            ExecutableState::Started { child, stdout, stderr, .. } => {
-                child.kill().await?;
-                let exit_status = child.wait().await?;
-                let _ = tokio::join!(stdout, stderr);
-                self.state = ExecutableState::Stopped(exit_status);
-                Some(exit_status)
+    /// Stops the executable, signaling its process if it is running, and returns a future that
+    /// resolves to its final status once the process has exited.
+    /// The future does not borrow the [Executable], so it can be awaited after releasing any
+    /// lock guarding it.
+    /// Resolves to [None] if the executable has never been started.
+    pub fn stop(
+        &mut self,
+        options: StopOptions,
+    ) -> impl Future<Output = io::Result<Option<ExecutableStatus>>> + Send + 'static
+    {
+        if let ExecutableState::Started { stop, .. } = &mut self.state {
+            if let Some(stop) = stop.take() {
+                // The supervisor may have already finished, in which case there is no receiver
+                let _ = stop.send(options);
             }
-            ExecutableState::Stopped(status) => Some(*status),
-        })
+        }
+
+        self.wait()
     }
 
-    /// Returns the [Pid] while [Executable] is running, otherwise returns [None].
-    pub fn pid(&self) -> io::Result<Option<Pid>> {
-        let ExecutableState::Started { child: process, .. } = &self.state
-        else {
+    /// Returns a future that resolves to the final status of the [Executable], once it has
+    /// exited and will not be restarted.
+    /// The future does not borrow the [Executable], so it can be awaited after releasing any
+    /// lock guarding it.
+    /// Resolves to [None] if the executable has never been started.
+    pub fn wait(
+        &self,
+    ) -> impl Future<Output = io::Result<Option<ExecutableStatus>>> + Send + 'static
+    {
+        let status = self.subscribe();
+
+        async move {
+            let Some(mut status) = status else {
+                return Ok(None);
+            };
+
+            let status = status
+                .wait_for(|status| status.phase.is_finished())
+                .await
+                .map_err(|_| {
+                    io::Error::other("executable is no longer supervised")
+                })?;
+
+            Ok(Some(status.clone()))
+        }
+    }
+
+    /// Sends `signal` to the process of the [Executable], and returns its [Pid].
+    /// Returns [None] if the process is not running, including while it is waiting to be
+    /// restarted.
+    pub fn signal(&self, signal: Signal) -> io::Result<Option<Pid>> {
+        let ExecutableState::Started { pidfd, .. } = &self.state else {
             return Ok(None);
         };
 
-        Ok(process.id().map(|id| Pid::from_raw(id as i32)))
+        let pidfd = pidfd.borrow();
+        let Some(pidfd) = pidfd.as_ref() else {
+            return Ok(None);
+        };
+
+        // The signal is sent through a pidfd, as the pid may have been reused once the
+        // process has exited and been reaped
+        match pidfd.send_signal(signal) {
+            Ok(()) => Ok(Some(pidfd.pid())),
+            Err(e) if e.raw_os_error() == Some(Errno::ESRCH as i32) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns a receiver that is notified of every change to the status of the [Executable].
+    /// Returns [None] if the executable has never been started.
+    pub fn subscribe(&self) -> Option<watch::Receiver<ExecutableStatus>> {
+        match &self.state {
+            ExecutableState::Init { .. } => None,
+            ExecutableState::Started { status, .. } => Some(status.clone()),
+        }
+    }
+
+    /// Returns the [Pid] of the most recent run of the [Executable], otherwise returns [None].
+    /// The [Pid] is still returned after the executable has exited.
+    pub fn pid(&self) -> Option<Pid> {
+        match &self.state {
+            ExecutableState::Init { .. } => None,
+            ExecutableState::Started { status, .. } => status.borrow().pid,
+        }
+    }
+
+    /// Returns a snapshot of the lifecycle of the [Executable].
+    pub fn status(&self) -> ExecutableStatus {
+        match &self.state {
+            ExecutableState::Init { .. } => {
+                ExecutableStatus::new(self.name.clone())
+            }
+            ExecutableState::Started { status, .. } => status.borrow().clone(),
+        }
+    }
+}
+
+/// Creates the log channel for one of the output streams of an executable.
+/// The channel persists its history under the runtime's `logs_dir`, which keeps earlier
+/// output of the executable around across restarts and crashes of its process.
+/// Unless `keep_history` is set, history left behind by an earlier executable with the same
+/// name is removed.
+fn log_channel(
+    name: &ExecutableName,
+    stream: LogChannelType,
+    keep_history: bool,
+) -> LogChannel {
+    let stream_name = match stream {
+        LogChannelType::Stderr => "stderr",
+        _ => "stdout",
+    };
+    let channel = LogChannel::new(format!("{name}::{stream_name}"))
+        .with_executable(name.to_string(), stream);
+
+    let Some(runtime) = AURAED_RUNTIME.get() else {
+        return channel;
+    };
+
+    // Executable names are not restricted, so keep them from escaping the logs directory
+    let dir = name.to_string().replace('/', "_");
+    let dir = if dir.starts_with('.') { format!("_{dir}") } else { dir };
+    let path = runtime.logs_dir.join(dir).join(format!("{stream_name}.log"));
+
+    let store = if keep_history {
+        LogStore::open(&path)
+    } else {
+        LogStore::create(&path)
+    };
+
+    match store {
+        Ok(store) => channel.with_store(store),
+        Err(e) => {
+            warn!("failed to open log store {}: {e}", path.display());
+            channel
+        }
     }
 }
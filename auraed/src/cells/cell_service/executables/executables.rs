@@ -14,9 +14,12 @@
 \* -------------------------------------------------------------------------- */
 
 use super::{
-    Executable, ExecutableName, ExecutableSpec, ExecutablesError, Result,
+    Executable, ExecutableName, ExecutableSpec, ExecutableStatus,
+    ExecutablesError, Result, StopOptions,
 };
-use std::{collections::HashMap, process::ExitStatus};
+use nix::{sys::signal::Signal, unistd::Pid};
+use std::{collections::HashMap, future::Future};
+use tokio::sync::watch;
 
 type Cache = HashMap<ExecutableName, Executable>;
 
@@ -24,6 +27,9 @@ type Cache = HashMap<ExecutableName, Executable>;
 #[derive(Debug, Default)]
 pub struct Executables {
     cache: Cache,
+    /// Executables that have been removed from the cache, but may still be running their grace
+    /// period. Their names stay reserved, as a new executable would clear their logs.
+    stopping: HashMap<ExecutableName, watch::Receiver<ExecutableStatus>>,
 }
 
 impl Executables {
@@ -33,8 +39,27 @@ impl Executables {
         uid: Option<u32>,
         gid: Option<u32>,
     ) -> Result<&Executable> {
-        let executable_spec = executable_spec.into();
+        self.do_start(executable_spec.into(), uid, gid, false)
+    }
+
+    /// Starts an executable that was running before auraed restarted, keeping the output of
+    /// its earlier run (see [Executable::restore]).
+    pub fn restore<T: Into<ExecutableSpec>>(
+        &mut self,
+        executable_spec: T,
+        uid: Option<u32>,
+        gid: Option<u32>,
+    ) -> Result<&Executable> {
+        self.do_start(executable_spec.into(), uid, gid, true)
+    }
 
+    fn do_start(
+        &mut self,
+        executable_spec: ExecutableSpec,
+        uid: Option<u32>,
+        gid: Option<u32>,
+        keep_history: bool,
+    ) -> Result<&Executable> {
         // TODO: replace with try_insert when it becomes stable
         // Check if there was already an executable with the same name.
         if self.cache.contains_key(&executable_spec.name) {
@@ -43,12 +68,26 @@ impl Executables {
             });
         }
 
+        self.stopping.retain(|_, status| {
+            // A closed channel means the executable is no longer supervised
+            !status.borrow().phase.is_finished() && status.has_changed().is_ok()
+        });
+        if self.stopping.contains_key(&executable_spec.name) {
+            return Err(ExecutablesError::ExecutableStopping {
+                executable_name: executable_spec.name,
+            });
+        }
+
         let executable_name = executable_spec.name.clone();
-        let mut executable = Executable::new(executable_spec);
+        let executable = if keep_history {
+            Executable::restore(executable_spec)
+        } else {
+            Executable::new(executable_spec)
+        };
 
         // start the exe before we add it to the cache, as otherwise a failure leads to the
         // executable remaining in the cache and start cannot be called again.
-        executable.start(uid, gid).map_err(|e| {
+        let executable = executable.start(uid, gid).map_err(|e| {
             ExecutablesError::FailedToStartExecutable {
                 executable_name: executable_name.clone(),
                 source: e,
@@ -72,52 +111,76 @@ impl Executables {
         Ok(executable)
     }
 
-    pub async fn stop(
+    /// Stops the executable, if it is still running, and removes it from the cache.
+    /// Its name cannot be used to start another executable until it has stopped.
+    /// Returns a future that resolves to the final status of the executable once it has stopped.
+    /// The future does not borrow [Executables], so it can be awaited after releasing any lock
+    /// guarding it.
+    pub fn stop(
         &mut self,
         executable_name: &ExecutableName,
-    ) -> Result<ExitStatus> {
-        let Some(executable) = self.cache.get_mut(executable_name) else {
+        options: StopOptions,
+    ) -> Result<impl Future<Output = Result<ExecutableStatus>> + Send + 'static>
+    {
+        let Some(mut executable) = self.cache.remove(executable_name) else {
             return Err(ExecutablesError::ExecutableNotFound {
                 executable_name: executable_name.clone(),
             });
         };
 
-        let exit_status = executable.kill().await.map_err(|e| {
-            ExecutablesError::FailedToStopExecutable {
-                executable_name: executable_name.clone(),
-                source: e,
-            }
-        })?;
+        let stopped = executable.stop(options);
+        let executable_name = executable_name.clone();
+        if let Some(status) = executable.subscribe() {
+            let _ = self.stopping.insert(executable_name.clone(), status);
+        }
+
+        Ok(async move {
+            let status = stopped.await.map_err(|e| {
+                ExecutablesError::FailedToStopExecutable {
+                    executable_name: executable_name.clone(),
+                    source: e,
+                }
+            })?;
 
-        let Some(exit_status) = exit_status else {
             // Exes that never started return None
-            let executable =
-                self.cache.remove(executable_name).expect("exe in cache");
-            return Err(ExecutablesError::ExecutableNotFound {
-                executable_name: executable.name,
-            });
-        };
+            status
+                .ok_or(ExecutablesError::ExecutableNotFound { executable_name })
+        })
+    }
+
+    /// Sends `signal` to the process of a running executable, and returns its [Pid].
+    pub fn signal(
+        &self,
+        executable_name: &ExecutableName,
+        signal: Signal,
+    ) -> Result<Pid> {
+        let executable = self.get(executable_name)?;
 
-        let _ = self.cache.remove(executable_name).ok_or_else(|| {
-            // get_mut would have already thrown this error, so we should never reach here
-            ExecutablesError::ExecutableNotFound {
+        executable
+            .signal(signal)
+            .map_err(|e| ExecutablesError::FailedToSignalExecutable {
                 executable_name: executable_name.clone(),
-            }
-        })?;
+                source: e,
+            })?
+            .ok_or_else(|| ExecutablesError::ExecutableNotRunning {
+                executable_name: executable_name.clone(),
+            })
+    }
 
-        Ok(exit_status)
+    /// Returns the status of every executable in the cache, including those that have exited
+    /// but have not been stopped.
+    pub fn list(&self) -> Vec<ExecutableStatus> {
+        self.cache.values().map(Executable::status).collect()
     }
 
     /// Stops all executables concurrently
-    pub async fn broadcast_stop(&mut self) {
-        let mut names = vec![];
-        for exe in self.cache.values_mut() {
-            let _ = exe.kill().await;
-            names.push(exe.name.clone())
-        }
-
-        for name in names {
-            let _ = self.cache.remove(&name);
-        }
+    pub async fn broadcast_stop(&mut self, options: StopOptions) {
+        let stopped = self
+            .cache
+            .drain()
+            .map(|(_, mut exe)| exe.stop(options))
+            .collect::<Vec<_>>();
+
+        let _ = futures::future::join_all(stopped).await;
     }
 }
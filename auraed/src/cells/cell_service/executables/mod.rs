@@ -14,9 +14,15 @@
 \* -------------------------------------------------------------------------- */
 
 pub use error::{ExecutablesError, Result};
-pub use executable::Executable;
+pub use executable::{
+    Executable, ExecutableExit, ExecutablePhase, ExecutableStatus, StopOptions,
+};
 pub use executable_name::ExecutableName;
 pub use executables::Executables;
+pub use restart_policy::{RestartMode, RestartPolicy};
+pub use security_profile::{
+    Capabilities, SeccompProfile, SeccompProfileError, SecurityProfile,
+};
 use tokio::process::Command;
 
 mod error;
@@ -24,9 +30,16 @@ mod executable;
 mod executable_name;
 #[allow(clippy::module_inception)]
 mod executables;
+mod pidfd;
+mod restart_policy;
+mod security_profile;
+mod supervisor;
+mod syscalls;
 
 pub struct ExecutableSpec {
     pub name: ExecutableName,
     pub description: String,
     pub command: Command,
+    pub restart_policy: RestartPolicy,
+    pub security_profile: Option<SecurityProfile>,
 }
@@ -0,0 +1,83 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use nix::{errno::Errno, sys::signal::Signal, unistd::Pid};
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+/// A file descriptor referring to a process.
+/// Unlike its [Pid], the descriptor keeps referring to the process after it exits and is reaped,
+/// so signals sent through it never reach another process that reuses the [Pid].
+#[derive(Debug)]
+pub(super) struct PidFd {
+    pid: Pid,
+    fd: OwnedFd,
+}
+
+impl PidFd {
+    /// Opens a descriptor for the process `pid`.
+    /// The process must be a child that has not been reaped yet, as its [Pid] could otherwise
+    /// already refer to another process.
+    pub fn open(pid: Pid) -> io::Result<Self> {
+        // SAFETY: pidfd_open takes no pointers, and returns a new descriptor on success
+        let res =
+            unsafe { libc::syscall(libc::SYS_pidfd_open, pid.as_raw(), 0) };
+        let fd = Errno::result(res)?;
+        // SAFETY: the descriptor was just opened, and nothing else owns it
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+        Ok(Self { pid, fd })
+    }
+
+    /// The [Pid] the process had when the descriptor was opened.
+    pub fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// Sends `signal` to the process.
+    /// Returns [Errno::ESRCH] if the process has exited and been reaped.
+    pub fn send_signal(&self, signal: Signal) -> io::Result<()> {
+        // SAFETY: a null siginfo makes the kernel fill it in as kill(2) does
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_pidfd_send_signal,
+                self.fd.as_raw_fd(),
+                signal as libc::c_int,
+                std::ptr::null::<libc::siginfo_t>(),
+                0,
+            )
+        };
+        let _ = Errno::result(res)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    #[test]
+    fn test_send_signal_after_exit_fails() {
+        let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+        let pidfd = PidFd::open(Pid::from_raw(child.id() as i32)).unwrap();
+
+        pidfd.send_signal(Signal::SIGKILL).unwrap();
+        let _ = child.wait().unwrap();
+
+        let err = pidfd.send_signal(Signal::SIGTERM).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::ESRCH as i32));
+    }
+}
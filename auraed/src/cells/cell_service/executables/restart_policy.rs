@@ -0,0 +1,131 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
+use std::{process::ExitStatus, time::Duration};
+
+/// When an executable is restarted after its process exits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartMode {
+    /// Never restart the executable.
+    #[default]
+    Never,
+    /// Restart the executable when it exits with a non-zero code or is terminated by a signal.
+    OnFailure,
+    /// Restart the executable whenever it exits, unless it was stopped.
+    Always,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub mode: RestartMode,
+    /// The number of consecutive restarts after which the executable is considered to be crash
+    /// looping and is no longer restarted. [None] allows unlimited restarts.
+    pub max_restarts: Option<u32>,
+    /// The delay before the first restart. The delay doubles with every consecutive restart.
+    pub initial_backoff: Duration,
+    /// The maximum delay between restarts. A process that stays up for longer than this is
+    /// considered to have started successfully, which resets the backoff and restart count.
+    pub max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    pub const DEFAULT_MAX_RESTARTS: u32 = 5;
+    pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+    /// Returns true if a process that exited with `status` should be restarted.
+    pub fn should_restart(&self, status: &ExitStatus) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => !status.success(),
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Returns the backoff strategy used to delay restarts.
+    pub fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.initial_backoff)
+            .with_multiplier(2.0)
+            .with_randomization_factor(0.0)
+            .with_max_interval(self.max_backoff)
+            // Supervision only gives up because of `max_restarts`, never because of time
+            .with_max_elapsed_time(None)
+            .build()
+    }
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::default(),
+            max_restarts: Some(Self::DEFAULT_MAX_RESTARTS),
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backoff::backoff::Backoff;
+    use std::os::unix::process::ExitStatusExt;
+
+    fn policy(mode: RestartMode) -> RestartPolicy {
+        RestartPolicy { mode, ..Default::default() }
+    }
+
+    #[test]
+    fn test_should_restart() {
+        let success = ExitStatus::from_raw(0);
+        let failure = ExitStatus::from_raw(1 << 8);
+        let killed = ExitStatus::from_raw(9);
+
+        let never = policy(RestartMode::Never);
+        assert!(!never.should_restart(&success));
+        assert!(!never.should_restart(&failure));
+        assert!(!never.should_restart(&killed));
+
+        let on_failure = policy(RestartMode::OnFailure);
+        assert!(!on_failure.should_restart(&success));
+        assert!(on_failure.should_restart(&failure));
+        assert!(on_failure.should_restart(&killed));
+
+        let always = policy(RestartMode::Always);
+        assert!(always.should_restart(&success));
+        assert!(always.should_restart(&failure));
+        assert!(always.should_restart(&killed));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        let mut backoff = policy.backoff();
+        let mut next_millis = || backoff.next_backoff().map(|x| x.as_millis());
+        assert_eq!(next_millis(), Some(100));
+        assert_eq!(next_millis(), Some(200));
+        assert_eq!(next_millis(), Some(300));
+        assert_eq!(next_millis(), Some(300));
+
+        backoff.reset();
+        assert_eq!(backoff.next_backoff().map(|x| x.as_millis()), Some(100));
+    }
+}
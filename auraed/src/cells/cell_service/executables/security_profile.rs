@@ -0,0 +1,530 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use super::syscalls::syscall_number;
+use caps::{CapSet, Capability, CapsHashSet};
+use nix::{
+    errno::Errno,
+    unistd::{setgid, setgroups, setuid, Gid, Uid},
+};
+use oci_spec::runtime::{
+    LinuxSeccomp, LinuxSeccompAction, LinuxSeccompArg, LinuxSeccompOperator,
+};
+use seccompiler::{
+    BackendError, BpfProgram, SeccompAction, SeccompCmpArgLen, SeccompCmpOp,
+    SeccompCondition, SeccompFilter, SeccompRule, TargetArch,
+};
+use std::{collections::BTreeMap, fmt, io};
+use thiserror::Error;
+
+/// Restricts the privileges of the process of an executable, and of the processes it starts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityProfile {
+    /// [None] keeps the capabilities of auraed.
+    pub capabilities: Option<Capabilities>,
+    /// Prevents the process from gaining privileges through execve.
+    pub no_new_privs: bool,
+    pub seccomp: Option<SeccompProfile>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The capabilities the process may ever hold. All others are dropped from its bounding set.
+    pub bounding: CapsHashSet,
+    /// The capabilities kept by the process when it does not run as root.
+    pub ambient: CapsHashSet,
+}
+
+impl SecurityProfile {
+    /// Returns a function that applies the profile to the calling process, switching to `uid` and
+    /// `gid` when set, to be run between fork and exec.
+    ///
+    /// The user is switched by the returned function, rather than by the [Command], as dropping
+    /// capabilities and loading a seccomp filter without `no_new_privs` require privileges that
+    /// are lost when switching to a user other than root.
+    ///
+    /// [Command]: tokio::process::Command
+    pub(crate) fn pre_exec(
+        &self,
+        uid: Option<u32>,
+        gid: Option<u32>,
+    ) -> io::Result<impl FnMut() -> io::Result<()> + Send + Sync + 'static>
+    {
+        // Anything that allocates is prepared before forking
+        let (drop_bounding, ambient) = match &self.capabilities {
+            Some(capabilities) => {
+                let bounding = caps::read(None, CapSet::Bounding)
+                    .map_err(io::Error::other)?;
+                let drop_bounding: Vec<Capability> = bounding
+                    .difference(&capabilities.bounding)
+                    .copied()
+                    .collect();
+                let ambient: Vec<Capability> =
+                    capabilities.ambient.iter().copied().collect();
+                (drop_bounding, ambient)
+            }
+            None => (vec![], vec![]),
+        };
+        let ambient_mask = ambient
+            .iter()
+            .fold(0, |mask, capability| mask | capability.bitmask());
+        let no_new_privs = self.no_new_privs;
+        let filters = self
+            .seccomp
+            .as_ref()
+            .map(|seccomp| seccomp.filters.clone())
+            .unwrap_or_default();
+
+        Ok(move || {
+            // Loading a filter without no_new_privs requires CAP_SYS_ADMIN
+            if !no_new_privs {
+                load_seccomp_filters(&filters)?;
+            }
+
+            for capability in &drop_bounding {
+                prctl(libc::PR_CAPBSET_DROP, capability.index().into(), 0)?;
+            }
+
+            if uid.is_some() && !ambient.is_empty() {
+                prctl(libc::PR_SET_KEEPCAPS, 1, 0)?;
+            }
+
+            if uid.is_some() || gid.is_some() {
+                // As with Command::uid, the supplementary groups of auraed are not kept, which
+                // may not be allowed in a user namespace
+                let _ = setgroups(&[]);
+            }
+            if let Some(gid) = gid {
+                setgid(Gid::from_raw(gid))?;
+            }
+            if let Some(uid) = uid {
+                setuid(Uid::from_raw(uid))?;
+            }
+
+            if !ambient.is_empty() {
+                // Ambient capabilities must be both permitted and inheritable
+                capset(ambient_mask)?;
+                prctl(
+                    libc::PR_CAP_AMBIENT,
+                    libc::PR_CAP_AMBIENT_CLEAR_ALL as _,
+                    0,
+                )?;
+                for capability in &ambient {
+                    prctl(
+                        libc::PR_CAP_AMBIENT,
+                        libc::PR_CAP_AMBIENT_RAISE as _,
+                        capability.index().into(),
+                    )?;
+                }
+            }
+
+            if no_new_privs {
+                prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0)?;
+
+                // Loaded last, so that the filter only needs to allow the syscalls of exec
+                load_seccomp_filters(&filters)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn prctl(
+    option: libc::c_int,
+    arg2: libc::c_ulong,
+    arg3: libc::c_ulong,
+) -> io::Result<()> {
+    // SAFETY: the options used by security profiles do not access memory
+    let res = unsafe { libc::prctl(option, arg2, arg3, 0, 0) };
+    let _ = Errno::result(res)?;
+    Ok(())
+}
+
+/// Sets the effective, permitted and inheritable capabilities of the calling thread to `mask`.
+fn capset(mask: u64) -> io::Result<()> {
+    // From linux/capability.h, which the libc crate does not provide
+    const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+    #[repr(C)]
+    struct CapUserHeader {
+        version: u32,
+        pid: libc::c_int,
+    }
+
+    #[repr(C)]
+    struct CapUserData {
+        effective: u32,
+        permitted: u32,
+        inheritable: u32,
+    }
+
+    let mut header =
+        CapUserHeader { version: LINUX_CAPABILITY_VERSION_3, pid: 0 };
+    // Version 3 splits the 64 bit sets in two
+    let data = [mask as u32, (mask >> 32) as u32].map(|x| CapUserData {
+        effective: x,
+        permitted: x,
+        inheritable: x,
+    });
+
+    // SAFETY: the header and data have the layout of version 3, and outlive the call
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_capset,
+            &mut header as *mut CapUserHeader,
+            data.as_ptr(),
+        )
+    };
+    let _ = Errno::result(res)?;
+    Ok(())
+}
+
+/// Installs the filters on the calling thread, which the process it executes inherits.
+fn load_seccomp_filters(filters: &[BpfProgram]) -> io::Result<()> {
+    for filter in filters {
+        let prog = libc::sock_fprog {
+            len: filter.len() as u16,
+            filter: filter.as_ptr().cast_mut().cast(),
+        };
+
+        // SAFETY: the kernel copies the program, which outlives the call
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_seccomp,
+                libc::SECCOMP_SET_MODE_FILTER,
+                0,
+                &prog as *const libc::sock_fprog,
+            )
+        };
+        let _ = Errno::result(res)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum SeccompProfileError {
+    #[error("seccomp is not supported on {0}")]
+    UnsupportedArchitecture(&'static str),
+    #[error("the seccomp action {0:?} is not supported")]
+    UnsupportedAction(LinuxSeccompAction),
+    #[error("syscall argument {0} does not exist")]
+    InvalidArgument(usize),
+    #[error("seccomp profile has too many instructions")]
+    TooLong,
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// A seccomp profile, compiled for the architecture of auraed.
+#[derive(Clone)]
+pub struct SeccompProfile {
+    /// The profile as given, in the JSON format of the OCI runtime spec.
+    json: String,
+    filters: Vec<BpfProgram>,
+}
+
+impl SeccompProfile {
+    /// Compiles the profile, whose JSON is kept as given.
+    ///
+    /// A filter only has one action for the syscalls it matches, so a filter is compiled for
+    /// every action of the profile, which matches its syscalls and allows any other.
+    /// An additional filter applies the default action to the syscalls that are not listed.
+    /// When several filters match a syscall, the kernel applies the most restrictive action.
+    pub fn new(
+        json: String,
+        seccomp: &LinuxSeccomp,
+    ) -> Result<Self, SeccompProfileError> {
+        let arch: TargetArch =
+            std::env::consts::ARCH.try_into().map_err(|_| {
+                SeccompProfileError::UnsupportedArchitecture(
+                    std::env::consts::ARCH,
+                )
+            })?;
+
+        let default_action = seccomp_action(
+            seccomp.default_action(),
+            seccomp.default_errno_ret(),
+        )?;
+
+        let mut listed = Rules::default();
+        let mut by_action: Vec<(SeccompAction, Rules)> = vec![];
+        for syscall in seccomp.syscalls().iter().flatten() {
+            let action = seccomp_action(syscall.action(), syscall.errno_ret())?;
+            let args = syscall.args().as_deref().unwrap_or_default();
+
+            let i = match by_action.iter().position(|(x, _)| *x == action) {
+                Some(i) => i,
+                None => {
+                    by_action.push((action, Rules::default()));
+                    by_action.len() - 1
+                }
+            };
+            let rules = &mut by_action[i].1;
+
+            // Like other runtimes, syscalls unknown on this architecture are ignored
+            for number in
+                syscall.names().iter().filter_map(|x| syscall_number(x))
+            {
+                listed.add(number, args)?;
+                rules.add(number, args)?;
+            }
+        }
+
+        let mut filters = vec![];
+        if default_action != SeccompAction::Allow {
+            filters.push(compile(
+                listed,
+                default_action,
+                SeccompAction::Allow,
+                arch,
+            )?);
+        }
+        for (action, rules) in by_action {
+            if action != SeccompAction::Allow && !rules.0.is_empty() {
+                filters.push(compile(
+                    rules,
+                    SeccompAction::Allow,
+                    action,
+                    arch,
+                )?);
+            }
+        }
+
+        Ok(Self { json, filters })
+    }
+
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+}
+
+// The filters are compiled from the JSON
+impl PartialEq for SeccompProfile {
+    fn eq(&self, other: &Self) -> bool {
+        self.json == other.json
+    }
+}
+
+impl Eq for SeccompProfile {}
+
+impl fmt::Debug for SeccompProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeccompProfile")
+            .field("json", &self.json)
+            .field("filters", &self.filters.len())
+            .finish()
+    }
+}
+
+/// The rules of the syscalls matched by a filter, where [None] matches a syscall regardless of
+/// its arguments.
+#[derive(Default)]
+struct Rules(BTreeMap<i64, Option<Vec<SeccompRule>>>);
+
+impl Rules {
+    fn add(
+        &mut self,
+        number: i64,
+        args: &[LinuxSeccompArg],
+    ) -> Result<(), SeccompProfileError> {
+        let rules = self.0.entry(number).or_insert_with(|| Some(vec![]));
+
+        if args.is_empty() {
+            *rules = None;
+        } else if let Some(rules) = rules {
+            // All the arguments of an entry must match
+            let conditions =
+                args.iter().map(seccomp_condition).collect::<Result<_, _>>()?;
+            rules.push(SeccompRule::new(conditions)?);
+        }
+
+        Ok(())
+    }
+}
+
+fn compile(
+    rules: Rules,
+    mismatch_action: SeccompAction,
+    match_action: SeccompAction,
+    arch: TargetArch,
+) -> Result<BpfProgram, SeccompProfileError> {
+    let rules = rules
+        .0
+        .into_iter()
+        .map(|(number, rules)| (number, rules.unwrap_or_default()))
+        .collect();
+    let filter =
+        SeccompFilter::new(rules, mismatch_action, match_action, arch)?;
+    let filter: BpfProgram = filter.try_into()?;
+
+    // The length of a program is a u16 for the kernel
+    if filter.len() > usize::from(u16::MAX) {
+        return Err(SeccompProfileError::TooLong);
+    }
+
+    Ok(filter)
+}
+
+fn seccomp_action(
+    action: LinuxSeccompAction,
+    errno_ret: Option<u32>,
+) -> Result<SeccompAction, SeccompProfileError> {
+    // The OCI runtime spec defaults errnoRet to EPERM
+    let errno_ret = errno_ret.unwrap_or(libc::EPERM as u32);
+
+    Ok(match action {
+        LinuxSeccompAction::ScmpActKill
+        | LinuxSeccompAction::ScmpActKillThread => SeccompAction::KillThread,
+        LinuxSeccompAction::ScmpActKillProcess => SeccompAction::KillProcess,
+        LinuxSeccompAction::ScmpActTrap => SeccompAction::Trap,
+        LinuxSeccompAction::ScmpActErrno => SeccompAction::Errno(errno_ret),
+        LinuxSeccompAction::ScmpActTrace => SeccompAction::Trace(errno_ret),
+        LinuxSeccompAction::ScmpActAllow => SeccompAction::Allow,
+        LinuxSeccompAction::ScmpActLog => SeccompAction::Log,
+        action => return Err(SeccompProfileError::UnsupportedAction(action)),
+    })
+}
+
+fn seccomp_condition(
+    arg: &LinuxSeccompArg,
+) -> Result<SeccompCondition, SeccompProfileError> {
+    let index = u8::try_from(arg.index())
+        .map_err(|_| SeccompProfileError::InvalidArgument(arg.index()))?;
+
+    let (operator, value) = match arg.op() {
+        LinuxSeccompOperator::ScmpCmpNe => (SeccompCmpOp::Ne, arg.value()),
+        LinuxSeccompOperator::ScmpCmpLt => (SeccompCmpOp::Lt, arg.value()),
+        LinuxSeccompOperator::ScmpCmpLe => (SeccompCmpOp::Le, arg.value()),
+        LinuxSeccompOperator::ScmpCmpEq => (SeccompCmpOp::Eq, arg.value()),
+        LinuxSeccompOperator::ScmpCmpGe => (SeccompCmpOp::Ge, arg.value()),
+        LinuxSeccompOperator::ScmpCmpGt => (SeccompCmpOp::Gt, arg.value()),
+        // As in libseccomp, value is the mask and valueTwo the masked value
+        LinuxSeccompOperator::ScmpCmpMaskedEq => (
+            SeccompCmpOp::MaskedEq(arg.value()),
+            arg.value_two().unwrap_or_default(),
+        ),
+    };
+
+    Ok(SeccompCondition::new(index, SeccompCmpArgLen::Qword, operator, value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seccomp(json: &str) -> Result<SeccompProfile, SeccompProfileError> {
+        let seccomp: LinuxSeccomp =
+            serde_json::from_str(json).expect("valid OCI seccomp profile");
+        SeccompProfile::new(json.to_string(), &seccomp)
+    }
+
+    #[test]
+    fn test_seccomp_allow_list() {
+        let profile = seccomp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ERRNO",
+                "syscalls": [
+                    { "names": ["read", "write", "not_a_syscall"], "action": "SCMP_ACT_ALLOW" }
+                ]
+            }"#,
+        )
+        .expect("compiled");
+
+        // Only the default action needs a filter
+        assert_eq!(profile.filters.len(), 1);
+    }
+
+    #[test]
+    fn test_seccomp_deny_list() {
+        let profile = seccomp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ALLOW",
+                "syscalls": [
+                    { "names": ["mount"], "action": "SCMP_ACT_ERRNO", "errnoRet": 1 },
+                    { "names": ["reboot"], "action": "SCMP_ACT_KILL_PROCESS" }
+                ]
+            }"#,
+        )
+        .expect("compiled");
+
+        assert_eq!(profile.filters.len(), 2);
+    }
+
+    #[test]
+    fn test_seccomp_args() {
+        let profile = seccomp(
+            r#"{
+                "defaultAction": "SCMP_ACT_ERRNO",
+                "syscalls": [
+                    {
+                        "names": ["personality"],
+                        "action": "SCMP_ACT_ALLOW",
+                        "args": [{ "index": 0, "value": 0, "op": "SCMP_CMP_EQ" }]
+                    },
+                    {
+                        "names": ["personality"],
+                        "action": "SCMP_ACT_ALLOW",
+                        "args": [{ "index": 0, "value": 8, "op": "SCMP_CMP_EQ" }]
+                    }
+                ]
+            }"#,
+        )
+        .expect("compiled");
+
+        assert_eq!(profile.filters.len(), 1);
+    }
+
+    #[test]
+    fn test_seccomp_invalid_argument() {
+        assert!(matches!(
+            seccomp(
+                r#"{
+                    "defaultAction": "SCMP_ACT_ERRNO",
+                    "syscalls": [{
+                        "names": ["personality"],
+                        "action": "SCMP_ACT_ALLOW",
+                        "args": [{ "index": 6, "value": 0, "op": "SCMP_CMP_EQ" }]
+                    }]
+                }"#,
+            ),
+            Err(SeccompProfileError::Backend(_))
+        ));
+    }
+
+    #[test]
+    fn test_seccomp_notify_unsupported() {
+        assert!(matches!(
+            seccomp(
+                r#"{
+                    "defaultAction": "SCMP_ACT_ALLOW",
+                    "syscalls": [{ "names": ["mount"], "action": "SCMP_ACT_NOTIFY" }]
+                }"#,
+            ),
+            Err(SeccompProfileError::UnsupportedAction(
+                LinuxSeccompAction::ScmpActNotify
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_seccomp_json_is_kept() {
+        let json = r#"{"defaultAction":"SCMP_ACT_ALLOW"}"#;
+        let profile = seccomp(json).expect("compiled");
+
+        assert_eq!(profile.json(), json);
+        assert!(profile.filters.is_empty());
+    }
+}
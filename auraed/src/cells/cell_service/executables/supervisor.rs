@@ -0,0 +1,277 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use super::{
+    pidfd::PidFd, ExecutableExit, ExecutableName, ExecutablePhase,
+    ExecutableStatus, RestartPolicy, StopOptions,
+};
+use crate::logging::log_channel::LogChannel;
+use backoff::backoff::Backoff;
+use nix::{
+    errno::Errno,
+    sys::signal::{self, Signal},
+    unistd::Pid,
+};
+use std::{io, process::ExitStatus, time::Duration, time::SystemTime};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tracing::{error, info, info_span, warn};
+
+/// How long to wait for the output of an exited process to be forwarded to its log channels.
+/// Processes that leave their output open (e.g., by leaving a background job running) would
+/// otherwise hold up the supervisor.
+const LOG_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A single run of the process of an executable.
+pub(super) struct Run {
+    child: Child,
+    started_at: SystemTime,
+    stdout: JoinHandle<()>,
+    stderr: JoinHandle<()>,
+}
+
+/// Runs the process of an executable, restarting it according to its [RestartPolicy], until it is
+/// stopped or it exits and is not restarted. Changes to the lifecycle are published to `status`.
+pub(super) struct Supervisor {
+    pub name: ExecutableName,
+    pub command: Command,
+    pub restart_policy: RestartPolicy,
+    pub stdout: LogChannel,
+    pub stderr: LogChannel,
+    pub status: watch::Sender<ExecutableStatus>,
+    /// Refers to the process of the most recent run, for it to be signaled.
+    pub pidfd: watch::Sender<Option<PidFd>>,
+}
+
+impl Supervisor {
+    /// Spawns a new run of the process, forwarding its output to the log channels.
+    pub fn spawn(&mut self) -> io::Result<Run> {
+        let mut child = self.command.spawn()?;
+        let started_at = SystemTime::now();
+        let pid =
+            Pid::from_raw(child.id().expect("pid of running child") as i32);
+        // The child has not been waited on, so its pid cannot have been reused yet.
+        // On error, dropping the child kills it.
+        let pidfd = PidFd::open(pid)?;
+
+        let stdout = forward_lines(
+            child.stdout.take().expect("stdout"),
+            self.stdout.clone(),
+            &self.name,
+        );
+        let stderr = forward_lines(
+            child.stderr.take().expect("stderr"),
+            self.stderr.clone(),
+            &self.name,
+        );
+
+        let _ = self.pidfd.send_replace(Some(pidfd));
+        self.status.send_modify(|status| {
+            status.phase = ExecutablePhase::Running;
+            status.pid = Some(pid);
+            status.started_at = Some(started_at);
+        });
+
+        Ok(Run { child, started_at, stdout, stderr })
+    }
+
+    /// Supervises the process until the supervisor is stopped, or the process exits and is not
+    /// restarted. Sending on `stop` stops the process gracefully, while dropping its sender kills
+    /// the process.
+    pub async fn run(
+        mut self,
+        run: Run,
+        mut stop: oneshot::Receiver<StopOptions>,
+    ) {
+        let mut backoff = self.restart_policy.backoff();
+        let mut consecutive_restarts = 0;
+        let mut run = Some(run);
+
+        loop {
+            // There is no run if the previous restart failed to spawn the process
+            if let Some(run) = run.take() {
+                let started_at = run.started_at;
+
+                let Some(exit) = self.wait(run, &mut stop).await else {
+                    self.finish(ExecutablePhase::Exited);
+                    break;
+                };
+
+                if !self.restart_policy.should_restart(&exit.status) {
+                    self.finish(ExecutablePhase::Exited);
+                    break;
+                }
+
+                let ran_for = exit
+                    .exited_at
+                    .duration_since(started_at)
+                    .unwrap_or_default();
+                if ran_for >= self.restart_policy.max_backoff {
+                    backoff.reset();
+                    consecutive_restarts = 0;
+                }
+            }
+
+            if matches!(
+                self.restart_policy.max_restarts,
+                Some(max_restarts) if consecutive_restarts >= max_restarts
+            ) {
+                self.log(format!(
+                    "crash looping after {consecutive_restarts} consecutive restarts, giving up"
+                ));
+                self.finish(ExecutablePhase::CrashLoop);
+                break;
+            }
+
+            let delay = backoff
+                .next_backoff()
+                .unwrap_or(self.restart_policy.max_backoff);
+            self.status.send_modify(|status| {
+                status.phase = ExecutablePhase::Restarting;
+            });
+            self.log(format!("restarting in {delay:?}"));
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = &mut stop => {
+                    self.finish(ExecutablePhase::Exited);
+                    break;
+                }
+            }
+
+            consecutive_restarts += 1;
+            self.status.send_modify(|status| status.restart_count += 1);
+
+            match self.spawn() {
+                Ok(next) => run = Some(next),
+                Err(e) => self.log(format!("failed to restart: {e}")),
+            }
+        }
+    }
+
+    /// Waits for the process of `run` to exit, stopping it if `stop` completes first.
+    /// Returns [None] if the process was stopped, or if waiting on it failed.
+    async fn wait(
+        &mut self,
+        mut run: Run,
+        stop: &mut oneshot::Receiver<StopOptions>,
+    ) -> Option<ExecutableExit> {
+        let exited = tokio::select! {
+            status = run.child.wait() => Ok(status),
+            options = stop => Err(options.ok()),
+        };
+
+        let stopped = exited.is_err();
+        let status = match exited {
+            Ok(status) => status,
+            Err(options) => self.stop(&mut run, options).await,
+        };
+
+        let status = match status {
+            Ok(status) => status,
+            Err(e) => {
+                error!("failed to wait on executable {}: {e}", self.name);
+                return None;
+            }
+        };
+
+        let exit = ExecutableExit { status, exited_at: SystemTime::now() };
+
+        let _ = tokio::time::timeout(LOG_FLUSH_TIMEOUT, async {
+            let _ = tokio::join!(&mut run.stdout, &mut run.stderr);
+        })
+        .await;
+
+        self.status.send_modify(|x| x.exit = Some(exit));
+        self.log(format!("exited: {status}"));
+
+        (!stopped).then_some(exit)
+    }
+
+    /// Stops the process of `run` by sending it the signal of `options`, and killing it if it has
+    /// not exited by the end of the grace period. Without `options`, the process is killed
+    /// immediately.
+    async fn stop(
+        &self,
+        run: &mut Run,
+        options: Option<StopOptions>,
+    ) -> io::Result<ExitStatus> {
+        let pid = run.child.id().map(|pid| Pid::from_raw(pid as i32));
+
+        if let (Some(pid), Some(StopOptions { signal, grace_period })) =
+            (pid, options)
+        {
+            if signal != Signal::SIGKILL {
+                match signal::kill(pid, signal) {
+                    // ESRCH means the process exited since we checked, which `wait` reports
+                    Ok(()) | Err(Errno::ESRCH) => {
+                        if let Ok(status) =
+                            tokio::time::timeout(grace_period, run.child.wait())
+                                .await
+                        {
+                            return status;
+                        }
+
+                        self.log(format!(
+                            "did not exit within {grace_period:?} of {signal}, killing"
+                        ));
+                    }
+                    Err(e) => warn!(
+                        "failed to send {signal} to executable {}: {e}",
+                        self.name
+                    ),
+                }
+            }
+        }
+
+        // Errors mean the process already exited, which `wait` reports either way
+        let _ = run.child.start_kill();
+        run.child.wait().await
+    }
+
+    fn finish(&self, phase: ExecutablePhase) {
+        self.status.send_modify(|status| status.phase = phase);
+    }
+
+    /// Logs a supervision event to auraed's logs, and to the stderr log channel of the
+    /// executable, so that it is seen alongside the output of the process.
+    fn log(&self, message: String) {
+        info!("executable {}: {message}", self.name);
+        self.stderr.send(format!("auraed: {message}"));
+    }
+}
+
+/// Forwards each line read from `output` to `log_channel`, until `output` is closed.
+fn forward_lines<R>(
+    output: R,
+    log_channel: LogChannel,
+    name: &ExecutableName,
+) -> JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let span = info_span!("running process", name = ?name);
+    tokio::spawn(async move {
+        let log_channel = log_channel;
+        let mut span = Some(span);
+        let mut lines = BufReader::new(output).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let entered_span = span.take().expect("span").entered();
+            log_channel.send(line);
+            span = Some(entered_span.exit());
+        }
+    })
+}
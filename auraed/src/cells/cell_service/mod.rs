@@ -19,5 +19,7 @@ use error::Result;
 mod cell_service;
 mod cells;
 mod error;
+mod exec;
 mod executables;
+mod state;
 mod validation;
\ No newline at end of file
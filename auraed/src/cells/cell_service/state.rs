@@ -0,0 +1,292 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use super::cells::CellAllocation;
+use nix::{
+    sys::signal::{self, Signal},
+    unistd::Pid,
+};
+use proto::cells::{Cell, CellServiceStartRequest};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+use tracing::error;
+
+/// An allocated cell, as persisted in the [State].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CellRecord {
+    /// The spec of the cell, named with its full [CellName].
+    ///
+    /// [CellName]: super::cells::CellName
+    pub cell: Cell,
+    pub allocation: CellAllocation,
+}
+
+/// An executable started outside of a cell, as persisted in the [State].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ExecutableRecord {
+    /// The request that started the executable.
+    pub request: CellServiceStartRequest,
+    /// The pid of the most recent run of the executable.
+    pub pid: i32,
+    /// When the most recent run of the executable started after system boot, in clock ticks.
+    pub start_time: Option<u64>,
+}
+
+impl ExecutableRecord {
+    pub fn new(request: CellServiceStartRequest, pid: i32) -> Self {
+        Self { request, pid, start_time: start_time(pid) }
+    }
+
+    pub fn name(&self) -> &str {
+        self.request.executable.as_ref().map(|x| x.name.as_str()).unwrap_or("")
+    }
+
+    /// Sends [SIGKILL] to the process of the executable, if it outlived the auraed that
+    /// started it. The process is only signaled if its pid has not been reused since.
+    ///
+    /// [SIGKILL]: Signal::SIGKILL
+    pub fn kill_orphan(&self) {
+        if self.start_time.is_some() && start_time(self.pid) == self.start_time
+        {
+            let _best_effort =
+                signal::kill(Pid::from_raw(self.pid), Signal::SIGKILL);
+        }
+    }
+}
+
+/// The cells and executables of an auraed, which are adopted or started again after a restart.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct State {
+    /// Parents are always listed before their children.
+    pub cells: Vec<CellRecord>,
+    pub executables: Vec<ExecutableRecord>,
+}
+
+/// Persists the [State] as JSON in a file of `state_dir`. The file is replaced atomically on
+/// every change, so a crash never leaves a partially written state behind.
+///
+/// Without a `state_dir`, the [State] is only kept in memory.
+#[derive(Debug, Default)]
+pub(crate) struct StateStore {
+    path: Option<PathBuf>,
+    state: State,
+}
+
+impl StateStore {
+    const FILE_NAME: &'static str = "cells.json";
+
+    /// Loads the [State] persisted in `state_dir`, which is empty if nothing was persisted yet.
+    pub fn open(state_dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&state_dir)?;
+        let path = state_dir.join(Self::FILE_NAME);
+
+        let state = match fs::read(&path) {
+            Ok(json) => serde_json::from_slice(&json)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self { path: Some(path), state })
+    }
+
+    /// Creates a store that persists to `state_dir`, discarding the [State] already there.
+    pub fn empty(state_dir: PathBuf) -> Self {
+        Self {
+            path: Some(state_dir.join(Self::FILE_NAME)),
+            state: State::default(),
+        }
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Returns false if the [State] is only kept in memory.
+    pub fn persistent(&self) -> bool {
+        self.path.is_some()
+    }
+
+    /// Stops persisting changes, so that the [State] on disk is left as it is for the next
+    /// run of auraed.
+    pub fn close(&mut self) {
+        self.path = None;
+    }
+
+    pub fn set_cells(&mut self, cells: Vec<CellRecord>) {
+        if self.state.cells != cells {
+            self.state.cells = cells;
+            self.save();
+        }
+    }
+
+    /// Adds the record of an executable, replacing any record with the same name.
+    pub fn set_executable(&mut self, record: ExecutableRecord) {
+        self.state.executables.retain(|x| x.name() != record.name());
+        self.state.executables.push(record);
+        self.save();
+    }
+
+    /// Records that the executable restarted from `pid` as `restarted_pid`.
+    pub fn restart_executable(
+        &mut self,
+        name: &str,
+        pid: i32,
+        restarted_pid: i32,
+    ) {
+        let Some(record) = self
+            .state
+            .executables
+            .iter_mut()
+            .find(|x| x.name() == name && x.pid == pid)
+        else {
+            return;
+        };
+
+        record.pid = restarted_pid;
+        record.start_time = start_time(restarted_pid);
+        self.save();
+    }
+
+    /// Removes the record of the executable, if its most recent run is still `pid`.
+    /// Without a `pid`, the record is removed regardless of its run.
+    pub fn remove_executable(&mut self, name: &str, pid: Option<i32>) {
+        let len = self.state.executables.len();
+        self.state.executables.retain(|x| {
+            x.name() != name || pid.is_some_and(|pid| x.pid != pid)
+        });
+
+        if self.state.executables.len() != len {
+            self.save();
+        }
+    }
+
+    pub fn clear_executables(&mut self) {
+        if !self.state.executables.is_empty() {
+            self.state.executables.clear();
+            self.save();
+        }
+    }
+
+    /// Writes the [State] to a temporary file, which then replaces the persisted one.
+    /// Failing to persist does not fail the change, which has already been made, so the
+    /// error is only logged. The [State] is written in full again on the next change.
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let res = serde_json::to_vec_pretty(&self.state)
+            .map_err(io::Error::from)
+            .and_then(|json| {
+                let tmp = path.with_extension("json.tmp");
+                fs::write(&tmp, json)?;
+                fs::rename(&tmp, path)
+            });
+
+        if let Err(e) = res {
+            error!("failed to persist state to {}: {e}", path.display());
+        }
+    }
+}
+
+/// Returns when the process started after system boot, in clock ticks.
+/// Along with its pid, it identifies a process across restarts of auraed.
+fn start_time(pid: i32) -> Option<u64> {
+    procfs::process::Process::new(pid)
+        .and_then(|process| process.stat())
+        .map(|stat| stat.starttime)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::cells::Executable;
+
+    fn state_dir() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("aurae-state-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn executable_record(name: &str, pid: i32) -> ExecutableRecord {
+        ExecutableRecord {
+            request: CellServiceStartRequest {
+                executable: Some(Executable {
+                    name: name.into(),
+                    command: "sleep 42".into(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            pid,
+            start_time: None,
+        }
+    }
+
+    #[test]
+    fn test_state_is_loaded_after_reopen() {
+        let state_dir = state_dir();
+
+        let mut store = StateStore::open(state_dir.clone()).expect("open");
+        assert_eq!(store.state(), &State::default());
+
+        store.set_cells(vec![CellRecord {
+            cell: Cell { name: "ae-test/nested".into(), ..Default::default() },
+            allocation: CellAllocation {
+                nested_auraed_pid: 42,
+                nested_auraed_start_time: 1234,
+                socket: "/var/run/aurae/aurae-test.sock".into(),
+                host_iface: Some("ae-12345678".into()),
+                address: Some("10.88.0.2/16".into()),
+            },
+        }]);
+        store.set_executable(executable_record("ae-test-exe", 42));
+
+        let reopened = StateStore::open(state_dir.clone()).expect("reopen");
+        assert_eq!(reopened.state(), store.state());
+
+        let _ = fs::remove_dir_all(state_dir);
+    }
+
+    #[test]
+    fn test_executable_record_follows_restarts() {
+        let mut store = StateStore::default();
+
+        store.set_executable(executable_record("ae-test-exe", 42));
+        store.restart_executable("ae-test-exe", 42, 43);
+        assert_eq!(store.state().executables[0].pid, 43);
+
+        // A run that is no longer the most recent one can't remove the record
+        store.remove_executable("ae-test-exe", Some(42));
+        assert_eq!(store.state().executables.len(), 1);
+
+        store.remove_executable("ae-test-exe", Some(43));
+        assert!(store.state().executables.is_empty());
+    }
+
+    #[test]
+    fn test_executable_record_is_replaced_by_name() {
+        let mut store = StateStore::default();
+
+        store.set_executable(executable_record("ae-test-exe", 42));
+        store.set_executable(executable_record("ae-test-other-exe", 43));
+        store.set_executable(executable_record("ae-test-exe", 44));
+        assert_eq!(store.state().executables.len(), 2);
+
+        store.remove_executable("ae-test-exe", None);
+        assert_eq!(store.state().executables.len(), 1);
+        assert_eq!(store.state().executables[0].name(), "ae-test-other-exe");
+    }
+}
@@ -16,18 +16,37 @@ use super::cells::{
     cgroups::{
         self,
         cpuset::{Cpus, Mems},
+        hugetlb::{self, HugetlbLimit},
+        io::{IoDevice, IoLatency, IoMax},
+        rdma::{self, RdmaMax},
         CgroupSpec, Limit, Protection, Weight,
     },
-    IsolationControls,
+    IdMap, IsolationControls, Mount, SubordinateIds, SUBGID_PATH, SUBUID_PATH,
 };
-use super::executables::ExecutableName;
+use super::exec;
+use super::executables::{self, ExecutableName};
 use crate::cells::cell_service::cells::CellName;
+use caps::{Capability, CapsHashSet};
+use nix::sys::{
+    signal::Signal,
+    stat::{umask, Mode},
+};
+use oci_spec::runtime::LinuxSeccomp;
 use proto::cells::{
-    Cell, CellServiceAllocateRequest, CellServiceFreeRequest,
-    CellServiceStartRequest, CellServiceStopRequest, CpuController,
-    CpusetController, Executable, MemoryController,
+    Capabilities, Cell, CellServiceAllocateRequest, CellServiceExecStart,
+    CellServiceFreeRequest, CellServiceListExecutablesRequest,
+    CellServiceListRequest, CellServicePauseRequest, CellServiceResumeRequest,
+    CellServiceSignalRequest, CellServiceStartRequest, CellServiceStatsRequest,
+    CellServiceStopRequest, CellServiceUpdateRequest, CellServiceWaitRequest,
+    CellServiceWatchStatsRequest, CpuController, CpusetController, Executable,
+    HugetlbController, IdMapping, IoController, MemoryController, MountType,
+    PidsController, RdmaController, RdmaLimit, RestartMode, RestartPolicy,
+    SecurityProfile, TerminalSize,
+};
+use std::{
+    collections::HashSet, ffi::OsString, ops::Range, path::PathBuf,
+    time::Duration,
 };
-use std::ffi::OsString;
 use tokio::process::Command;
 use validation::{ValidatedType, ValidationError};
 use validation_macros::ValidatedType;
@@ -75,11 +94,41 @@ pub struct ValidatedCell {
     #[field_type(Option<MemoryController>)]
     pub memory: Option<ValidatedMemoryController>,
 
+    #[field_type(Option<IoController>)]
+    pub io: Option<ValidatedIoController>,
+
+    #[field_type(Option<PidsController>)]
+    pub pids: Option<ValidatedPidsController>,
+
+    #[field_type(Option<HugetlbController>)]
+    pub hugetlb: Option<ValidatedHugetlbController>,
+
+    #[field_type(Option<RdmaController>)]
+    pub rdma: Option<ValidatedRdmaController>,
+
     #[validate(none)]
     pub isolate_process: bool,
 
     #[validate(none)]
     pub isolate_network: bool,
+
+    #[validate(none)]
+    pub isolate_user: bool,
+
+    #[field_type(Vec<IdMapping>)]
+    pub uid_map: Vec<IdMap>,
+
+    #[field_type(Vec<IdMapping>)]
+    pub gid_map: Vec<IdMap>,
+
+    #[field_type(Option<String>)]
+    pub rootfs: Option<PathBuf>,
+
+    #[field_type(Vec<proto::cells::Mount>)]
+    pub mounts: Vec<Mount>,
+
+    #[field_type(Option<SecurityProfile>)]
+    pub security_profile: Option<ValidatedSecurityProfile>,
 }
 
 impl CellTypeValidator for CellValidator {
@@ -127,6 +176,318 @@ impl CellTypeValidator for CellValidator {
             Some(&*validation::field_name(field_name, parent_name)),
         )?))
     }
+
+    fn validate_io(
+        io: Option<IoController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedIoController>, ValidationError> {
+        let Some(io) = io else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatedIoController::validate(
+            io,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )?))
+    }
+
+    fn validate_pids(
+        pids: Option<PidsController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedPidsController>, ValidationError> {
+        let Some(pids) = pids else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatedPidsController::validate(
+            pids,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )?))
+    }
+
+    fn validate_hugetlb(
+        hugetlb: Option<HugetlbController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedHugetlbController>, ValidationError> {
+        let Some(hugetlb) = hugetlb else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatedHugetlbController::validate(
+            hugetlb,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )?))
+    }
+
+    fn validate_rdma(
+        rdma: Option<RdmaController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedRdmaController>, ValidationError> {
+        let Some(rdma) = rdma else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatedRdmaController::validate(
+            rdma,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )?))
+    }
+
+    fn validate_security_profile(
+        security_profile: Option<SecurityProfile>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedSecurityProfile>, ValidationError> {
+        validate_security_profile(security_profile, field_name, parent_name)
+    }
+
+    fn validate_uid_map(
+        uid_map: Vec<IdMapping>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<IdMap>, ValidationError> {
+        if uid_map.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let subuids = SubordinateIds::read(SUBUID_PATH).map_err(|_| {
+            ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            }
+        })?;
+
+        validate_id_map(uid_map, &subuids, field_name, parent_name)
+    }
+
+    fn validate_gid_map(
+        gid_map: Vec<IdMapping>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<IdMap>, ValidationError> {
+        if gid_map.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let subgids = SubordinateIds::read(SUBGID_PATH).map_err(|_| {
+            ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            }
+        })?;
+
+        validate_id_map(gid_map, &subgids, field_name, parent_name)
+    }
+
+    fn validate_rootfs(
+        rootfs: Option<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<PathBuf>, ValidationError> {
+        let Some(rootfs) = rootfs else {
+            return Ok(None);
+        };
+
+        let rootfs = PathBuf::from(rootfs);
+        if !rootfs.is_absolute() {
+            return Err(ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            });
+        }
+
+        Ok(Some(rootfs))
+    }
+
+    fn validate_mounts(
+        mounts: Vec<proto::cells::Mount>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<Mount>, ValidationError> {
+        let mut targets = HashSet::new();
+
+        mounts
+            .into_iter()
+            .enumerate()
+            .map(|(i, mount)| {
+                let invalid = || ValidationError::Invalid {
+                    field: validation::field_name(
+                        &format!("{field_name}[{i}]"),
+                        parent_name,
+                    ),
+                };
+
+                let proto::cells::Mount {
+                    r#type,
+                    source,
+                    target,
+                    readonly,
+                    size,
+                } = mount;
+
+                let target = PathBuf::from(target);
+                if !target.is_absolute() || !targets.insert(target.clone()) {
+                    return Err(invalid());
+                }
+
+                let source = PathBuf::from(source);
+                match MountType::try_from(r#type).map_err(|_| invalid())? {
+                    MountType::Bind
+                        if source.is_absolute() && size.is_none() =>
+                    {
+                        Ok(Mount::Bind { source, target, readonly })
+                    }
+                    MountType::Tmpfs
+                        if source.as_os_str().is_empty() && !readonly =>
+                    {
+                        Ok(Mount::Tmpfs { target, size })
+                    }
+                    _ => Err(invalid()),
+                }
+            })
+            .collect()
+    }
+
+    fn post_validate(
+        output: &ValidatedCell,
+        parent_name: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        let invalid = |field_name: &str| ValidationError::Invalid {
+            field: validation::field_name(field_name, parent_name),
+        };
+
+        // Mounts are applied in the mount namespace of the cell,
+        // which only exists when the process is isolated.
+        if !output.isolate_process {
+            if output.rootfs.is_some() {
+                return Err(invalid("rootfs"));
+            }
+            if !output.mounts.is_empty() {
+                return Err(invalid("mounts"));
+            }
+        }
+
+        for (field_name, id_map) in
+            [("uid_map", &output.uid_map), ("gid_map", &output.gid_map)]
+        {
+            let valid = if output.isolate_user {
+                // The nested auraed runs as root of the user namespace
+                IdMap::to_outside(id_map, 0).is_some()
+            } else {
+                id_map.is_empty()
+            };
+
+            if !valid {
+                return Err(invalid(field_name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ValidatedCell {
+    /// Validates a cell persisted by a previous run of auraed, so that it can be adopted.
+    ///
+    /// The checks against the host (the ids delegated to auraed, the hugetlb page sizes, and
+    /// the RDMA devices) passed when the cell was allocated, and are skipped. The cell keeps
+    /// running whether they still hold or not.
+    pub fn validate_persisted(mut cell: Cell) -> Result<Self, ValidationError> {
+        // The fields checked against the host are validated on their own below, along with
+        // `isolate_user`, which requires the id maps
+        let isolate_user = std::mem::take(&mut cell.isolate_user);
+        let uid_map = std::mem::take(&mut cell.uid_map);
+        let gid_map = std::mem::take(&mut cell.gid_map);
+        let hugetlb = cell.hugetlb.take();
+        let rdma = cell.rdma.take();
+
+        let mut output = Self::validate(cell, None)?;
+
+        output.isolate_user = isolate_user;
+        output.uid_map =
+            validate_id_map(uid_map, &SubordinateIds::all(), "uid_map", None)?;
+        output.gid_map =
+            validate_id_map(gid_map, &SubordinateIds::all(), "gid_map", None)?;
+
+        output.hugetlb = hugetlb
+            .map(|HugetlbController { max }| {
+                let page_sizes: Vec<_> = max
+                    .iter()
+                    .filter_map(|x| x.split_once('='))
+                    .map(|(page_size, _)| page_size.to_string())
+                    .collect();
+                validate_hugetlb_max(max, &page_sizes, "max", Some("hugetlb"))
+                    .map(|max| ValidatedHugetlbController { max })
+            })
+            .transpose()?;
+
+        output.rdma = rdma
+            .map(|RdmaController { max }| {
+                let devices: Vec<_> =
+                    max.iter().map(|x| x.device.clone()).collect();
+                validate_rdma_max(max, &devices, "max", Some("rdma"))
+                    .map(|max| ValidatedRdmaController { max })
+            })
+            .transpose()?;
+
+        CellValidator::post_validate(&output, None)?;
+
+        Ok(output)
+    }
+}
+
+/// The kernel accepts at most 340 lines in uid_map and gid_map.
+const MAX_ID_MAP_LINES: u64 = 340;
+
+fn validate_id_map(
+    id_map: Vec<IdMapping>,
+    subordinate_ids: &SubordinateIds,
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<Vec<IdMap>, ValidationError> {
+    validation::maximum_length(
+        &id_map,
+        MAX_ID_MAP_LINES,
+        "mappings",
+        field_name,
+        parent_name,
+    )?;
+
+    let mut validated: Vec<IdMap> = vec![];
+    for (i, IdMapping { inside_id, outside_id, count }) in
+        id_map.into_iter().enumerate()
+    {
+        let invalid = || ValidationError::Invalid {
+            field: validation::field_name(
+                &format!("{field_name}[{i}]"),
+                parent_name,
+            ),
+        };
+
+        let map = IdMap { inside: inside_id, outside: outside_id, count };
+        if count == 0
+            || map.inside_range().end > 1 << 32
+            || map.outside_range().end > 1 << 32
+        {
+            return Err(invalid());
+        }
+
+        // Ranges may not overlap, and the host ids must be delegated to auraed
+        let overlaps =
+            |a: Range<u64>, b: Range<u64>| a.start < b.end && b.start < a.end;
+        if validated.iter().any(|x| {
+            overlaps(x.inside_range(), map.inside_range())
+                || overlaps(x.outside_range(), map.outside_range())
+        }) || !subordinate_ids.contains(&map)
+        {
+            return Err(invalid());
+        }
+
+        validated.push(map);
+    }
+
+    Ok(validated)
 }
 
 impl From<ValidatedCell> for super::cells::CellSpec {
@@ -136,8 +497,18 @@ impl From<ValidatedCell> for super::cells::CellSpec {
             cpu,
             cpuset,
             memory,
+            io,
+            pids,
+            hugetlb,
+            rdma,
             isolate_process,
             isolate_network,
+            isolate_user,
+            uid_map,
+            gid_map,
+            rootfs,
+            mounts,
+            security_profile,
         } = x;
 
         Self {
@@ -145,8 +516,21 @@ impl From<ValidatedCell> for super::cells::CellSpec {
                 cpu: cpu.map(|x| x.into()),
                 cpuset: cpuset.map(|x| x.into()),
                 memory: memory.map(|x| x.into()),
+                io: io.map(|x| x.into()),
+                pids: pids.map(|x| x.into()),
+                hugetlb: hugetlb.map(|x| x.into()),
+                rdma: rdma.map(|x| x.into()),
+            },
+            iso_ctl: IsolationControls {
+                isolate_process,
+                isolate_network,
+                isolate_user,
+                uid_map,
+                gid_map,
+                rootfs,
+                mounts,
             },
-            iso_ctl: IsolationControls { isolate_process, isolate_network },
+            security_profile: security_profile.map(|x| x.into()),
         }
     }
 }
@@ -211,271 +595,2032 @@ pub struct ValidatedMemoryController {
     #[field_type(Option<i64>)]
     #[validate(opt)]
     pub max: Option<Limit>,
+
+    #[field_type(Option<i64>)]
+    #[validate(opt)]
+    pub swap_max: Option<Limit>,
+
+    #[field_type(Option<i64>)]
+    #[validate(opt)]
+    pub swap_high: Option<Limit>,
+
+    #[validate(none)]
+    pub oom_group: Option<bool>,
 }
 
 impl MemoryControllerTypeValidator for MemoryControllerValidator {}
 
 impl From<ValidatedMemoryController> for cgroups::memory::MemoryController {
     fn from(value: ValidatedMemoryController) -> Self {
-        let ValidatedMemoryController { min, low, high, max } = value;
-        Self { min, low, high, max }
+        let ValidatedMemoryController {
+            min,
+            low,
+            high,
+            max,
+            swap_max,
+            swap_high,
+            oom_group,
+        } = value;
+
+        Self { min, low, high, max, swap_max, swap_high, oom_group }
     }
 }
 
-#[derive(Debug, ValidatedType)]
-pub struct ValidatedCellServiceFreeRequest {
-    #[field_type(String)]
-    #[validate]
-    pub cell_name: CellName,
-}
+#[derive(ValidatedType, Debug, Clone)]
+pub struct ValidatedIoController {
+    #[field_type(Option<u64>)]
+    #[validate(opt)]
+    pub weight: Option<Weight>,
 
-impl CellServiceFreeRequestTypeValidator for CellServiceFreeRequestValidator {}
+    #[field_type(Vec<String>)]
+    pub max: Vec<IoMax>,
 
-#[derive(Debug, ValidatedType)]
-pub struct ValidatedCellServiceStartRequest {
-    #[field_type(Option<String>)]
-    #[validate(opt)]
-    pub cell_name: Option<CellName>,
-    #[field_type(Option<Executable>)]
-    pub executable: ValidatedExecutable,
-    #[validate(none)]
-    pub uid: Option<u32>,
-    #[validate(none)]
-    pub gid: Option<u32>,
+    #[field_type(Vec<String>)]
+    pub latency: Vec<IoLatency>,
 }
 
-impl CellServiceStartRequestTypeValidator for CellServiceStartRequestValidator {
-    fn validate_executable(
-        executable: Option<Executable>,
+impl IoControllerTypeValidator for IoControllerValidator {
+    fn validate_max(
+        max: Vec<String>,
         field_name: &str,
         parent_name: Option<&str>,
-    ) -> Result<ValidatedExecutable, ValidationError> {
-        let executable =
-            validation::required(executable, field_name, parent_name)?;
-        ValidatedExecutable::validate(
-            executable,
-            Some(&*validation::field_name(field_name, parent_name)),
-        )
+    ) -> Result<Vec<IoMax>, ValidationError> {
+        let mut devices = HashSet::new();
+
+        max.into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let field_name = format!("{field_name}[{i}]");
+                let invalid = || ValidationError::Invalid {
+                    field: validation::field_name(&field_name, parent_name),
+                };
+
+                let mut fields = line.split_whitespace();
+                let device = validate_io_device(
+                    fields.next(),
+                    &field_name,
+                    parent_name,
+                )?;
+                if !devices.insert(device) {
+                    return Err(invalid());
+                }
+
+                let mut limits = IoMax {
+                    device,
+                    rbps: None,
+                    wbps: None,
+                    riops: None,
+                    wiops: None,
+                };
+
+                let mut has_limit = false;
+                for field in fields {
+                    let (key, value) =
+                        field.split_once('=').ok_or_else(invalid)?;
+                    let value = match value {
+                        "max" => None,
+                        value => Some(value.parse().map_err(|_| invalid())?),
+                    };
+
+                    match key {
+                        "rbps" => limits.rbps = value,
+                        "wbps" => limits.wbps = value,
+                        "riops" => limits.riops = value,
+                        "wiops" => limits.wiops = value,
+                        _ => return Err(invalid()),
+                    }
+
+                    has_limit = true;
+                }
+
+                if !has_limit {
+                    return Err(invalid());
+                }
+
+                Ok(limits)
+            })
+            .collect()
     }
-}
 
-#[derive(Debug, ValidatedType)]
-pub struct ValidatedCellServiceStopRequest {
-    #[field_type(Option<String>)]
-    #[validate(opt)]
-    pub cell_name: Option<CellName>,
-    #[field_type(String)]
-    #[validate]
-    pub executable_name: ExecutableName,
+    fn validate_latency(
+        latency: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<IoLatency>, ValidationError> {
+        let mut devices = HashSet::new();
+
+        latency
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let field_name = format!("{field_name}[{i}]");
+                let invalid = || ValidationError::Invalid {
+                    field: validation::field_name(&field_name, parent_name),
+                };
+
+                let mut fields = line.split_whitespace();
+                let device = validate_io_device(
+                    fields.next(),
+                    &field_name,
+                    parent_name,
+                )?;
+                if !devices.insert(device) {
+                    return Err(invalid());
+                }
+
+                let target = match (fields.next(), fields.next()) {
+                    (Some(target), None) => target
+                        .strip_prefix("target=")
+                        .and_then(|x| x.parse().ok())
+                        .ok_or_else(invalid)?,
+                    _ => return Err(invalid()),
+                };
+
+                Ok(IoLatency { device, target })
+            })
+            .collect()
+    }
 }
 
-impl CellServiceStopRequestTypeValidator for CellServiceStopRequestValidator {}
-
-#[derive(ValidatedType, Debug, PartialEq, Eq)]
-pub struct ValidatedExecutable {
-    #[field_type(String)]
-    #[validate(create)]
-    pub name: ExecutableName,
+/// Validates a device in the "MAJ:MIN" format of the io controller files.
+fn validate_io_device(
+    device: Option<&str>,
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<IoDevice, ValidationError> {
+    let device = validation::required(device, field_name, parent_name)?;
+
+    device
+        .split_once(':')
+        .and_then(|(major, minor)| {
+            Some(IoDevice {
+                major: major.parse().ok()?,
+                minor: minor.parse().ok()?,
+            })
+        })
+        .ok_or_else(|| ValidationError::Invalid {
+            field: validation::field_name(field_name, parent_name),
+        })
+}
 
-    #[field_type(String)]
-    pub command: OsString,
+impl From<ValidatedIoController> for cgroups::io::IoController {
+    fn from(value: ValidatedIoController) -> Self {
+        let ValidatedIoController { weight, max, latency } = value;
+        Self { weight, max, latency }
+    }
+}
 
-    // TODO: `#[validate(none)] is used to skip validation. Actually validate when restrictions are known.
-    #[validate(none)]
-    pub description: String,
+#[derive(ValidatedType, Debug, Clone)]
+pub struct ValidatedPidsController {
+    #[field_type(Option<i64>)]
+    pub max: Option<Limit>,
 }
 
-impl ExecutableTypeValidator for ExecutableValidator {
-    fn validate_command(
-        command: String,
+impl PidsControllerTypeValidator for PidsControllerValidator {
+    fn validate_max(
+        max: Option<i64>,
         field_name: &str,
         parent_name: Option<&str>,
-    ) -> Result<OsString, ValidationError> {
-        let command = validation::required_not_empty(
-            Some(command),
+    ) -> Result<Option<Limit>, ValidationError> {
+        let Some(max) = max else {
+            return Ok(None);
+        };
+
+        // A limit of 0 would be written as "max", leaving the cell unlimited
+        validation::minimum_value(
+            max,
+            1,
+            "processes",
             field_name,
             parent_name,
         )?;
 
-        Ok(OsString::from(command))
+        Ok(Some(Limit::validate(Some(max), field_name, parent_name)?))
     }
 }
 
-impl From<ValidatedExecutable> for super::executables::ExecutableSpec {
-    fn from(x: ValidatedExecutable) -> Self {
-        let ValidatedExecutable { name, command, description } = x;
+impl From<ValidatedPidsController> for cgroups::pids::PidsController {
+    fn from(value: ValidatedPidsController) -> Self {
+        let ValidatedPidsController { max } = value;
+        Self { max }
+    }
+}
+
+#[derive(ValidatedType, Debug, Clone)]
+pub struct ValidatedHugetlbController {
+    #[field_type(Vec<String>)]
+    pub max: Vec<HugetlbLimit>,
+}
 
-        let mut c = Command::new("sh");
-        let _ = c.args([OsString::from("-c"), command]);
+impl HugetlbControllerTypeValidator for HugetlbControllerValidator {
+    fn validate_max(
+        max: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<HugetlbLimit>, ValidationError> {
+        if max.is_empty() {
+            return Ok(vec![]);
+        }
 
-        // We are checking that command has an arg to assure ourselves that `command.arg`
-        // mutates command, and is not making a clone to return
-        assert_eq!(c.as_std().get_args().len(), 2);
+        // Limits of page sizes the host doesn't support would fail to be written to the cgroup
+        let page_sizes =
+            hugetlb::page_sizes().map_err(|_| ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            })?;
 
-        Self { name, command: c, description }
+        validate_hugetlb_max(max, &page_sizes, field_name, parent_name)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn validate_hugetlb_max(
+    max: Vec<String>,
+    page_sizes: &[String],
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<Vec<HugetlbLimit>, ValidationError> {
+    let mut seen = HashSet::new();
+
+    max.into_iter()
+        .enumerate()
+        .map(|(i, limit)| {
+            let field_name = format!("{field_name}[{i}]");
+            let invalid = || ValidationError::Invalid {
+                field: validation::field_name(&field_name, parent_name),
+            };
+
+            let (page_size, bytes) =
+                limit.split_once('=').ok_or_else(invalid)?;
+
+            if !page_sizes.iter().any(|x| x == page_size)
+                || !seen.insert(page_size.to_string())
+            {
+                return Err(invalid());
+            }
+
+            let bytes = bytes.parse().map_err(|_| invalid())?;
+            let max = Limit::validate(Some(bytes), &field_name, parent_name)?;
+
+            Ok(HugetlbLimit { page_size: page_size.to_string(), max })
+        })
+        .collect()
+}
 
-    #[test]
-    fn test_cell_type_empty_cpu_valid() {
-        let validated =
-            CellValidator::validate_cpu(None, "field", Some("parent"));
-        assert!(validated.is_ok());
-        assert!(validated.unwrap().is_none());
+impl From<ValidatedHugetlbController> for cgroups::hugetlb::HugetlbController {
+    fn from(value: ValidatedHugetlbController) -> Self {
+        let ValidatedHugetlbController { max } = value;
+        Self { max }
     }
+}
 
-    #[test]
-    fn test_cell_type_cpu_valid() {
-        let validated = CellValidator::validate_cpu(
-            Some(CpuController { weight: Some(1000), max: None, period: None }),
-            "field",
-            Some("parent"),
-        );
-        assert!(validated.is_ok());
-        let inner = validated.unwrap();
-        assert!(inner.is_some());
-        let controller = inner.unwrap();
-        assert_eq!(controller.weight, Some(Weight::new(1000)));
-        assert_eq!(controller.max, None);
-        assert_eq!(controller.period, None);
-    }
+#[derive(ValidatedType, Debug, Clone)]
+pub struct ValidatedRdmaController {
+    #[field_type(Vec<RdmaLimit>)]
+    pub max: Vec<RdmaMax>,
+}
 
-    #[test]
-    fn test_cell_type_cpu_weight_too_small() {
-        let validated = CellValidator::validate_cpu(
-            Some(CpuController { weight: Some(0), max: None, period: None }),
-            "field",
-            Some("parent"),
-        );
-        assert!(validated.is_err());
-    }
+impl RdmaControllerTypeValidator for RdmaControllerValidator {
+    fn validate_max(
+        max: Vec<RdmaLimit>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<RdmaMax>, ValidationError> {
+        if max.is_empty() {
+            return Ok(vec![]);
+        }
 
-    #[test]
-    fn test_cell_type_cpu_weight_too_large() {
-        let validated = CellValidator::validate_cpu(
-            Some(CpuController {
-                weight: Some(10001),
-                max: None,
-                period: None,
-            }),
-            "field",
-            Some("parent"),
-        );
-        assert!(validated.is_err());
+        // Limits of devices the host doesn't have would fail to be written to the cgroup
+        let devices =
+            rdma::devices().map_err(|_| ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            })?;
+
+        validate_rdma_max(max, &devices, field_name, parent_name)
     }
+}
 
-    #[test]
-    fn test_cell_type_cpu_max_too_small() {
-        let validated = CellValidator::validate_cpu(
-            Some(CpuController {
-                weight: Some(1000),
-                max: Some(-1),
-                period: None,
-            }),
-            "field",
-            Some("parent"),
-        );
-        assert!(validated.is_err());
+fn validate_rdma_max(
+    max: Vec<RdmaLimit>,
+    devices: &[String],
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<Vec<RdmaMax>, ValidationError> {
+    let mut seen = HashSet::new();
+
+    max.into_iter()
+        .enumerate()
+        .map(|(i, RdmaLimit { device, hca_handle, hca_object })| {
+            let invalid = || ValidationError::Invalid {
+                field: validation::field_name(
+                    &format!("{field_name}[{i}]"),
+                    parent_name,
+                ),
+            };
+
+            if !devices.contains(&device)
+                || !seen.insert(device.clone())
+                || (hca_handle.is_none() && hca_object.is_none())
+            {
+                return Err(invalid());
+            }
+
+            Ok(RdmaMax { device, hca_handle, hca_object })
+        })
+        .collect()
+}
+
+impl From<ValidatedRdmaController> for cgroups::rdma::RdmaController {
+    fn from(value: ValidatedRdmaController) -> Self {
+        let ValidatedRdmaController { max } = value;
+        Self { max }
     }
+}
 
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceFreeRequest {
+    #[field_type(String)]
+    #[validate]
+    pub cell_name: CellName,
+}
+
+impl CellServiceFreeRequestTypeValidator for CellServiceFreeRequestValidator {}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServicePauseRequest {
+    #[field_type(String)]
+    #[validate]
+    pub cell_name: CellName,
+}
+
+impl CellServicePauseRequestTypeValidator for CellServicePauseRequestValidator {}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceResumeRequest {
+    #[field_type(String)]
+    #[validate]
+    pub cell_name: CellName,
+}
+
+impl CellServiceResumeRequestTypeValidator
+    for CellServiceResumeRequestValidator
+{
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceUpdateRequest {
+    #[field_type(String)]
+    #[validate]
+    pub cell_name: CellName,
+
+    #[field_type(Option<CpuController>)]
+    pub cpu: Option<ValidatedCpuController>,
+
+    #[field_type(Option<CpusetController>)]
+    pub cpuset: Option<ValidatedCpusetController>,
+
+    #[field_type(Option<MemoryController>)]
+    pub memory: Option<ValidatedMemoryController>,
+
+    #[field_type(Option<IoController>)]
+    pub io: Option<ValidatedIoController>,
+
+    #[field_type(Option<PidsController>)]
+    pub pids: Option<ValidatedPidsController>,
+
+    #[field_type(Option<HugetlbController>)]
+    pub hugetlb: Option<ValidatedHugetlbController>,
+
+    #[field_type(Option<RdmaController>)]
+    pub rdma: Option<ValidatedRdmaController>,
+}
+
+// The controllers are validated the same way as those of a new cell
+impl CellServiceUpdateRequestTypeValidator
+    for CellServiceUpdateRequestValidator
+{
+    fn validate_cpu(
+        cpu: Option<CpuController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedCpuController>, ValidationError> {
+        CellValidator::validate_cpu(cpu, field_name, parent_name)
+    }
+
+    fn validate_cpuset(
+        cpuset: Option<CpusetController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedCpusetController>, ValidationError> {
+        CellValidator::validate_cpuset(cpuset, field_name, parent_name)
+    }
+
+    fn validate_memory(
+        memory: Option<MemoryController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedMemoryController>, ValidationError> {
+        CellValidator::validate_memory(memory, field_name, parent_name)
+    }
+
+    fn validate_io(
+        io: Option<IoController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedIoController>, ValidationError> {
+        CellValidator::validate_io(io, field_name, parent_name)
+    }
+
+    fn validate_pids(
+        pids: Option<PidsController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedPidsController>, ValidationError> {
+        CellValidator::validate_pids(pids, field_name, parent_name)
+    }
+
+    fn validate_hugetlb(
+        hugetlb: Option<HugetlbController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedHugetlbController>, ValidationError> {
+        CellValidator::validate_hugetlb(hugetlb, field_name, parent_name)
+    }
+
+    fn validate_rdma(
+        rdma: Option<RdmaController>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedRdmaController>, ValidationError> {
+        CellValidator::validate_rdma(rdma, field_name, parent_name)
+    }
+}
+
+impl From<ValidatedCellServiceUpdateRequest> for CgroupSpec {
+    fn from(x: ValidatedCellServiceUpdateRequest) -> Self {
+        let ValidatedCellServiceUpdateRequest {
+            cell_name: _,
+            cpu,
+            cpuset,
+            memory,
+            io,
+            pids,
+            hugetlb,
+            rdma,
+        } = x;
+
+        Self {
+            cpu: cpu.map(|x| x.into()),
+            cpuset: cpuset.map(|x| x.into()),
+            memory: memory.map(|x| x.into()),
+            io: io.map(|x| x.into()),
+            pids: pids.map(|x| x.into()),
+            hugetlb: hugetlb.map(|x| x.into()),
+            rdma: rdma.map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceStartRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[field_type(Option<Executable>)]
+    pub executable: ValidatedExecutable,
+    #[validate(none)]
+    pub uid: Option<u32>,
+    #[validate(none)]
+    pub gid: Option<u32>,
+}
+
+impl CellServiceStartRequestTypeValidator for CellServiceStartRequestValidator {
+    fn validate_executable(
+        executable: Option<Executable>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<ValidatedExecutable, ValidationError> {
+        let executable =
+            validation::required(executable, field_name, parent_name)?;
+        ValidatedExecutable::validate(
+            executable,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceStopRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[field_type(String)]
+    #[validate]
+    pub executable_name: ExecutableName,
+    #[field_type(Option<i32>)]
+    pub signal: Signal,
+    #[field_type(Option<u64>)]
+    pub grace_period_ms: Duration,
+}
+
+impl CellServiceStopRequestTypeValidator for CellServiceStopRequestValidator {
+    fn validate_signal(
+        signal: Option<i32>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Signal, ValidationError> {
+        let Some(signal) = signal else {
+            return Ok(executables::StopOptions::DEFAULT_SIGNAL);
+        };
+
+        Signal::try_from(signal).map_err(|_| ValidationError::Invalid {
+            field: validation::field_name(field_name, parent_name),
+        })
+    }
+
+    fn validate_grace_period_ms(
+        grace_period_ms: Option<u64>,
+        _field_name: &str,
+        _parent_name: Option<&str>,
+    ) -> Result<Duration, ValidationError> {
+        Ok(grace_period_ms
+            .map(Duration::from_millis)
+            .unwrap_or(executables::StopOptions::DEFAULT_GRACE_PERIOD))
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceWaitRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[field_type(String)]
+    #[validate]
+    pub executable_name: ExecutableName,
+}
+
+impl CellServiceWaitRequestTypeValidator for CellServiceWaitRequestValidator {}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceSignalRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub executable_name: Option<ExecutableName>,
+    #[field_type(i32)]
+    pub signal: Signal,
+}
+
+impl CellServiceSignalRequestTypeValidator
+    for CellServiceSignalRequestValidator
+{
+    fn validate_signal(
+        signal: i32,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Signal, ValidationError> {
+        Signal::try_from(signal).map_err(|_| ValidationError::Invalid {
+            field: validation::field_name(field_name, parent_name),
+        })
+    }
+
+    fn pre_validate(
+        input: &CellServiceSignalRequest,
+        parent_name: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        // Without a cell, there is nothing but an executable to signal
+        if input.cell_name.is_none() && input.executable_name.is_none() {
+            return Err(ValidationError::Required {
+                field: validation::field_name("executable_name", parent_name),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceListRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[validate(none)]
+    pub depth: Option<u32>,
+    pub name_pattern: Option<String>,
+    #[validate(none)]
+    pub include_executables: bool,
+}
+
+impl CellServiceListRequestTypeValidator for CellServiceListRequestValidator {
+    fn validate_name_pattern(
+        name_pattern: Option<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<String>, ValidationError> {
+        if name_pattern.as_ref().is_some_and(|x| x.is_empty()) {
+            return Err(ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            });
+        }
+
+        Ok(name_pattern)
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceListExecutablesRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+}
+
+impl CellServiceListExecutablesRequestTypeValidator
+    for CellServiceListExecutablesRequestValidator
+{
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceStatsRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+}
+
+impl CellServiceStatsRequestTypeValidator for CellServiceStatsRequestValidator {}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceWatchStatsRequest {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+    #[field_type(Option<u64>)]
+    pub interval_ms: Duration,
+}
+
+impl ValidatedCellServiceWatchStatsRequest {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+    pub const MINIMUM_INTERVAL_MS: u64 = 100;
+}
+
+impl CellServiceWatchStatsRequestTypeValidator
+    for CellServiceWatchStatsRequestValidator
+{
+    fn validate_interval_ms(
+        interval_ms: Option<u64>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Duration, ValidationError> {
+        let Some(interval_ms) = interval_ms else {
+            return Ok(ValidatedCellServiceWatchStatsRequest::DEFAULT_INTERVAL);
+        };
+
+        validation::minimum_value(
+            interval_ms,
+            ValidatedCellServiceWatchStatsRequest::MINIMUM_INTERVAL_MS,
+            "ms",
+            field_name,
+            parent_name,
+        )?;
+
+        Ok(Duration::from_millis(interval_ms))
+    }
+}
+
+#[derive(Debug, ValidatedType)]
+pub struct ValidatedCellServiceExecStart {
+    #[field_type(Option<String>)]
+    #[validate(opt)]
+    pub cell_name: Option<CellName>,
+
+    #[field_type(Vec<String>)]
+    pub args: Vec<OsString>,
+
+    #[field_type(Vec<String>)]
+    pub env: Vec<(OsString, OsString)>,
+
+    #[field_type(Option<String>)]
+    pub cwd: Option<PathBuf>,
+
+    #[validate(none)]
+    pub tty: bool,
+
+    #[validate(none)]
+    pub terminal_size: Option<TerminalSize>,
+
+    #[validate(none)]
+    pub uid: Option<u32>,
+
+    #[validate(none)]
+    pub gid: Option<u32>,
+
+    #[field_type(Option<SecurityProfile>)]
+    pub security_profile: Option<ValidatedSecurityProfile>,
+}
+
+// The command is validated the same way as the args, env and cwd of an executable
+impl CellServiceExecStartTypeValidator for CellServiceExecStartValidator {
+    fn validate_args(
+        args: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<OsString>, ValidationError> {
+        if args.is_empty() {
+            return Err(ValidationError::Required {
+                field: validation::field_name(field_name, parent_name),
+            });
+        }
+
+        ExecutableValidator::validate_args(args, field_name, parent_name)
+    }
+
+    fn validate_env(
+        env: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<(OsString, OsString)>, ValidationError> {
+        ExecutableValidator::validate_env(env, field_name, parent_name)
+    }
+
+    fn validate_cwd(
+        cwd: Option<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<PathBuf>, ValidationError> {
+        ExecutableValidator::validate_cwd(cwd, field_name, parent_name)
+    }
+
+    fn validate_security_profile(
+        security_profile: Option<SecurityProfile>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedSecurityProfile>, ValidationError> {
+        validate_security_profile(security_profile, field_name, parent_name)
+    }
+}
+
+impl From<ValidatedCellServiceExecStart> for exec::ExecSpec {
+    fn from(x: ValidatedCellServiceExecStart) -> Self {
+        let ValidatedCellServiceExecStart {
+            cell_name: _,
+            args,
+            env,
+            cwd,
+            tty,
+            terminal_size,
+            uid,
+            gid,
+            security_profile,
+        } = x;
+
+        let mut args = args.into_iter();
+        let mut command =
+            Command::new(args.next().expect("validated args not empty"));
+        let _ = command
+            .args(args)
+            .envs(env)
+            .current_dir(cwd.unwrap_or_else(|| PathBuf::from("/")));
+
+        Self {
+            command,
+            tty: tty.then(|| terminal_size.unwrap_or_default()),
+            uid,
+            gid,
+            security_profile: security_profile.map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(ValidatedType, Debug, PartialEq, Eq)]
+pub struct ValidatedExecutable {
+    #[field_type(String)]
+    #[validate(create)]
+    pub name: ExecutableName,
+
+    #[field_type(String)]
+    pub command: Option<OsString>,
+
+    // TODO: `#[validate(none)] is used to skip validation. Actually validate when restrictions are known.
+    #[validate(none)]
+    pub description: String,
+
+    #[field_type(Vec<String>)]
+    pub args: Vec<OsString>,
+
+    #[field_type(Vec<String>)]
+    pub env: Vec<(OsString, OsString)>,
+
+    #[validate(none)]
+    pub clear_env: bool,
+
+    #[field_type(Option<String>)]
+    pub cwd: Option<PathBuf>,
+
+    pub umask: Option<u32>,
+
+    #[field_type(Option<RestartPolicy>)]
+    pub restart_policy: ValidatedRestartPolicy,
+
+    #[field_type(Option<SecurityProfile>)]
+    pub security_profile: Option<ValidatedSecurityProfile>,
+}
+
+impl ExecutableTypeValidator for ExecutableValidator {
+    fn validate_command(
+        command: String,
+        _field_name: &str,
+        _parent_name: Option<&str>,
+    ) -> Result<Option<OsString>, ValidationError> {
+        // Whether the command or args are required is checked in `pre_validate`
+        if command.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(OsString::from(command)))
+    }
+
+    fn validate_args(
+        args: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<OsString>, ValidationError> {
+        // Arguments may be empty strings, but the program may not
+        if matches!(args.first(), Some(program) if program.is_empty()) {
+            return Err(ValidationError::Required {
+                field: validation::field_name(
+                    &format!("{field_name}[0]"),
+                    parent_name,
+                ),
+            });
+        }
+
+        Ok(args.into_iter().map(OsString::from).collect())
+    }
+
+    fn validate_env(
+        env: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Vec<(OsString, OsString)>, ValidationError> {
+        env.into_iter()
+            .enumerate()
+            .map(|(i, var)| match var.split_once('=') {
+                Some((key, value)) if !key.is_empty() => {
+                    Ok((OsString::from(key), OsString::from(value)))
+                }
+                _ => Err(ValidationError::Invalid {
+                    field: validation::field_name(
+                        &format!("{field_name}[{i}]"),
+                        parent_name,
+                    ),
+                }),
+            })
+            .collect()
+    }
+
+    fn validate_cwd(
+        cwd: Option<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<PathBuf>, ValidationError> {
+        let Some(cwd) = cwd else {
+            return Ok(None);
+        };
+
+        let cwd = PathBuf::from(cwd);
+        if !cwd.is_absolute() {
+            return Err(ValidationError::Invalid {
+                field: validation::field_name(field_name, parent_name),
+            });
+        }
+
+        Ok(Some(cwd))
+    }
+
+    fn validate_umask(
+        umask: Option<u32>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<u32>, ValidationError> {
+        if let Some(umask) = umask {
+            validation::maximum_value(
+                umask,
+                0o777,
+                "",
+                field_name,
+                parent_name,
+            )?;
+        }
+
+        Ok(umask)
+    }
+
+    fn validate_restart_policy(
+        restart_policy: Option<RestartPolicy>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<ValidatedRestartPolicy, ValidationError> {
+        // A missing policy gets the defaults of all its fields
+        ValidatedRestartPolicy::validate(
+            restart_policy.unwrap_or_default(),
+            Some(&validation::field_name(field_name, parent_name)),
+        )
+    }
+
+    fn validate_security_profile(
+        security_profile: Option<SecurityProfile>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedSecurityProfile>, ValidationError> {
+        validate_security_profile(security_profile, field_name, parent_name)
+    }
+
+    fn pre_validate(
+        input: &Executable,
+        parent_name: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        // Exactly one of command or args is required
+        match (input.command.is_empty(), input.args.is_empty()) {
+            (true, true) => Err(ValidationError::Required {
+                field: validation::field_name("command", parent_name),
+            }),
+            (false, false) => Err(ValidationError::Invalid {
+                field: validation::field_name("args", parent_name),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<ValidatedExecutable> for executables::ExecutableSpec {
+    fn from(x: ValidatedExecutable) -> Self {
+        let ValidatedExecutable {
+            name,
+            command,
+            description,
+            args,
+            env,
+            clear_env,
+            cwd,
+            umask: mask,
+            restart_policy,
+            security_profile,
+        } = x;
+
+        let mut c = match command {
+            Some(command) => {
+                let mut c = Command::new("sh");
+                let _ = c.args([OsString::from("-c"), command]);
+
+                // We are checking that command has an arg to assure ourselves that `command.arg`
+                // mutates command, and is not making a clone to return
+                assert_eq!(c.as_std().get_args().len(), 2);
+
+                c
+            }
+            None => {
+                let mut args = args.into_iter();
+                let mut c = Command::new(
+                    args.next().expect("validated args not empty"),
+                );
+                let _ = c.args(args);
+                c
+            }
+        };
+
+        if clear_env {
+            let _ = c.env_clear();
+        }
+
+        let _ =
+            c.envs(env).current_dir(cwd.unwrap_or_else(|| PathBuf::from("/")));
+
+        if let Some(mask) = mask {
+            let mask = Mode::from_bits_truncate(mask);
+            // SAFETY: umask is async-signal-safe and does not allocate
+            unsafe {
+                let _ = c.pre_exec(move || {
+                    let _ = umask(mask);
+                    Ok(())
+                });
+            }
+        }
+
+        Self {
+            name,
+            command: c,
+            description,
+            restart_policy: restart_policy.into(),
+            security_profile: security_profile.map(|x| x.into()),
+        }
+    }
+}
+
+#[derive(ValidatedType, Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedRestartPolicy {
+    #[field_type(Option<i32>)]
+    pub mode: executables::RestartMode,
+
+    pub max_restarts: Option<u32>,
+
+    #[field_type(Option<u64>)]
+    pub initial_backoff_ms: Duration,
+
+    #[field_type(Option<u64>)]
+    pub max_backoff_ms: Duration,
+}
+
+impl RestartPolicyTypeValidator for RestartPolicyValidator {
+    fn validate_mode(
+        mode: Option<i32>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<executables::RestartMode, ValidationError> {
+        let Some(mode) = mode else {
+            return Ok(executables::RestartMode::default());
+        };
+
+        let mode: RestartMode =
+            validation::valid_enum(mode, field_name, parent_name)?;
+
+        Ok(match mode {
+            RestartMode::Unspecified | RestartMode::Never => {
+                executables::RestartMode::Never
+            }
+            RestartMode::OnFailure => executables::RestartMode::OnFailure,
+            RestartMode::Always => executables::RestartMode::Always,
+        })
+    }
+
+    fn validate_max_restarts(
+        max_restarts: Option<u32>,
+        _field_name: &str,
+        _parent_name: Option<&str>,
+    ) -> Result<Option<u32>, ValidationError> {
+        // 0 allows unlimited restarts, which is represented as `None`
+        Ok(match max_restarts {
+            None => Some(executables::RestartPolicy::DEFAULT_MAX_RESTARTS),
+            Some(0) => None,
+            Some(max_restarts) => Some(max_restarts),
+        })
+    }
+
+    fn validate_initial_backoff_ms(
+        initial_backoff_ms: Option<u64>,
+        _field_name: &str,
+        _parent_name: Option<&str>,
+    ) -> Result<Duration, ValidationError> {
+        Ok(initial_backoff_ms
+            .map(Duration::from_millis)
+            .unwrap_or(executables::RestartPolicy::DEFAULT_INITIAL_BACKOFF))
+    }
+
+    fn validate_max_backoff_ms(
+        max_backoff_ms: Option<u64>,
+        _field_name: &str,
+        _parent_name: Option<&str>,
+    ) -> Result<Duration, ValidationError> {
+        Ok(max_backoff_ms
+            .map(Duration::from_millis)
+            .unwrap_or(executables::RestartPolicy::DEFAULT_MAX_BACKOFF))
+    }
+
+    fn post_validate(
+        output: &ValidatedRestartPolicy,
+        parent_name: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        if output.initial_backoff_ms > output.max_backoff_ms {
+            return Err(ValidationError::Invalid {
+                field: validation::field_name(
+                    "initial_backoff_ms",
+                    parent_name,
+                ),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl From<ValidatedRestartPolicy> for executables::RestartPolicy {
+    fn from(x: ValidatedRestartPolicy) -> Self {
+        let ValidatedRestartPolicy {
+            mode,
+            max_restarts,
+            initial_backoff_ms,
+            max_backoff_ms,
+        } = x;
+
+        Self {
+            mode,
+            max_restarts,
+            initial_backoff: initial_backoff_ms,
+            max_backoff: max_backoff_ms,
+        }
+    }
+}
+
+fn validate_security_profile(
+    security_profile: Option<SecurityProfile>,
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<Option<ValidatedSecurityProfile>, ValidationError> {
+    let Some(security_profile) = security_profile else {
+        return Ok(None);
+    };
+
+    Ok(Some(ValidatedSecurityProfile::validate(
+        security_profile,
+        Some(&*validation::field_name(field_name, parent_name)),
+    )?))
+}
+
+#[derive(ValidatedType, Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedSecurityProfile {
+    #[field_type(Option<Capabilities>)]
+    pub capabilities: Option<ValidatedCapabilities>,
+
+    #[validate(none)]
+    pub no_new_privs: bool,
+
+    #[field_type(Option<String>)]
+    pub seccomp: Option<executables::SeccompProfile>,
+}
+
+impl SecurityProfileTypeValidator for SecurityProfileValidator {
+    fn validate_capabilities(
+        capabilities: Option<Capabilities>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<ValidatedCapabilities>, ValidationError> {
+        let Some(capabilities) = capabilities else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatedCapabilities::validate(
+            capabilities,
+            Some(&*validation::field_name(field_name, parent_name)),
+        )?))
+    }
+
+    fn validate_seccomp(
+        seccomp: Option<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<Option<executables::SeccompProfile>, ValidationError> {
+        let Some(seccomp) = seccomp else {
+            return Ok(None);
+        };
+
+        let value = validation::valid_json(&seccomp, field_name, parent_name)?;
+
+        // Valid JSON that is not a valid profile, e.g., with an unknown action
+        let invalid = || ValidationError::Invalid {
+            field: validation::field_name(field_name, parent_name),
+        };
+        let profile: LinuxSeccomp =
+            serde_json::from_value(value).map_err(|_| invalid())?;
+        let profile = executables::SeccompProfile::new(seccomp, &profile)
+            .map_err(|_| invalid())?;
+
+        Ok(Some(profile))
+    }
+}
+
+impl From<ValidatedSecurityProfile> for executables::SecurityProfile {
+    fn from(x: ValidatedSecurityProfile) -> Self {
+        let ValidatedSecurityProfile { capabilities, no_new_privs, seccomp } =
+            x;
+
+        Self {
+            capabilities: capabilities.map(|x| x.into()),
+            no_new_privs,
+            seccomp,
+        }
+    }
+}
+
+#[derive(ValidatedType, Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedCapabilities {
+    #[field_type(Vec<String>)]
+    pub bounding: CapsHashSet,
+
+    #[field_type(Vec<String>)]
+    pub ambient: CapsHashSet,
+}
+
+impl CapabilitiesTypeValidator for CapabilitiesValidator {
+    fn validate_bounding(
+        bounding: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<CapsHashSet, ValidationError> {
+        validate_capability_names(bounding, field_name, parent_name)
+    }
+
+    fn validate_ambient(
+        ambient: Vec<String>,
+        field_name: &str,
+        parent_name: Option<&str>,
+    ) -> Result<CapsHashSet, ValidationError> {
+        validate_capability_names(ambient, field_name, parent_name)
+    }
+
+    fn post_validate(
+        output: &ValidatedCapabilities,
+        parent_name: Option<&str>,
+    ) -> Result<(), ValidationError> {
+        // A capability dropped from the bounding set can't be raised
+        if !output.ambient.is_subset(&output.bounding) {
+            return Err(ValidationError::Invalid {
+                field: validation::field_name("ambient", parent_name),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_capability_names(
+    names: Vec<String>,
+    field_name: &str,
+    parent_name: Option<&str>,
+) -> Result<CapsHashSet, ValidationError> {
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            name.parse::<Capability>().map_err(|_| ValidationError::Invalid {
+                field: validation::field_name(
+                    &format!("{field_name}[{i}]"),
+                    parent_name,
+                ),
+            })
+        })
+        .collect()
+}
+
+impl From<ValidatedCapabilities> for executables::Capabilities {
+    fn from(x: ValidatedCapabilities) -> Self {
+        let ValidatedCapabilities { bounding, ambient } = x;
+        Self { bounding, ambient }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    #[test]
+    fn test_cell_type_empty_cpu_valid() {
+        let validated =
+            CellValidator::validate_cpu(None, "field", Some("parent"));
+        assert!(validated.is_ok());
+        assert!(validated.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cell_type_cpu_valid() {
+        let validated = CellValidator::validate_cpu(
+            Some(CpuController { weight: Some(1000), max: None, period: None }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_ok());
+        let inner = validated.unwrap();
+        assert!(inner.is_some());
+        let controller = inner.unwrap();
+        assert_eq!(controller.weight, Some(Weight::new(1000)));
+        assert_eq!(controller.max, None);
+        assert_eq!(controller.period, None);
+    }
+
+    #[test]
+    fn test_cell_type_cpu_weight_too_small() {
+        let validated = CellValidator::validate_cpu(
+            Some(CpuController { weight: Some(0), max: None, period: None }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_cpu_weight_too_large() {
+        let validated = CellValidator::validate_cpu(
+            Some(CpuController {
+                weight: Some(10001),
+                max: None,
+                period: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_cpu_max_too_small() {
+        let validated = CellValidator::validate_cpu(
+            Some(CpuController {
+                weight: Some(1000),
+                max: Some(-1),
+                period: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_cpuset_valid() {
+        let validated = CellValidator::validate_cpuset(
+            Some(CpusetController {
+                cpus: Some(String::from("1,2-4")),
+                mems: Some(String::from("1-4")),
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_ok());
+        let inner = validated.unwrap();
+        assert!(inner.is_some());
+        let controller = inner.unwrap();
+        assert_eq!(controller.cpus, Some(Cpus::new(String::from("1,2-4"))));
+        assert_eq!(controller.mems, Some(Mems::new(String::from("1-4"))));
+    }
+
+    #[test]
+    fn test_cell_type_cpuset_invalid_cpus() {
+        let validated = CellValidator::validate_cpuset(
+            Some(CpusetController {
+                cpus: Some(String::from("foo")),
+                mems: Some(String::from("1-4")),
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_cpuset_invalid_mems() {
+        let validated = CellValidator::validate_cpuset(
+            Some(CpusetController {
+                cpus: Some(String::from("1,2-4")),
+                mems: Some(String::from("1..4")),
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_memory_valid() {
+        let validated = CellValidator::validate_memory(
+            Some(MemoryController {
+                min: None,
+                low: Some(1000),
+                high: None,
+                max: Some(10000),
+                swap_max: None,
+                swap_high: None,
+                oom_group: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_ok());
+        let inner = validated.unwrap();
+        assert!(inner.is_some());
+        let controller = inner.unwrap();
+        assert_eq!(controller.min, None);
+        assert_eq!(controller.low, Some(Protection::new(1000)));
+        assert_eq!(controller.high, None);
+        assert_eq!(controller.max, Some(Limit::new(10000)));
+    }
+
+    #[test]
+    fn test_cell_type_memory_swap_and_oom_group_valid() {
+        let validated = CellValidator::validate_memory(
+            Some(MemoryController {
+                min: Some(1000),
+                low: None,
+                high: Some(5000),
+                max: Some(10000),
+                swap_max: Some(0),
+                swap_high: Some(2000),
+                oom_group: Some(true),
+            }),
+            "field",
+            Some("parent"),
+        );
+        let controller = validated.unwrap().unwrap();
+        assert_eq!(controller.min, Some(Protection::new(1000)));
+        assert_eq!(controller.high, Some(Limit::new(5000)));
+        assert_eq!(controller.swap_max, Some(Limit::new(0)));
+        assert_eq!(controller.swap_high, Some(Limit::new(2000)));
+        assert_eq!(controller.oom_group, Some(true));
+    }
+
+    #[test]
+    fn test_cell_type_memory_swap_max_too_small() {
+        let validated = CellValidator::validate_memory(
+            Some(MemoryController {
+                min: None,
+                low: None,
+                high: None,
+                max: None,
+                swap_max: Some(-1),
+                swap_high: None,
+                oom_group: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Minimum { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_memory_low_too_small() {
+        let validated = CellValidator::validate_memory(
+            Some(MemoryController {
+                min: None,
+                low: Some(-1),
+                high: None,
+                max: Some(10000),
+                swap_max: None,
+                swap_high: None,
+                oom_group: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_memory_max_too_small() {
+        let validated = CellValidator::validate_memory(
+            Some(MemoryController {
+                min: None,
+                low: Some(1000),
+                high: None,
+                max: Some(-1),
+                swap_max: None,
+                swap_high: None,
+                oom_group: None,
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_cell_type_io_valid() {
+        let validated = CellValidator::validate_io(
+            Some(IoController {
+                weight: Some(200),
+                max: vec![
+                    "8:0 rbps=1048576 wiops=120".into(),
+                    "8:16 wbps=max riops=10".into(),
+                ],
+                latency: vec!["8:0 target=75".into()],
+            }),
+            "field",
+            Some("parent"),
+        );
+        let controller = validated.unwrap().unwrap();
+        assert_eq!(controller.weight, Some(Weight::new(200)));
+        assert_eq!(
+            controller.max,
+            vec![
+                IoMax {
+                    device: IoDevice { major: 8, minor: 0 },
+                    rbps: Some(1048576),
+                    wbps: None,
+                    riops: None,
+                    wiops: Some(120),
+                },
+                IoMax {
+                    device: IoDevice { major: 8, minor: 16 },
+                    rbps: None,
+                    wbps: None,
+                    riops: Some(10),
+                    wiops: None,
+                },
+            ]
+        );
+        assert_eq!(
+            controller.latency,
+            vec![IoLatency {
+                device: IoDevice { major: 8, minor: 0 },
+                target: 75
+            }]
+        );
+    }
+
+    #[test_case("8:0"; "no limits")]
+    #[test_case("8 rbps=1"; "no minor")]
+    #[test_case("a:0 rbps=1"; "invalid major")]
+    #[test_case("8:-1 rbps=1"; "invalid minor")]
+    #[test_case("8:0 rbps=-1"; "negative limit")]
+    #[test_case("8:0 dbps=1"; "unknown limit")]
+    #[test_case("8:0 rbps"; "limit without value")]
+    #[test]
+    fn test_cell_type_io_max_invalid(max: &str) {
+        let validated = CellValidator::validate_io(
+            Some(IoController {
+                weight: None,
+                max: vec![max.into()],
+                latency: vec![],
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_io_max_duplicate_device() {
+        let validated = CellValidator::validate_io(
+            Some(IoController {
+                weight: None,
+                max: vec!["8:0 rbps=1".into(), "8:0 wbps=1".into()],
+                latency: vec![],
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test_case("8:0"; "no target")]
+    #[test_case("8:0 target=a"; "invalid target")]
+    #[test_case("8:0 target=75 target=50"; "multiple targets")]
+    #[test_case("8:0 latency=75"; "unknown key")]
+    #[test]
+    fn test_cell_type_io_latency_invalid(latency: &str) {
+        let validated = CellValidator::validate_io(
+            Some(IoController {
+                weight: None,
+                max: vec![],
+                latency: vec![latency.into()],
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_pids_valid() {
+        let validated = CellValidator::validate_pids(
+            Some(PidsController { max: Some(100) }),
+            "field",
+            Some("parent"),
+        );
+        let controller = validated.unwrap().unwrap();
+        assert_eq!(controller.max, Some(Limit::new(100)));
+    }
+
+    #[test]
+    fn test_cell_type_pids_max_too_small() {
+        let validated = CellValidator::validate_pids(
+            Some(PidsController { max: Some(0) }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Minimum { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_hugetlb_max_valid() {
+        let page_sizes = vec!["1GB".to_string(), "2MB".to_string()];
+        let validated = validate_hugetlb_max(
+            vec!["2MB=1073741824".into(), "1GB=0".into()],
+            &page_sizes,
+            "max",
+            Some("hugetlb"),
+        );
+        assert_eq!(
+            validated.unwrap(),
+            vec![
+                HugetlbLimit {
+                    page_size: "2MB".into(),
+                    max: Limit::new(1073741824)
+                },
+                HugetlbLimit { page_size: "1GB".into(), max: Limit::new(0) },
+            ]
+        );
+    }
+
+    #[test_case("2MB"; "no limit")]
+    #[test_case("3MB=1024"; "unsupported page size")]
+    #[test_case("2MB=a"; "invalid limit")]
+    #[test]
+    fn test_cell_type_hugetlb_max_invalid(max: &str) {
+        let page_sizes = vec!["2MB".to_string()];
+        let validated = validate_hugetlb_max(
+            vec![max.into()],
+            &page_sizes,
+            "max",
+            Some("hugetlb"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_hugetlb_max_duplicate_page_size() {
+        let page_sizes = vec!["2MB".to_string()];
+        let validated = validate_hugetlb_max(
+            vec!["2MB=1024".into(), "2MB=2048".into()],
+            &page_sizes,
+            "max",
+            Some("hugetlb"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_hugetlb_max_too_small() {
+        let page_sizes = vec!["2MB".to_string()];
+        let validated = validate_hugetlb_max(
+            vec!["2MB=-1".into()],
+            &page_sizes,
+            "max",
+            Some("hugetlb"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Minimum { .. })));
+    }
+
+    fn rdma_limit(
+        device: &str,
+        hca_handle: Option<u32>,
+        hca_object: Option<u32>,
+    ) -> RdmaLimit {
+        RdmaLimit { device: device.into(), hca_handle, hca_object }
+    }
+
+    #[test]
+    fn test_cell_type_rdma_max_valid() {
+        let devices = vec!["irdma0".to_string(), "mlx4_0".to_string()];
+        let validated = validate_rdma_max(
+            vec![
+                rdma_limit("mlx4_0", Some(2), Some(2000)),
+                rdma_limit("irdma0", None, Some(0)),
+            ],
+            &devices,
+            "max",
+            Some("rdma"),
+        );
+        assert_eq!(
+            validated.unwrap(),
+            vec![
+                RdmaMax {
+                    device: "mlx4_0".into(),
+                    hca_handle: Some(2),
+                    hca_object: Some(2000),
+                },
+                RdmaMax {
+                    device: "irdma0".into(),
+                    hca_handle: None,
+                    hca_object: Some(0),
+                },
+            ]
+        );
+    }
+
+    #[test_case(rdma_limit("mlx4_0", None, None); "no limit")]
+    #[test_case(rdma_limit("mlx5_0", Some(2), None); "unknown device")]
+    #[test_case(rdma_limit("", Some(2), None); "no device")]
+    #[test]
+    fn test_cell_type_rdma_max_invalid(max: RdmaLimit) {
+        let devices = vec!["mlx4_0".to_string()];
+        let validated =
+            validate_rdma_max(vec![max], &devices, "max", Some("rdma"));
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_rdma_max_duplicate_device() {
+        let devices = vec!["mlx4_0".to_string()];
+        let validated = validate_rdma_max(
+            vec![
+                rdma_limit("mlx4_0", Some(2), None),
+                rdma_limit("mlx4_0", None, Some(2)),
+            ],
+            &devices,
+            "max",
+            Some("rdma"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_io_weight_too_large() {
+        let validated = CellValidator::validate_io(
+            Some(IoController {
+                weight: Some(10001),
+                max: vec![],
+                latency: vec![],
+            }),
+            "field",
+            Some("parent"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Maximum { .. })));
+    }
+
+    fn bind_mount(
+        source: &str,
+        target: &str,
+        readonly: bool,
+    ) -> proto::cells::Mount {
+        proto::cells::Mount {
+            r#type: MountType::Bind as i32,
+            source: source.into(),
+            target: target.into(),
+            readonly,
+            size: None,
+        }
+    }
+
+    fn tmpfs_mount(target: &str, size: Option<u64>) -> proto::cells::Mount {
+        proto::cells::Mount {
+            r#type: MountType::Tmpfs as i32,
+            source: String::new(),
+            target: target.into(),
+            readonly: false,
+            size,
+        }
+    }
+
+    #[test]
+    fn test_cell_type_mounts_valid() {
+        let validated = CellValidator::validate_mounts(
+            vec![
+                bind_mount("/data", "/mnt/data", true),
+                tmpfs_mount("/tmp", Some(1048576)),
+            ],
+            "mounts",
+            Some("cell"),
+        );
+        assert_eq!(
+            validated.unwrap(),
+            vec![
+                Mount::Bind {
+                    source: "/data".into(),
+                    target: "/mnt/data".into(),
+                    readonly: true,
+                },
+                Mount::Tmpfs { target: "/tmp".into(), size: Some(1048576) },
+            ]
+        );
+    }
+
+    #[test_case(bind_mount("", "/data", false); "bind without source")]
+    #[test_case(bind_mount("data", "/data", false); "relative source")]
+    #[test_case(bind_mount("/data", "data", false); "relative target")]
+    #[test_case(bind_mount("/data", "", false); "no target")]
+    #[test_case(
+        proto::cells::Mount { size: Some(1), ..bind_mount("/data", "/data", false) };
+        "bind with size"
+    )]
+    #[test_case(
+        proto::cells::Mount { source: "/data".into(), ..tmpfs_mount("/tmp", None) };
+        "tmpfs with source"
+    )]
+    #[test_case(
+        proto::cells::Mount { readonly: true, ..tmpfs_mount("/tmp", None) };
+        "readonly tmpfs"
+    )]
+    #[test_case(
+        proto::cells::Mount { r#type: MountType::Unspecified as i32, ..tmpfs_mount("/tmp", None) };
+        "no type"
+    )]
+    #[test_case(
+        proto::cells::Mount { r#type: 42, ..tmpfs_mount("/tmp", None) };
+        "unknown type"
+    )]
     #[test]
-    fn test_cell_type_cpuset_valid() {
-        let validated = CellValidator::validate_cpuset(
-            Some(CpusetController {
-                cpus: Some(String::from("1,2-4")),
-                mems: Some(String::from("1-4")),
-            }),
-            "field",
-            Some("parent"),
+    fn test_cell_type_mounts_invalid(mount: proto::cells::Mount) {
+        let validated =
+            CellValidator::validate_mounts(vec![mount], "mounts", Some("cell"));
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_mounts_duplicate_target() {
+        let validated = CellValidator::validate_mounts(
+            vec![tmpfs_mount("/tmp", None), bind_mount("/data", "/tmp", false)],
+            "mounts",
+            Some("cell"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_type_rootfs_relative() {
+        let validated = CellValidator::validate_rootfs(
+            Some("rootfs".into()),
+            "rootfs",
+            Some("cell"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_cell_rootfs_and_mounts_require_isolate_process() {
+        let cell = Cell {
+            name: "cell".into(),
+            rootfs: Some("/var/lib/rootfs".into()),
+            mounts: vec![tmpfs_mount("/tmp", None)],
+            ..Default::default()
+        };
+
+        let validated = ValidatedCell::validate(cell.clone(), None);
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+
+        let validated = ValidatedCell::validate(
+            Cell { isolate_process: true, ..cell },
+            None,
         );
         assert!(validated.is_ok());
-        let inner = validated.unwrap();
-        assert!(inner.is_some());
-        let controller = inner.unwrap();
-        assert_eq!(controller.cpus, Some(Cpus::new(String::from("1,2-4"))));
-        assert_eq!(controller.mems, Some(Mems::new(String::from("1-4"))));
+    }
+
+    fn id_mapping(inside_id: u32, outside_id: u32, count: u32) -> IdMapping {
+        IdMapping { inside_id, outside_id, count }
     }
 
     #[test]
-    fn test_cell_type_cpuset_invalid_cpus() {
-        let validated = CellValidator::validate_cpuset(
-            Some(CpusetController {
-                cpus: Some(String::from("foo")),
-                mems: Some(String::from("1-4")),
-            }),
-            "field",
-            Some("parent"),
+    fn test_id_map_valid() {
+        let subordinate_ids =
+            SubordinateIds::parse("root:100000:65536", Some("root"), 0);
+        let validated = validate_id_map(
+            vec![id_mapping(0, 100000, 1000), id_mapping(1000, 101000, 64536)],
+            &subordinate_ids,
+            "uid_map",
+            Some("cell"),
+        );
+        assert_eq!(
+            validated.unwrap(),
+            vec![
+                IdMap { inside: 0, outside: 100000, count: 1000 },
+                IdMap { inside: 1000, outside: 101000, count: 64536 },
+            ]
         );
-        assert!(validated.is_err());
     }
 
+    #[test_case(&[id_mapping(0, 100000, 0)]; "empty range")]
+    #[test_case(&[id_mapping(u32::MAX, 100000, 2)]; "inside out of range")]
+    #[test_case(&[id_mapping(0, 0, 1)]; "host root")]
+    #[test_case(&[id_mapping(0, 100000, 65537)]; "not delegated")]
+    #[test_case(
+        &[id_mapping(0, 100000, 10), id_mapping(5, 100010, 10)];
+        "overlapping inside"
+    )]
+    #[test_case(
+        &[id_mapping(0, 100000, 10), id_mapping(10, 100005, 10)];
+        "overlapping outside"
+    )]
     #[test]
-    fn test_cell_type_cpuset_invalid_mems() {
-        let validated = CellValidator::validate_cpuset(
-            Some(CpusetController {
-                cpus: Some(String::from("1,2-4")),
-                mems: Some(String::from("1..4")),
-            }),
-            "field",
-            Some("parent"),
+    fn test_id_map_invalid(id_map: &[IdMapping]) {
+        let subordinate_ids =
+            SubordinateIds::parse("root:100000:65536", Some("root"), 0);
+        let validated = validate_id_map(
+            id_map.to_vec(),
+            &subordinate_ids,
+            "uid_map",
+            Some("cell"),
         );
-        assert!(validated.is_err());
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
     }
 
     #[test]
-    fn test_cell_type_memory_valid() {
-        let validated = CellValidator::validate_memory(
-            Some(MemoryController {
-                min: None,
-                low: Some(1000),
-                high: None,
-                max: Some(10000),
-            }),
-            "field",
-            Some("parent"),
+    fn test_cell_isolate_user_requires_root_mapping() {
+        let cell = ValidatedCell::validate(
+            Cell {
+                name: "cell".into(),
+                isolate_user: true,
+                ..Default::default()
+            },
+            None,
         );
-        assert!(validated.is_ok());
-        let inner = validated.unwrap();
-        assert!(inner.is_some());
-        let controller = inner.unwrap();
-        assert_eq!(controller.min, None);
-        assert_eq!(controller.low, Some(Protection::new(1000)));
-        assert_eq!(controller.high, None);
-        assert_eq!(controller.max, Some(Limit::new(10000)));
+        assert!(matches!(cell, Err(ValidationError::Invalid { .. })));
     }
 
     #[test]
-    fn test_cell_type_memory_low_too_small() {
-        let validated = CellValidator::validate_memory(
-            Some(MemoryController {
-                min: None,
-                low: Some(-1),
-                high: None,
-                max: Some(10000),
+    fn test_cell_persisted_skips_host_checks() {
+        // Neither the ids, the page size, nor the device are available on a test host
+        let cell = Cell {
+            name: "cell".into(),
+            hugetlb: Some(HugetlbController { max: vec!["3MB=1024".into()] }),
+            rdma: Some(RdmaController {
+                max: vec![rdma_limit("ae-test-rdma0", Some(1), None)],
             }),
-            "field",
-            Some("parent"),
+            isolate_user: true,
+            uid_map: vec![id_mapping(0, 4000000000, 1000)],
+            gid_map: vec![id_mapping(0, 4000000000, 1000)],
+            ..Default::default()
+        };
+
+        let validated = ValidatedCell::validate(cell.clone(), None);
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+
+        let validated = ValidatedCell::validate_persisted(cell).unwrap();
+        assert!(validated.isolate_user);
+        assert_eq!(
+            validated.uid_map,
+            vec![IdMap { inside: 0, outside: 4000000000, count: 1000 }]
         );
-        assert!(validated.is_err());
+        assert_eq!(validated.hugetlb.unwrap().max.len(), 1);
+        assert_eq!(validated.rdma.unwrap().max.len(), 1);
     }
 
     #[test]
-    fn test_cell_type_memory_max_too_small() {
-        let validated = CellValidator::validate_memory(
-            Some(MemoryController {
-                min: None,
-                low: Some(1000),
-                high: None,
-                max: Some(-1),
-            }),
-            "field",
-            Some("parent"),
+    fn test_cell_persisted_is_still_validated() {
+        let cell = Cell {
+            name: "cell".into(),
+            isolate_user: true,
+            uid_map: vec![id_mapping(1, 4000000000, 1000)],
+            ..Default::default()
+        };
+
+        // The nested auraed must still be mapped to root
+        let validated = ValidatedCell::validate_persisted(cell);
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_capabilities_valid() {
+        let validated = ValidatedCapabilities::validate(
+            Capabilities {
+                bounding: vec![
+                    "CAP_NET_BIND_SERVICE".into(),
+                    "CAP_CHOWN".into(),
+                ],
+                ambient: vec!["CAP_NET_BIND_SERVICE".into()],
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            validated.bounding,
+            CapsHashSet::from([
+                Capability::CAP_NET_BIND_SERVICE,
+                Capability::CAP_CHOWN
+            ])
+        );
+        assert_eq!(
+            validated.ambient,
+            CapsHashSet::from([Capability::CAP_NET_BIND_SERVICE])
         );
-        assert!(validated.is_err());
+    }
+
+    #[test_case(&["CAP_NOT_A_CAPABILITY"], &[]; "unknown capability")]
+    #[test_case(&["CAP_CHOWN"], &["CAP_NET_BIND_SERVICE"]; "ambient not bounding")]
+    #[test]
+    fn test_capabilities_invalid(bounding: &[&str], ambient: &[&str]) {
+        let validated = ValidatedCapabilities::validate(
+            Capabilities {
+                bounding: bounding.iter().map(|x| x.to_string()).collect(),
+                ambient: ambient.iter().map(|x| x.to_string()).collect(),
+            },
+            None,
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+    }
+
+    #[test]
+    fn test_security_profile_seccomp_valid() {
+        let json = r#"{
+            "defaultAction": "SCMP_ACT_ERRNO",
+            "syscalls": [{ "names": ["read"], "action": "SCMP_ACT_ALLOW" }]
+        }"#;
+        let validated = SecurityProfileValidator::validate_seccomp(
+            Some(json.into()),
+            "seccomp",
+            Some("security_profile"),
+        )
+        .unwrap();
+        assert_eq!(validated.expect("seccomp profile").json(), json);
+    }
+
+    #[test_case("defaultAction: SCMP_ACT_ERRNO"; "invalid json")]
+    #[test_case(r#"{"syscalls": []}"#; "missing default action")]
+    #[test_case(r#"{"defaultAction": "SCMP_ACT_NOPE"}"#; "unknown action")]
+    #[test_case(r#"{"defaultAction": "SCMP_ACT_NOTIFY"}"#; "unsupported action")]
+    #[test]
+    fn test_security_profile_seccomp_invalid(json: &str) {
+        let validated = SecurityProfileValidator::validate_seccomp(
+            Some(json.into()),
+            "seccomp",
+            Some("security_profile"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
     }
 
     #[test]
@@ -495,6 +2640,7 @@ mod tests {
                 command: String::from(""),
                 name: String::from("name"),
                 description: String::from("description"),
+                ..Default::default()
             }),
             "field",
             Some("parent"),
@@ -509,6 +2655,7 @@ mod tests {
                 command: String::from("command"),
                 name: String::from("name"),
                 description: String::from("description"),
+                ..Default::default()
             }),
             "field",
             Some("parent"),
@@ -519,16 +2666,84 @@ mod tests {
             ValidatedExecutable {
                 name: ExecutableName::new(String::from("name")),
                 description: String::from("description"),
-                command: OsString::from("command"),
+                command: Some(OsString::from("command")),
+                args: vec![],
+                env: vec![],
+                clear_env: false,
+                cwd: None,
+                umask: None,
+                restart_policy: ValidatedRestartPolicy {
+                    mode: executables::RestartMode::Never,
+                    max_restarts: Some(5),
+                    initial_backoff_ms: Duration::from_millis(100),
+                    max_backoff_ms: Duration::from_secs(30),
+                },
+                security_profile: None,
             },
         );
     }
 
     #[test]
-    fn test_executable_empty_command() {
-        assert!(ExecutableValidator::validate_command(
-            String::from(""),
+    fn test_cell_service_start_request_args_valid() {
+        let validated = CellServiceStartRequestValidator::validate_executable(
+            Some(Executable {
+                name: String::from("name"),
+                args: vec![String::from("/bin/echo"), String::from("a b")],
+                env: vec![String::from("FOO=bar=baz")],
+                clear_env: true,
+                cwd: Some(String::from("/tmp")),
+                umask: Some(0o022),
+                restart_policy: Some(RestartPolicy {
+                    mode: Some(RestartMode::OnFailure.into()),
+                    max_restarts: Some(0),
+                    initial_backoff_ms: Some(10),
+                    max_backoff_ms: Some(1000),
+                }),
+                ..Default::default()
+            }),
             "field",
+            Some("parent"),
+        );
+        assert!(validated.is_ok());
+        assert_eq!(
+            validated.unwrap(),
+            ValidatedExecutable {
+                name: ExecutableName::new(String::from("name")),
+                description: String::new(),
+                command: None,
+                args: vec![OsString::from("/bin/echo"), OsString::from("a b")],
+                env: vec![(OsString::from("FOO"), OsString::from("bar=baz"))],
+                clear_env: true,
+                cwd: Some(PathBuf::from("/tmp")),
+                umask: Some(0o022),
+                restart_policy: ValidatedRestartPolicy {
+                    mode: executables::RestartMode::OnFailure,
+                    max_restarts: None,
+                    initial_backoff_ms: Duration::from_millis(10),
+                    max_backoff_ms: Duration::from_secs(1),
+                },
+                security_profile: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_executable_empty_command() {
+        assert!(ExecutableValidator::pre_validate(
+            &Executable { command: String::from(""), ..Default::default() },
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_executable_command_and_args() {
+        assert!(ExecutableValidator::pre_validate(
+            &Executable {
+                command: String::from("command"),
+                args: vec![String::from("command")],
+                ..Default::default()
+            },
             Some("parent")
         )
         .is_err());
@@ -542,6 +2757,239 @@ mod tests {
             Some("parent"),
         );
         assert!(validated.is_ok());
-        assert_eq!(validated.unwrap(), OsString::from("command"));
+        assert_eq!(validated.unwrap(), Some(OsString::from("command")));
+    }
+
+    #[test]
+    fn test_executable_empty_program() {
+        assert!(ExecutableValidator::validate_args(
+            vec![String::from(""), String::from("arg")],
+            "field",
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_executable_env_without_key() {
+        assert!(ExecutableValidator::validate_env(
+            vec![String::from("=value")],
+            "field",
+            Some("parent")
+        )
+        .is_err());
+        assert!(ExecutableValidator::validate_env(
+            vec![String::from("KEY")],
+            "field",
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_executable_relative_cwd() {
+        assert!(ExecutableValidator::validate_cwd(
+            Some(String::from("tmp")),
+            "field",
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_executable_umask_too_large() {
+        assert!(ExecutableValidator::validate_umask(
+            Some(0o1000),
+            "field",
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_restart_policy_invalid_mode() {
+        assert!(RestartPolicyValidator::validate_mode(
+            Some(42),
+            "field",
+            Some("parent")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_restart_policy_initial_backoff_above_max() {
+        let validated = ValidatedRestartPolicy::validate(
+            RestartPolicy {
+                mode: None,
+                max_restarts: None,
+                initial_backoff_ms: Some(2000),
+                max_backoff_ms: Some(1000),
+            },
+            Some("parent"),
+        );
+        assert!(validated.is_err());
+    }
+
+    #[test]
+    fn test_stop_request_defaults() {
+        let validated = ValidatedCellServiceStopRequest::validate(
+            CellServiceStopRequest {
+                cell_name: None,
+                executable_name: String::from("name"),
+                signal: None,
+                grace_period_ms: None,
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(validated.signal, Signal::SIGTERM);
+        assert_eq!(validated.grace_period_ms, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_stop_request_invalid_signal() {
+        for signal in [0, 1000] {
+            assert!(CellServiceStopRequestValidator::validate_signal(
+                Some(signal),
+                "field",
+                Some("parent")
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_signal_request_requires_cell_or_executable() {
+        assert!(CellServiceSignalRequestValidator::pre_validate(
+            &CellServiceSignalRequest {
+                cell_name: None,
+                executable_name: None,
+                signal: 1,
+            },
+            None,
+        )
+        .is_err());
+        assert!(CellServiceSignalRequestValidator::pre_validate(
+            &CellServiceSignalRequest {
+                cell_name: Some(String::from("cell")),
+                executable_name: None,
+                signal: 1,
+            },
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_cell_service_watch_stats_request_default_interval() {
+        let validated = ValidatedCellServiceWatchStatsRequest::validate(
+            CellServiceWatchStatsRequest { cell_name: None, interval_ms: None },
+            None,
+        )
+        .expect("valid request");
+
+        assert_eq!(
+            validated.interval_ms,
+            ValidatedCellServiceWatchStatsRequest::DEFAULT_INTERVAL
+        );
+    }
+
+    #[test]
+    fn test_cell_service_watch_stats_request_interval_too_small() {
+        let validated = ValidatedCellServiceWatchStatsRequest::validate(
+            CellServiceWatchStatsRequest {
+                cell_name: Some("ae-test".into()),
+                interval_ms: Some(10),
+            },
+            None,
+        );
+
+        assert!(matches!(validated, Err(ValidationError::Minimum { .. })));
+    }
+
+    #[test]
+    fn test_cell_service_update_request_valid() {
+        let validated = ValidatedCellServiceUpdateRequest::validate(
+            CellServiceUpdateRequest {
+                cell_name: "ae-test".into(),
+                cpu: None,
+                cpuset: None,
+                memory: Some(MemoryController {
+                    min: None,
+                    low: None,
+                    high: None,
+                    max: Some(10000),
+                    swap_max: None,
+                    swap_high: None,
+                    oom_group: None,
+                }),
+                io: None,
+                pids: None,
+                hugetlb: None,
+                rdma: None,
+            },
+            None,
+        )
+        .expect("valid request");
+
+        let spec: CgroupSpec = validated.into();
+        assert!(spec.cpu.is_none());
+        assert_eq!(spec.memory.and_then(|x| x.max), Some(Limit::new(10000)));
+    }
+
+    #[test]
+    fn test_cell_service_update_request_invalid_controller() {
+        let validated = ValidatedCellServiceUpdateRequest::validate(
+            CellServiceUpdateRequest {
+                cell_name: "ae-test".into(),
+                cpu: Some(CpuController {
+                    weight: Some(0),
+                    max: None,
+                    period: None,
+                }),
+                cpuset: None,
+                memory: None,
+                io: None,
+                pids: None,
+                hugetlb: None,
+                rdma: None,
+            },
+            None,
+        );
+
+        assert!(matches!(validated, Err(ValidationError::Minimum { .. })));
+    }
+    #[test]
+    fn test_cell_service_exec_start() {
+        let validated = ValidatedCellServiceExecStart::validate(
+            CellServiceExecStart { args: vec![], ..Default::default() },
+            Some("start"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Required { .. })));
+
+        let validated = ValidatedCellServiceExecStart::validate(
+            CellServiceExecStart {
+                args: vec!["sh".into()],
+                env: vec!["KEY".into()],
+                ..Default::default()
+            },
+            Some("start"),
+        );
+        assert!(matches!(validated, Err(ValidationError::Invalid { .. })));
+
+        let validated = ValidatedCellServiceExecStart::validate(
+            CellServiceExecStart {
+                args: vec!["sh".into()],
+                env: vec!["KEY=value".into()],
+                tty: true,
+                ..Default::default()
+            },
+            Some("start"),
+        )
+        .expect("valid start");
+
+        let spec: exec::ExecSpec = validated.into();
+        assert_eq!(spec.command.as_std().get_program(), "sh");
+        assert_eq!(spec.tty, Some(TerminalSize::default()));
     }
 }
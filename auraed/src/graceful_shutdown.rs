@@ -49,7 +49,11 @@ impl GracefulShutdown {
     /// Waits for a signal and then...
     /// * Broadcasts a shutdown signal to all subscribers. See [subscribe]
     /// * Waits for all subscribers to drop
-    /// * Calls [CellService::free_all]
+    /// * On [SIGTERM], if the [CellService] persists its state, calls
+    ///   [CellService::detach_all], which leaves the cells running for the next run of auraed
+    /// * Otherwise, calls [CellService::free_all] and [CellService::stop_all], which sends
+    ///   executables [SIGTERM] and kills those that have not exited by the end of the default
+    ///   grace period
     /// ---
     /// Signals:
     /// * [SIGTERM]
@@ -57,10 +61,10 @@ impl GracefulShutdown {
     /// ---
     /// Returns after processing the first received signal.
     pub async fn wait(mut self) {
-        tokio::select! {
-            _ = wait_for_sigterm() => {},
-            _ = wait_for_sigint() => {},
-        }
+        let sigterm = tokio::select! {
+            _ = wait_for_sigterm() => true,
+            _ = wait_for_sigint() => false,
+        };
 
         // update health reporter
         let health_reporter = self.health_reporter.borrow_mut();
@@ -77,6 +81,17 @@ impl GracefulShutdown {
         // wait for all subscribers to drop
         self.shutdown_broadcaster.closed().await;
 
+        // A nested auraed, which doesn't persist its state, is stopped with SIGTERM when its
+        // cell is freed, so it must free its own cells.
+        if sigterm && self.cell_service.persists_state().await {
+            if let Err(e) = self.cell_service.detach_all().await {
+                error!(
+                    "Attempt to detach all cells on terminate resulted in error: {e}"
+                )
+            }
+            return;
+        }
+
         if let Err(e) = self.cell_service.free_all().await {
             error!(
                 "Attempt to free all cells on terminate resulted in error: {e}"
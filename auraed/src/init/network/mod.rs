@@ -18,7 +18,7 @@ use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 use netlink_packet_route::rtnl::link::nlas::Nla;
 use rtnetlink::Handle;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, Ipv4Addr};
 use std::str;
 use std::thread;
 use std::time::Duration;
@@ -42,6 +42,12 @@ pub(crate) enum NetworkError {
     ErrorSettingLinkUp { iface: String, source: rtnetlink::Error },
     #[error("Failed to set link down for device `{iface}`: {source}")]
     ErrorSettingLinkDown { iface: String, source: rtnetlink::Error },
+    #[error("Failed to add link `{iface}`: {source}")]
+    ErrorAddingLink { iface: String, source: rtnetlink::Error },
+    #[error("Failed to change link `{iface}`: {source}")]
+    ErrorChangingLink { iface: String, source: rtnetlink::Error },
+    #[error("Failed to delete link `{iface}`: {source}")]
+    ErrorDeletingLink { iface: String, source: rtnetlink::Error },
     #[error("Error adding route from `{route_source}` to {route_destination}` for device `{iface}`: {source}")]
     ErrorAddingRoute {
         iface: String,
@@ -101,6 +107,69 @@ impl Network {
         }
         info!("==========================");
     }
+
+    /// Creates the bridge `name` with `address` if it does not exist yet, and sets it up.
+    pub(crate) async fn ensure_bridge(
+        &self,
+        name: &str,
+        address: Ipv4Network,
+    ) -> Result<(), NetworkError> {
+        if get_link_index(&self.0, name.to_owned()).await.is_err() {
+            match add_bridge(&self.0, name.to_owned()).await {
+                Ok(()) => {
+                    add_address(&self.0, name.to_owned(), address).await?
+                }
+                // Another auraed may have created the bridge in the meantime
+                Err(e) => {
+                    if get_link_index(&self.0, name.to_owned()).await.is_err() {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        set_link_up(&self.0, name.to_owned()).await
+    }
+
+    /// Creates a veth pair, attaches the `host` end to `bridge` and moves the `peer` end
+    /// into the network namespace of the process `pid`.
+    pub(crate) async fn add_veth_to_netns(
+        &self,
+        host: &str,
+        peer: &str,
+        bridge: &str,
+        pid: u32,
+    ) -> Result<(), NetworkError> {
+        add_veth(&self.0, host.to_owned(), peer.to_owned()).await?;
+        set_link_master(&self.0, host.to_owned(), bridge.to_owned()).await?;
+        set_link_up(&self.0, host.to_owned()).await?;
+        set_link_netns(&self.0, peer.to_owned(), pid).await?;
+        Ok(())
+    }
+
+    /// Renames the moved `peer` end of a veth pair to `iface`, and routes all traffic
+    /// through the `gateway` over it. Also sets up the loopback device.
+    pub(crate) async fn configure_veth(
+        &self,
+        peer: &str,
+        iface: &str,
+        address: Ipv4Network,
+        gateway: Ipv4Addr,
+    ) -> Result<(), NetworkError> {
+        set_link_name(&self.0, peer.to_owned(), iface.to_owned()).await?;
+        add_address(&self.0, iface.to_owned(), address).await?;
+        set_link_up(&self.0, "lo".to_owned()).await?;
+        set_link_up(&self.0, iface.to_owned()).await?;
+        add_default_route_v4(&self.0, iface.to_owned(), gateway).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn delete_link(
+        &self,
+        iface: &str,
+    ) -> Result<(), NetworkError> {
+        delete_link(&self.0, iface.to_owned()).await
+    }
 }
 
 async fn configure_loopback(handle: &Handle) -> Result<(), NetworkError> {
@@ -131,6 +200,7 @@ async fn configure_loopback(handle: &Handle) -> Result<(), NetworkError> {
     .await?;
 
     set_link_up(handle, LOOPBACK_DEV.to_owned()).await?;
+    wait_for_link_up(LOOPBACK_DEV);
 
     info!("Successfully configured {}", LOOPBACK_DEV);
     Ok(())
@@ -152,6 +222,7 @@ async fn configure_nic(
     add_address(handle, config.device.clone(), ipv6_addr).await?;
 
     set_link_up(handle, config.device.clone()).await?;
+    wait_for_link_up(&config.device);
 
     add_route_v6(
         handle,
@@ -203,15 +274,19 @@ async fn set_link_up(
         .execute()
         .await
         .map(|_| {
-            // TODO: replace sleep with an await mechanism that checks if device is up (with a timeout)
-            // TODO: https://github.com/aurae-runtime/auraed/issues/40
-            info!("Waiting for link '{iface}' to become up");
-            thread::sleep(Duration::from_secs(3));
-            info!("Waited 3 seconds, assuming link '{iface}' is up");
+            trace!("Set link {iface} up");
         })
         .map_err(|e| NetworkError::ErrorSettingLinkUp { iface, source: e })
 }
 
+fn wait_for_link_up(iface: &str) {
+    // TODO: replace sleep with an await mechanism that checks if device is up (with a timeout)
+    // TODO: https://github.com/aurae-runtime/auraed/issues/40
+    info!("Waiting for link '{iface}' to become up");
+    thread::sleep(Duration::from_secs(3));
+    info!("Waited 3 seconds, assuming link '{iface}' is up");
+}
+
 #[allow(unused)]
 async fn set_link_down(
     handle: &Handle,
@@ -231,6 +306,114 @@ async fn set_link_down(
         .map_err(|e| NetworkError::ErrorSettingLinkDown { iface, source: e })
 }
 
+async fn add_bridge(
+    handle: &Handle,
+    iface: String,
+) -> Result<(), NetworkError> {
+    handle
+        .link()
+        .add()
+        .bridge(iface.clone())
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Added bridge {iface}");
+        })
+        .map_err(|e| NetworkError::ErrorAddingLink { iface, source: e })
+}
+
+async fn add_veth(
+    handle: &Handle,
+    iface: String,
+    peer: String,
+) -> Result<(), NetworkError> {
+    handle
+        .link()
+        .add()
+        .veth(iface.clone(), peer)
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Added veth pair {iface}");
+        })
+        .map_err(|e| NetworkError::ErrorAddingLink { iface, source: e })
+}
+
+async fn set_link_master(
+    handle: &Handle,
+    iface: String,
+    master: String,
+) -> Result<(), NetworkError> {
+    let link_index = get_link_index(handle, iface.clone()).await?;
+    let master_index = get_link_index(handle, master.clone()).await?;
+
+    handle
+        .link()
+        .set(link_index)
+        .master(master_index)
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Attached link {iface} to {master}");
+        })
+        .map_err(|e| NetworkError::ErrorChangingLink { iface, source: e })
+}
+
+async fn set_link_netns(
+    handle: &Handle,
+    iface: String,
+    pid: u32,
+) -> Result<(), NetworkError> {
+    let link_index = get_link_index(handle, iface.clone()).await?;
+
+    handle
+        .link()
+        .set(link_index)
+        .setns_by_pid(pid)
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Moved link {iface} to the network namespace of {pid}");
+        })
+        .map_err(|e| NetworkError::ErrorChangingLink { iface, source: e })
+}
+
+async fn set_link_name(
+    handle: &Handle,
+    iface: String,
+    name: String,
+) -> Result<(), NetworkError> {
+    let link_index = get_link_index(handle, iface.clone()).await?;
+
+    handle
+        .link()
+        .set(link_index)
+        .name(name.clone())
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Renamed link {iface} to {name}");
+        })
+        .map_err(|e| NetworkError::ErrorChangingLink { iface, source: e })
+}
+
+async fn delete_link(
+    handle: &Handle,
+    iface: String,
+) -> Result<(), NetworkError> {
+    let link_index = get_link_index(handle, iface.clone()).await?;
+
+    handle
+        .link()
+        .del(link_index)
+        .execute()
+        .await
+        .map(|_| {
+            trace!("Deleted link {iface}");
+        })
+        .map_err(|e| NetworkError::ErrorDeletingLink { iface, source: e })
+}
+
 async fn get_link_index(
     handle: &Handle,
     iface: String,
@@ -278,6 +461,33 @@ async fn add_route_v4(
     Ok(())
 }
 
+async fn add_default_route_v4(
+    handle: &Handle,
+    iface: String,
+    gateway: Ipv4Addr,
+) -> Result<(), NetworkError> {
+    let link_index = get_link_index(handle, iface.clone()).await?;
+
+    handle
+        .route()
+        .add()
+        .v4()
+        .gateway(gateway)
+        .output_interface(link_index)
+        .execute()
+        .await
+        .map_err(|e| NetworkError::ErrorAddingRoute {
+            iface,
+            route_source: Ipv4Network::from(gateway).into(),
+            route_destination: Ipv4Network::new(Ipv4Addr::UNSPECIFIED, 0)
+                .expect("valid ipv4 network")
+                .into(),
+            source: e,
+        })?;
+
+    Ok(())
+}
+
 async fn add_route_v6(
     handle: &Handle,
     iface: String,
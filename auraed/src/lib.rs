@@ -77,6 +77,7 @@ use crate::{
 };
 use anyhow::{anyhow, Context};
 use aurae_ebpf_shared::{ForkedProcess, ProcessExit, Signal};
+use ipnetwork::Ipv4Network;
 use once_cell::sync::OnceCell;
 use proto::{
     cells::cell_service_server::CellServiceServer,
@@ -129,6 +130,16 @@ pub struct AuraedRuntime {
     pub runtime_dir: PathBuf,
     /// Configurable library directory. Defaults to /var/lib/aurae.
     pub library_dir: PathBuf,
+    /// Configurable directory for persisted executable logs. Defaults to /var/run/aurae/logs.
+    pub logs_dir: PathBuf,
+    /// Configurable directory for the persisted state of cells and executables, which are
+    /// adopted again after a restart. Defaults to /var/lib/aurae/state.
+    pub state_dir: PathBuf,
+    /// Host bridge that network isolated cells are attached to. Defaults to aurae0.
+    pub cell_bridge: String,
+    /// Pool of addresses for network isolated cells. Defaults to 10.88.0.0/16.
+    /// The first address of the pool is assigned to the bridge, and is the gateway of the cells.
+    pub cell_network: Ipv4Network,
     // /// Provides logging channels to expose auraed logging via grpc
     //pub log_collector: Arc<LogChannel>,
 }
@@ -157,6 +168,11 @@ impl Default for AuraedRuntime {
             server_key: PathBuf::from("/etc/aurae/pki/server.key"),
             runtime_dir: PathBuf::from("/var/run/aurae"),
             library_dir: PathBuf::from("/var/lib/aurae"),
+            logs_dir: PathBuf::from("/var/run/aurae/logs"),
+            state_dir: PathBuf::from("/var/lib/aurae/state"),
+            cell_bridge: String::from("aurae0"),
+            cell_network: Ipv4Network::new([10, 88, 0, 0].into(), 16)
+                .expect("valid cell network"),
         }
     }
 }
@@ -253,7 +269,17 @@ pub async fn run(
         let observe_service_server =
             ObserveServiceServer::new(observe_service.clone());
 
-        let cell_service = CellService::new(observe_service.clone());
+        let cell_service = if context == AuraeContext::Cell {
+            // The cells and executables of a nested auraed live as long as the nested auraed,
+            // which outlives restarts of its parent.
+            CellService::new(observe_service.clone())
+        } else {
+            CellService::with_state_dir(
+                observe_service.clone(),
+                runtime.state_dir.clone(),
+            )
+            .await
+        };
         let cell_service_server = CellServiceServer::new(cell_service.clone());
         health_reporter.set_serving::<CellServiceServer<CellService>>().await;
 
@@ -289,7 +315,6 @@ pub async fn run(
         let graceful_shutdown_signal = graceful_shutdown.subscribe();
 
         // Run the server concurrently
-        // TODO: pass a known-good path to CellService to store any runtime data.
         let server_handle = tokio::spawn(async move {
             server
                 .add_service(health_service)
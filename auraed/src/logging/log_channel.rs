@@ -13,9 +13,28 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
-use super::get_timestamp_sec;
-use proto::observe::LogItem;
-use tokio::sync::broadcast::{self, Receiver, Sender};
+use super::{get_timestamp, log_store::LogStore};
+use pbjson_types::Timestamp;
+use proto::observe::{LogChannelType, LogItem, LogSeverity};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{
+    broadcast::{self, error::RecvError, Receiver, Sender},
+    mpsc, watch,
+};
+use tracing::warn;
+
+/// The number of lines buffered for each consumer.
+/// Lines a slower consumer misses are read back from the [LogStore] of the channel, if any.
+const CAPACITY: usize = 1024;
+
+/// The maximum number of lines the [LogWriter] appends to the [LogStore] at once.
+const WRITE_BATCH_SIZE: usize = 256;
 
 /// Abstraction Layer for one log generating entity
 /// LogChannel provides channels between Log producers and log consumers
@@ -24,14 +43,54 @@ pub struct LogChannel {
     /// The human readable (public) name for this log channel.
     pub name: String,
     tx: Sender<LogItem>,
+    /// The sequence number of the last line sent to the channel.
+    /// Held while sending, so that lines are persisted and broadcast in order.
+    sequence: Arc<Mutex<u64>>,
+    /// Optional on-disk history of everything sent to this channel.
+    store: Option<LogWriter>,
+    /// The executable writing to this channel, if any.
+    executable_name: String,
+    /// The stream of the executable this channel is attached to.
+    stream: LogChannelType,
 }
 
 impl LogChannel {
     /// Constructor creating the channel for log communication
     pub fn new(name: String) -> LogChannel {
-        // TODO: decide for a cap. 40 is arbitrary
-        let (tx, _) = broadcast::channel(40);
-        LogChannel { name, tx }
+        let (tx, _) = broadcast::channel(CAPACITY);
+        LogChannel {
+            name,
+            tx,
+            sequence: Arc::new(Mutex::new(0)),
+            store: None,
+            executable_name: String::new(),
+            stream: LogChannelType::Unspecified,
+        }
+    }
+
+    /// Persists every log line sent to the channel to `store`.
+    /// Lines are numbered after those already in `store`, which remain part of the history.
+    /// Must be called from within a tokio runtime, which runs the writer of the store.
+    pub fn with_store(mut self, store: LogStore) -> LogChannel {
+        let sequence = store.last_sequence().unwrap_or_else(|e| {
+            warn!("failed to read log store of {}: {e}", self.name);
+            0
+        });
+
+        self.sequence = Arc::new(Mutex::new(sequence));
+        self.store = Some(LogWriter::spawn(store, sequence, self.name.clone()));
+        self
+    }
+
+    /// Attributes every log line sent to the channel to a stream of an executable
+    pub fn with_executable(
+        mut self,
+        executable_name: String,
+        stream: LogChannelType,
+    ) -> LogChannel {
+        self.executable_name = executable_name;
+        self.stream = stream;
+        self
     }
 
     /// Getter for consumer channel
@@ -39,16 +98,250 @@ impl LogChannel {
         self.tx.subscribe()
     }
 
+    /// Returns a [LogSubscription] to every line sent to the channel from now on.
+    pub fn follow(&self) -> LogSubscription {
+        // Holding the sequence lock ensures no line is sent between subscribing and reading it
+        let sequence = self.sequence.lock().expect("log sequence lock");
+        LogSubscription {
+            rx: self.tx.subscribe(),
+            store: self.store.clone(),
+            marker: self.item(String::new(), LogSeverity::Warn),
+            last: *sequence,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns the persisted history, filtered by `since` and `tail` (see [LogStore::read]),
+    /// together with a [LogSubscription] to every line sent afterwards.
+    /// Channels without a store have no history.
+    pub async fn history(
+        &self,
+        since: Option<Timestamp>,
+        tail: Option<usize>,
+    ) -> io::Result<(Vec<LogItem>, LogSubscription)> {
+        let subscription = self.follow();
+        let Some(store) = &self.store else {
+            return Ok((vec![], subscription));
+        };
+
+        let items = store.read(1..=subscription.last, since, tail).await?;
+        Ok((items, subscription))
+    }
+
     /// Wrapper that sends a log line to the channel
     pub fn send(&self, line: String) {
+        let fields = parse_fields(&line);
+        let severity = fields
+            .get("level")
+            .or_else(|| fields.get("severity"))
+            .and_then(|x| parse_severity(x))
+            .unwrap_or(match self.stream {
+                LogChannelType::Unspecified => LogSeverity::Unspecified,
+                LogChannelType::Stdout => LogSeverity::Info,
+                LogChannelType::Stderr => LogSeverity::Error,
+            });
+
+        let mut item = self.item(line, severity);
+        item.fields = fields;
+
+        let mut sequence = self.sequence.lock().expect("log sequence lock");
+        *sequence += 1;
+        item.sequence = *sequence;
+
+        if let Some(store) = &self.store {
+            // The writer only stops once every clone of the channel is dropped
+            let _ = store.tx.send(item.clone());
+        }
+
         // send returns an Err if there are no receivers. We ignore that.
-        let _ = self.tx.send(LogItem {
+        let _ = self.tx.send(item);
+    }
+
+    fn item(&self, line: String, severity: LogSeverity) -> LogItem {
+        LogItem {
             channel: self.name.clone(),
             line,
-            // TODO: milliseconds type in protobuf requires 128bit type
-            timestamp: get_timestamp_sec(),
+            timestamp: Some(get_timestamp()),
+            severity: severity.into(),
+            stream: self.stream.into(),
+            executable_name: self.executable_name.clone(),
+            // Executables do not know their cell, it is filled in when proxying out of the cell
+            cell_name: String::new(),
+            fields: HashMap::new(),
+            sequence: 0,
+        }
+    }
+}
+
+/// The consumer side of a [LogChannel], returning every line sent to the channel in order.
+/// Lines missed by falling behind are read back from the [LogStore] of the channel. Lines that
+/// cannot be read back are reported by a single warning line.
+#[derive(Debug)]
+pub struct LogSubscription {
+    rx: Receiver<LogItem>,
+    store: Option<LogWriter>,
+    /// The line reporting dropped lines, without its text.
+    marker: LogItem,
+    /// The sequence number of the last line returned.
+    last: u64,
+    /// Lines read back from the store, followed by the line received after falling behind.
+    pending: VecDeque<LogItem>,
+}
+
+impl LogSubscription {
+    /// Returns the next line, or [None] once the channel is closed.
+    pub async fn recv(&mut self) -> Option<LogItem> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                self.last = item.sequence;
+                return Some(item);
+            }
+
+            let item = match self.rx.recv().await {
+                Ok(item) => item,
+                // The next line received is the oldest one still buffered, whose sequence
+                // number tells which lines were missed
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            };
+
+            if item.sequence > self.last + 1 {
+                self.pending = self.read_back(item.sequence - 1).await;
+            }
+            self.pending.push_back(item);
+        }
+    }
+
+    /// Returns the lines after the last one returned, up to and including `until`.
+    async fn read_back(&self, until: u64) -> VecDeque<LogItem> {
+        let missed = self.last + 1..=until;
+
+        let mut items = match &self.store {
+            Some(store) => store
+                .read(missed.clone(), None, None)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "failed to read back log lines of {}: {e}",
+                        self.marker.channel
+                    );
+                    vec![]
+                }),
+            None => vec![],
+        };
+
+        // Lines may be missing from the store as it rotated, or failed to persist them
+        let dropped = missed.count() - items.len();
+        if dropped > 0 {
+            items.push(LogItem {
+                line: format!("auraed: {dropped} log lines were dropped"),
+                timestamp: Some(get_timestamp()),
+                sequence: until,
+                ..self.marker.clone()
+            });
+        }
+
+        items.into()
+    }
+}
+
+/// Appends the lines sent to a [LogChannel] to its [LogStore], so that sending never waits on
+/// the disk.
+#[derive(Clone, Debug)]
+struct LogWriter {
+    store: Arc<Mutex<LogStore>>,
+    tx: mpsc::UnboundedSender<LogItem>,
+    /// The sequence number of the last line the writer is done with.
+    written: watch::Receiver<u64>,
+}
+
+impl LogWriter {
+    /// Spawns the task writing to `store`, which runs until the channel is dropped.
+    /// `sequence` is the sequence number of the last line already in `store`.
+    fn spawn(store: LogStore, sequence: u64, name: String) -> Self {
+        let store = Arc::new(Mutex::new(store));
+        let (tx, mut rx) = mpsc::unbounded_channel::<LogItem>();
+        let (written_tx, written) = watch::channel(sequence);
+
+        let writer_store = store.clone();
+        let _ignored = tokio::spawn(async move {
+            let mut items = Vec::with_capacity(WRITE_BATCH_SIZE);
+            while rx.recv_many(&mut items, WRITE_BATCH_SIZE).await > 0 {
+                let items = std::mem::take(&mut items);
+                let last = items.last().expect("received items").sequence;
+
+                let store = writer_store.clone();
+                let written = tokio::task::spawn_blocking(move || {
+                    let mut store = store.lock().expect("log store lock");
+                    items.iter().try_for_each(|item| store.append(item))
+                })
+                .await
+                .map_err(io::Error::other)
+                .and_then(|x| x);
+                if let Err(e) = written {
+                    warn!("failed to persist log lines for {name}: {e}");
+                }
+
+                // Lines that failed to persist are reported as dropped when read back
+                let _ = written_tx.send_replace(last);
+            }
         });
+
+        Self { store, tx, written }
+    }
+
+    /// Reads lines from the store (see [LogStore::read]), once every line numbered up to the end
+    /// of `sequences` has been written.
+    async fn read(
+        &self,
+        sequences: RangeInclusive<u64>,
+        since: Option<Timestamp>,
+        tail: Option<usize>,
+    ) -> io::Result<Vec<LogItem>> {
+        let until = *sequences.end();
+        // Only fails if the writer is gone, in which case there is nothing left to wait for
+        let _ = self.written.clone().wait_for(|x| *x >= until).await;
+
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || {
+            let store = store.lock().expect("log store lock");
+            store.read(sequences, since.as_ref(), tail)
+        })
+        .await
+        .map_err(io::Error::other)?
+    }
+}
+
+/// Returns the top level keys of a line that is a JSON object.
+/// Values that are not strings are JSON encoded.
+fn parse_fields(line: &str) -> HashMap<String, String> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return HashMap::new();
     }
+
+    let Ok(Value::Object(object)) = serde_json::from_str(line) else {
+        return HashMap::new();
+    };
+
+    object
+        .into_iter()
+        .map(|(key, value)| match value {
+            Value::String(value) => (key, value),
+            value => (key, value.to_string()),
+        })
+        .collect()
+}
+
+fn parse_severity(level: &str) -> Option<LogSeverity> {
+    Some(match level.to_lowercase().as_str() {
+        "trace" => LogSeverity::Trace,
+        "debug" => LogSeverity::Debug,
+        "info" | "notice" => LogSeverity::Info,
+        "warn" | "warning" => LogSeverity::Warn,
+        "error" | "fatal" | "critical" | "panic" => LogSeverity::Error,
+        _ => return None,
+    })
 }
 
 #[cfg(test)]
@@ -56,6 +349,7 @@ mod tests {
     use super::*;
     use log::Level;
     use simplelog::SimpleLogger;
+    use std::path::PathBuf;
 
     fn init_logging() {
         let logger_simple = SimpleLogger::new(
@@ -89,4 +383,117 @@ mod tests {
         assert!(cur_item.is_some());
         assert_eq!(cur_item.unwrap().line, "bye".to_string());
     }
-}
\ No newline at end of file
+
+    fn store_path() -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()))
+            .join("stdout.log")
+    }
+
+    fn store() -> LogStore {
+        LogStore::open(store_path()).expect("failed to open log store")
+    }
+
+    #[tokio::test]
+    async fn history_must_include_lines_sent_before_subscribing() {
+        let channel = LogChannel::new("Test".into()).with_store(store());
+
+        channel.send("hello".into());
+        channel.send("aurae".into());
+
+        let (history, mut rx) = channel
+            .history(None, Some(1))
+            .await
+            .expect("failed to read history");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].line, "aurae".to_string());
+        assert_eq!(history[0].sequence, 2);
+
+        channel.send("bye".into());
+
+        let cur_item = rx.recv().await;
+        assert!(cur_item.is_some());
+        assert_eq!(cur_item.unwrap().line, "bye".to_string());
+    }
+
+    #[tokio::test]
+    async fn history_must_include_lines_of_reopened_store() {
+        let path = store_path();
+
+        let channel = LogChannel::new("Test".into())
+            .with_store(LogStore::open(path.clone()).expect("open"));
+        channel.send("hello".into());
+        channel.send("aurae".into());
+        // Waits for the lines to be written
+        let _ = channel.history(None, None).await.expect("history");
+        drop(channel);
+
+        let channel = LogChannel::new("Test".into())
+            .with_store(LogStore::open(path).expect("reopen"));
+        channel.send("bye".into());
+
+        let (history, _) = channel.history(None, None).await.expect("history");
+        let lines: Vec<_> = history.iter().map(|x| x.line.as_str()).collect();
+        assert_eq!(lines, ["hello", "aurae", "bye"]);
+        let sequences: Vec<_> = history.iter().map(|x| x.sequence).collect();
+        assert_eq!(sequences, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn follow_must_read_back_lines_missed_by_slow_reader() {
+        let channel = LogChannel::new("Test".into()).with_store(store());
+        let mut rx = channel.follow();
+
+        let count = CAPACITY + 10;
+        for i in 0..count {
+            channel.send(i.to_string());
+        }
+
+        for i in 0..count {
+            let item = rx.recv().await.expect("log item");
+            assert_eq!(item.line, i.to_string());
+            assert_eq!(item.sequence, i as u64 + 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn follow_must_report_lines_dropped_without_store() {
+        let channel = LogChannel::new("Test".into());
+        let mut rx = channel.follow();
+
+        for i in 0..CAPACITY + 10 {
+            channel.send(i.to_string());
+        }
+
+        let item = rx.recv().await.expect("log item");
+        assert_eq!(item.line, "auraed: 10 log lines were dropped");
+        assert_eq!(item.severity(), LogSeverity::Warn);
+        assert_eq!(item.sequence, 10);
+
+        let item = rx.recv().await.expect("log item");
+        assert_eq!(item.line, "10");
+        assert_eq!(item.sequence, 11);
+    }
+
+    #[tokio::test]
+    async fn send_must_parse_json_lines() {
+        let channel = LogChannel::new("Test".into())
+            .with_executable("exe".into(), LogChannelType::Stdout);
+        let mut rx = channel.subscribe();
+
+        channel.send("hello".into());
+        channel.send(r#"{"level":"warn","msg":"aurae","count":3}"#.into());
+
+        let item = rx.recv().await.expect("log item");
+        assert_eq!(item.executable_name, "exe");
+        assert_eq!(item.stream(), LogChannelType::Stdout);
+        assert_eq!(item.severity(), LogSeverity::Info);
+        assert!(item.timestamp.is_some());
+        assert!(item.fields.is_empty());
+
+        let item = rx.recv().await.expect("log item");
+        assert_eq!(item.severity(), LogSeverity::Warn);
+        assert_eq!(item.fields["msg"], "aurae");
+        assert_eq!(item.fields["count"], "3");
+    }
+}
@@ -0,0 +1,340 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use pbjson_types::Timestamp;
+use proto::observe::LogItem;
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    ops::RangeBounds,
+    path::{Path, PathBuf},
+};
+
+/// Size in bytes after which the active log file is rotated.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// Number of log files (active file included) kept on disk.
+pub const DEFAULT_MAX_FILES: usize = 5;
+
+/// On-disk history of a single log channel.
+///
+/// Items are appended as JSON lines to the active file. Once the active file
+/// would grow beyond `max_file_size` it is rotated to `<path>.1`, shifting
+/// older files up by one and dropping anything beyond `max_files`, so the
+/// history is capped at roughly `max_file_size * max_files` bytes.
+#[derive(Debug)]
+pub struct LogStore {
+    path: PathBuf,
+    max_file_size: u64,
+    max_files: usize,
+    file: File,
+    size: u64,
+}
+
+impl LogStore {
+    /// Opens (or creates) the store at `path` with the default limits.
+    /// Existing history at `path` is kept and appended to.
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        Self::with_limits(path, DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES)
+    }
+
+    pub fn with_limits<P: Into<PathBuf>>(
+        path: P,
+        max_file_size: u64,
+        max_files: usize,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = Self::open_active(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_file_size,
+            max_files: max_files.max(1),
+            file,
+            size,
+        })
+    }
+
+    /// Creates the store at `path` with the default limits.
+    /// Existing history at `path` is removed.
+    pub fn create<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        let mut store = Self::open(path)?;
+        store.clear()?;
+        Ok(store)
+    }
+
+    /// Removes the stored history.
+    pub fn clear(&mut self) -> io::Result<()> {
+        for path in self.paths().iter().skip(1) {
+            ignore_not_found(fs::remove_file(path))?;
+        }
+
+        self.file.set_len(0)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// Appends an item to the store, rotating the active file if needed.
+    pub fn append(&mut self, item: &LogItem) -> io::Result<()> {
+        let mut line = serde_json::to_vec(item)?;
+        line.push(b'\n');
+
+        let len = line.len() as u64;
+        if self.size > 0 && self.size + len > self.max_file_size {
+            self.rotate()?;
+        }
+
+        self.file.write_all(&line)?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// Reads the stored history, oldest first.
+    ///
+    /// Only items with a sequence number in `sequences`, logged at or after `since`, are returned
+    /// and, when `tail` is set, only the last `tail` of those.
+    /// Lines that cannot be parsed (e.g., a partial write) are skipped.
+    pub fn read(
+        &self,
+        sequences: impl RangeBounds<u64>,
+        since: Option<&Timestamp>,
+        tail: Option<usize>,
+    ) -> io::Result<Vec<LogItem>> {
+        let since = since.map(|x| (x.seconds, x.nanos));
+        let mut items = VecDeque::new();
+
+        for path in self.paths().into_iter().rev() {
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+
+            for line in BufReader::new(file).lines() {
+                let Ok(item) = serde_json::from_str::<LogItem>(&line?) else {
+                    continue;
+                };
+
+                if !sequences.contains(&item.sequence) {
+                    continue;
+                }
+
+                let timestamp = item
+                    .timestamp
+                    .as_ref()
+                    .map_or((0, 0), |x| (x.seconds, x.nanos));
+                if since.is_some_and(|since| timestamp < since) {
+                    continue;
+                }
+
+                items.push_back(item);
+                if tail.is_some_and(|tail| items.len() > tail) {
+                    let _ = items.pop_front();
+                }
+            }
+        }
+
+        Ok(items.into())
+    }
+
+    /// Returns the sequence number of the last stored item, or 0 if the store is empty.
+    pub fn last_sequence(&self) -> io::Result<u64> {
+        let items = self.read(.., None, Some(1))?;
+        Ok(items.last().map_or(0, |x| x.sequence))
+    }
+
+    /// All file paths of the store, newest (the active file) first.
+    fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.path.clone()];
+        paths.extend((1..self.max_files).map(|i| self.rotated_path(i)));
+        paths
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        let paths = self.paths();
+
+        // The oldest file falls off the end
+        ignore_not_found(fs::remove_file(&paths[paths.len() - 1]))?;
+
+        for i in (1..paths.len()).rev() {
+            ignore_not_found(fs::rename(&paths[i - 1], &paths[i]))?;
+        }
+
+        self.file = Self::open_active(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+
+    fn open_active(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+}
+
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(max_file_size: u64, max_files: usize) -> LogStore {
+        let path = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()))
+            .join("stdout.log");
+        LogStore::with_limits(path, max_file_size, max_files)
+            .expect("failed to open log store")
+    }
+
+    fn item(line: &str, seconds: i64) -> LogItem {
+        LogItem {
+            channel: "test".into(),
+            line: line.into(),
+            timestamp: Some(Timestamp { seconds, nanos: 0 }),
+            ..Default::default()
+        }
+    }
+
+    fn lines(items: Vec<LogItem>) -> Vec<String> {
+        items.into_iter().map(|x| x.line).collect()
+    }
+
+    #[test]
+    fn read_must_return_appended_items_in_order() {
+        let mut store = store(DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES);
+        for (i, line) in ["hello", "aurae", "bye"].into_iter().enumerate() {
+            store.append(&item(line, i as i64)).unwrap();
+        }
+
+        let items = store.read(.., None, None).unwrap();
+        assert_eq!(lines(items), ["hello", "aurae", "bye"]);
+    }
+
+    #[test]
+    fn read_must_respect_since_and_tail() {
+        let mut store = store(DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES);
+        for i in 0..10 {
+            let item =
+                LogItem { sequence: i as u64 + 1, ..item(&i.to_string(), i) };
+            store.append(&item).unwrap();
+        }
+
+        let since = Timestamp { seconds: 5, nanos: 0 };
+        let items = store.read(.., Some(&since), None).unwrap();
+        assert_eq!(lines(items), ["5", "6", "7", "8", "9"]);
+
+        let items = store.read(.., None, Some(3)).unwrap();
+        assert_eq!(lines(items), ["7", "8", "9"]);
+
+        let since = Timestamp { seconds: 8, nanos: 0 };
+        let items = store.read(.., Some(&since), Some(3)).unwrap();
+        assert_eq!(lines(items), ["8", "9"]);
+
+        // Sequence numbers are one more than the line
+        let items = store.read(3..=5, None, None).unwrap();
+        assert_eq!(lines(items), ["2", "3", "4"]);
+    }
+
+    #[test]
+    fn read_must_compare_since_with_nanosecond_precision() {
+        let mut store = store(DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES);
+        for (i, line) in ["hello", "aurae", "bye"].into_iter().enumerate() {
+            let timestamp = Timestamp { seconds: 1, nanos: i as i32 * 100 };
+            store
+                .append(&LogItem {
+                    timestamp: Some(timestamp),
+                    ..item(line, 0)
+                })
+                .unwrap();
+        }
+
+        // Resuming from the last item received replays only that item
+        let since = Timestamp { seconds: 1, nanos: 100 };
+        let items = store.read(.., Some(&since), None).unwrap();
+        assert_eq!(lines(items), ["aurae", "bye"]);
+    }
+
+    #[test]
+    fn append_must_rotate_and_cap_history() {
+        let line_len = serde_json::to_vec(&item("0", 0)).unwrap().len() as u64;
+        // Two lines per file, three files
+        let mut store = store(2 * (line_len + 1), 3);
+        for i in 0..10 {
+            store.append(&item(&i.to_string(), 0)).unwrap();
+        }
+
+        assert!(store.rotated_path(2).exists());
+        assert!(!store.rotated_path(3).exists());
+
+        let items = store.read(.., None, None).unwrap();
+        assert_eq!(lines(items), ["4", "5", "6", "7", "8", "9"]);
+    }
+
+    #[test]
+    fn open_must_keep_existing_history() {
+        let mut store = store(DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES);
+        store.append(&item("before", 0)).unwrap();
+
+        let mut reopened = LogStore::open(store.path.clone()).unwrap();
+        reopened.append(&item("after", 1)).unwrap();
+
+        let items = reopened.read(.., None, None).unwrap();
+        assert_eq!(lines(items), ["before", "after"]);
+    }
+
+    #[test]
+    fn last_sequence_must_return_sequence_of_last_item() {
+        let mut store = store(DEFAULT_MAX_FILE_SIZE, DEFAULT_MAX_FILES);
+        assert_eq!(store.last_sequence().unwrap(), 0);
+
+        for i in 1..=3 {
+            let item = LogItem { sequence: i, ..item(&i.to_string(), 0) };
+            store.append(&item).unwrap();
+        }
+        assert_eq!(store.last_sequence().unwrap(), 3);
+    }
+
+    #[test]
+    fn create_must_remove_existing_history() {
+        let line_len = serde_json::to_vec(&item("0", 0)).unwrap().len() as u64;
+        let mut store = store(line_len + 1, DEFAULT_MAX_FILES);
+        for i in 0..3 {
+            store.append(&item(&i.to_string(), 0)).unwrap();
+        }
+        assert!(store.rotated_path(1).exists());
+
+        let mut created = LogStore::create(store.path.clone()).unwrap();
+        assert!(!created.rotated_path(1).exists());
+        assert!(created.read(.., None, None).unwrap().is_empty());
+
+        created.append(&item("after", 1)).unwrap();
+        let items = created.read(.., None, None).unwrap();
+        assert_eq!(lines(items), ["after"]);
+    }
+}
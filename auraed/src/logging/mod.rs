@@ -16,20 +16,27 @@
 //! Internal logging system for Auraed and all spawned Executables, Containers
 //! and Instances.
 
+use pbjson_types::Timestamp;
 use std::time::SystemTime;
 
 /// Abstraction Layer for one log generating entity
 /// LogChannel provides channels between Log producers and log consumers
 pub mod log_channel;
 
+/// Rotated, size capped on-disk history for a log channel
+pub mod log_store;
+
 /// Implements Log trait. Used to add grpc API to log targets for rust internal logging
 pub mod stream_logger;
 
-/// Get UNIX timestamp in seconds for logging
-pub fn get_timestamp_sec() -> i64 {
+/// Get the current time with nanosecond precision for logging
+pub fn get_timestamp() -> Timestamp {
     let unix_ts = SystemTime::now()
         .duration_since(SystemTime::UNIX_EPOCH)
         .expect("System Clock went backwards");
 
-    unix_ts.as_secs() as i64
+    Timestamp {
+        seconds: unix_ts.as_secs() as i64,
+        nanos: unix_ts.subsec_nanos() as i32,
+    }
 }
\ No newline at end of file
@@ -13,8 +13,9 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
-use log::Log;
-use proto::observe::LogItem;
+use super::get_timestamp;
+use log::{Level, Log};
+use proto::observe::{LogItem, LogSeverity};
 use tokio::sync::broadcast::Sender;
 
 /// Sends log messages generated in rust code to the logging channel
@@ -48,7 +49,16 @@ impl Log for StreamLogger {
                 record.target(),
                 record.args()
             ),
-            timestamp: 0,
+            timestamp: Some(get_timestamp()),
+            severity: match record.level() {
+                Level::Trace => LogSeverity::Trace,
+                Level::Debug => LogSeverity::Debug,
+                Level::Info => LogSeverity::Info,
+                Level::Warn => LogSeverity::Warn,
+                Level::Error => LogSeverity::Error,
+            }
+            .into(),
+            ..Default::default()
         });
     }
 
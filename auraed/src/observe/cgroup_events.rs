@@ -0,0 +1,280 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+//! Events of a cgroup, read from `cgroup.events` and `memory.events`.
+//!
+//! Docs: https://docs.kernel.org/admin-guide/cgroup-v2.html
+//!
+//! The kernel generates a file modified event when a value in these files changes,
+//! which is watched with inotify.
+
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify};
+use std::{
+    collections::HashMap,
+    fs, io,
+    os::fd::{AsFd, AsRawFd, RawFd},
+    path::{Path, PathBuf},
+};
+use tokio::io::unix::AsyncFd;
+
+/// A change of the events of a cgroup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CgroupEvent {
+    /// The cgroup, or one of its descendants, now has processes.
+    Populated,
+    /// The cgroup, and all of its descendants, no longer have processes.
+    Unpopulated,
+    /// Memory usage was throttled because it exceeded `memory.high`.
+    /// The count is the number of times this happened.
+    MemoryHigh { count: u64 },
+    /// Memory usage was about to exceed `memory.max`.
+    MemoryMax { count: u64 },
+    /// Memory usage reached `memory.max` and reclaim failed.
+    Oom { count: u64 },
+    /// A process of the cgroup was killed by the OOM killer.
+    OomKill { count: u64 },
+}
+
+/// The values of `cgroup.events` and `memory.events`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct CgroupEvents {
+    populated: bool,
+    high: u64,
+    max: u64,
+    oom: u64,
+    oom_kill: u64,
+}
+
+impl CgroupEvents {
+    fn read(path: &Path) -> io::Result<Self> {
+        let events =
+            parse_flat_keyed(&fs::read_to_string(path.join("cgroup.events"))?);
+
+        // memory.events only exists if the memory controller is enabled
+        let memory_events = match fs::read_to_string(path.join("memory.events"))
+        {
+            Ok(content) => parse_flat_keyed(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        let memory = |key: &str| memory_events.get(key).copied().unwrap_or(0);
+
+        Ok(Self {
+            populated: events.get("populated").is_some_and(|x| *x == 1),
+            high: memory("high"),
+            max: memory("max"),
+            oom: memory("oom"),
+            oom_kill: memory("oom_kill"),
+        })
+    }
+
+    /// Returns the events that happened between `self` and `next`.
+    fn changes(&self, next: &Self) -> Vec<CgroupEvent> {
+        let mut changes = vec![];
+
+        if next.populated != self.populated {
+            changes.push(if next.populated {
+                CgroupEvent::Populated
+            } else {
+                CgroupEvent::Unpopulated
+            });
+        }
+
+        if next.high > self.high {
+            changes.push(CgroupEvent::MemoryHigh { count: next.high });
+        }
+
+        if next.max > self.max {
+            changes.push(CgroupEvent::MemoryMax { count: next.max });
+        }
+
+        if next.oom > self.oom {
+            changes.push(CgroupEvent::Oom { count: next.oom });
+        }
+
+        if next.oom_kill > self.oom_kill {
+            changes.push(CgroupEvent::OomKill { count: next.oom_kill });
+        }
+
+        changes
+    }
+}
+
+/// Watches the events of the cgroup at a path, including those of its descendants.
+#[derive(Debug)]
+pub(crate) struct CgroupEventsWatcher {
+    path: PathBuf,
+    inotify: AsyncFd<InotifyFd>,
+    last: CgroupEvents,
+}
+
+impl CgroupEventsWatcher {
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let inotify =
+            Inotify::init(InitFlags::IN_NONBLOCK | InitFlags::IN_CLOEXEC)?;
+
+        let _ = inotify
+            .add_watch(&path.join("cgroup.events"), AddWatchFlags::IN_MODIFY)?;
+
+        let memory_events = path.join("memory.events");
+        if memory_events.exists() {
+            let _ =
+                inotify.add_watch(&memory_events, AddWatchFlags::IN_MODIFY)?;
+        }
+
+        let last = CgroupEvents::read(&path)?;
+
+        Ok(Self { path, inotify: AsyncFd::new(InotifyFd(inotify))?, last })
+    }
+
+    /// Waits for the next events of the cgroup.
+    /// Returns [None] once the cgroup has been removed.
+    pub async fn next(&mut self) -> io::Result<Option<Vec<CgroupEvent>>> {
+        loop {
+            let mut guard = self.inotify.readable().await?;
+            let Ok(events) = guard.try_io(|inotify| {
+                inotify.get_ref().0.read_events().map_err(io::Error::from)
+            }) else {
+                // the inotify instance is not readable after all
+                continue;
+            };
+
+            // watches are removed with the files of the cgroup
+            if events?
+                .iter()
+                .any(|event| event.mask.contains(AddWatchFlags::IN_IGNORED))
+            {
+                return Ok(None);
+            }
+
+            let next = match CgroupEvents::read(&self.path) {
+                Ok(next) => next,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Ok(None)
+                }
+                Err(e) => return Err(e),
+            };
+
+            let changes = self.last.changes(&next);
+            self.last = next;
+
+            if !changes.is_empty() {
+                return Ok(Some(changes));
+            }
+        }
+    }
+}
+
+/// [AsyncFd] requires [AsRawFd], which [Inotify] does not implement.
+#[derive(Debug)]
+struct InotifyFd(Inotify);
+
+impl AsRawFd for InotifyFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_fd().as_raw_fd()
+    }
+}
+
+fn parse_flat_keyed(content: &str) -> HashMap<String, u64> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cgroup_dir() -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("ae-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("cgroup.events"), "populated 0\nfrozen 0\n")
+            .unwrap();
+        fs::write(
+            path.join("memory.events"),
+            "low 0\nhigh 0\nmax 0\noom 0\noom_kill 0\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_without_memory_controller() {
+        let path = cgroup_dir();
+        fs::remove_file(path.join("memory.events")).unwrap();
+        fs::write(path.join("cgroup.events"), "populated 1\nfrozen 0\n")
+            .unwrap();
+
+        let events = CgroupEvents::read(&path).unwrap();
+        assert_eq!(
+            events,
+            CgroupEvents { populated: true, ..CgroupEvents::default() }
+        );
+    }
+
+    #[test]
+    fn test_changes() {
+        let last =
+            CgroupEvents { populated: true, max: 1, ..Default::default() };
+        let next = CgroupEvents {
+            populated: false,
+            max: 3,
+            oom: 1,
+            oom_kill: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            last.changes(&next),
+            vec![
+                CgroupEvent::Unpopulated,
+                CgroupEvent::MemoryMax { count: 3 },
+                CgroupEvent::Oom { count: 1 },
+                CgroupEvent::OomKill { count: 1 },
+            ]
+        );
+        assert!(next.changes(&next).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_must_emit_changed_events() {
+        let path = cgroup_dir();
+        let mut watcher = CgroupEventsWatcher::new(path.clone()).unwrap();
+
+        fs::write(
+            path.join("memory.events"),
+            "low 0\nhigh 0\nmax 2\noom 1\noom_kill 1\n",
+        )
+        .unwrap();
+
+        let events = watcher.next().await.unwrap().expect("events");
+        assert_eq!(
+            events,
+            vec![
+                CgroupEvent::MemoryMax { count: 2 },
+                CgroupEvent::Oom { count: 1 },
+                CgroupEvent::OomKill { count: 1 },
+            ]
+        );
+
+        fs::remove_dir_all(&path).unwrap();
+        assert_eq!(watcher.next().await.unwrap(), None);
+    }
+}
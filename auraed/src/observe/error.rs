@@ -13,7 +13,9 @@
  * SPDX-License-Identifier: Apache-2.0                                        *
 \* -------------------------------------------------------------------------- */
 
+use client::ClientError;
 use proto::observe::LogChannelType;
+use std::io;
 use thiserror::Error;
 use tonic::Status;
 use tracing::error;
@@ -26,8 +28,23 @@ pub enum ObserveServiceError {
     NoChannelsForPid { pid: i32 },
     #[error("Failed to find channel type {channel_type:?} for {pid}")]
     ChannelNotRegistered { pid: i32, channel_type: LogChannelType },
+    #[error("Failed to find any registered channels for executable '{executable_name}'")]
+    NoChannelsForExecutable { executable_name: String },
+    #[error("Failed to find channel type {channel_type:?} for executable '{executable_name}'")]
+    ExecutableChannelNotRegistered {
+        executable_name: String,
+        channel_type: LogChannelType,
+    },
+    #[error("Failed to find cell '{cell_name}'")]
+    CellNotFound { cell_name: String },
+    #[error("Failed to connect to the auraed in cell '{cell_name}': {source}")]
+    FailedToConnectToCell { cell_name: String, source: ClientError },
     #[error("{channel_type} is not a valid LogChannelType")]
     InvalidLogChannelType { channel_type: i32 },
+    #[error("Failed to read log history of channel '{channel}': {source}")]
+    FailedToReadLogHistory { channel: String, source: io::Error },
+    #[error("Failed to watch the events of cell '{cell_name}': {source}")]
+    FailedToWatchCell { cell_name: String, source: io::Error },
 }
 
 impl From<ObserveServiceError> for Status {
@@ -39,12 +56,22 @@ impl From<ObserveServiceError> for Status {
                 Status::internal(msg)
             }
             ObserveServiceError::NoChannelsForPid { .. }
-            | ObserveServiceError::ChannelNotRegistered { .. } => {
+            | ObserveServiceError::ChannelNotRegistered { .. }
+            | ObserveServiceError::NoChannelsForExecutable { .. }
+            | ObserveServiceError::ExecutableChannelNotRegistered { .. }
+            | ObserveServiceError::CellNotFound { .. } => {
                 Status::not_found(msg)
             }
+            ObserveServiceError::FailedToConnectToCell { .. } => {
+                Status::unavailable(msg)
+            }
             ObserveServiceError::InvalidLogChannelType { .. } => {
                 Status::invalid_argument(msg)
             }
+            ObserveServiceError::FailedToReadLogHistory { .. }
+            | ObserveServiceError::FailedToWatchCell { .. } => {
+                Status::internal(msg)
+            }
         }
     }
 }
\ No newline at end of file
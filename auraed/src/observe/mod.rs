@@ -17,6 +17,7 @@ pub(crate) use error::ObserveServiceError;
 pub(crate) use observe_service::ObserveService;
 
 mod cgroup_cache;
+mod cgroup_events;
 mod error;
 mod observe_service;
 mod observed_event_stream;
@@ -17,28 +17,35 @@
 #![allow(dead_code)]
 
 use super::cgroup_cache;
+use super::cgroup_events::{CgroupEvent, CgroupEventsWatcher};
 use super::error::ObserveServiceError;
 use super::observed_event_stream::ObservedEventStream;
 use super::proc_cache::{ProcCache, ProcfsProcessInfo};
 use crate::ebpf::tracepoint::PerfEventBroadcast;
-use crate::logging::log_channel::LogChannel;
+use crate::logging::{get_timestamp, log_channel::LogChannel};
 use aurae_ebpf_shared::{ForkedProcess, ProcessExit, Signal};
 use cgroup_cache::CgroupCache;
+use client::{
+    observe::observe_service::ObserveServiceClient, AuraeSocket, Client,
+};
+use libcgroups::common::DEFAULT_CGROUP_ROOT;
 use proto::observe::{
-    observe_service_server, GetAuraeDaemonLogStreamRequest,
-    GetAuraeDaemonLogStreamResponse, GetPosixSignalsStreamRequest,
-    GetPosixSignalsStreamResponse, GetSubProcessStreamRequest,
-    GetSubProcessStreamResponse, LogChannelType, LogItem,
-    Signal as PosixSignal, WorkloadType,
+    observe_service_server, CellEvent, CellEventType,
+    GetAuraeDaemonLogStreamRequest, GetAuraeDaemonLogStreamResponse,
+    GetCellEventsStreamRequest, GetCellEventsStreamResponse,
+    GetPosixSignalsStreamRequest, GetPosixSignalsStreamResponse,
+    GetSubProcessStreamRequest, GetSubProcessStreamResponse, LogChannelType,
+    LogItem, Signal as PosixSignal, WorkloadType,
 };
 use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 use std::{ffi::OsString, sync::Arc};
 use tokio::sync::mpsc;
 use tokio::sync::{broadcast::Receiver, Mutex};
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
-use tracing::info;
+use tracing::{debug, info};
 
 #[derive(Debug, Clone)]
 pub struct ObserveService {
@@ -48,6 +55,10 @@ pub struct ObserveService {
     posix_signals: Option<PerfEventBroadcast<Signal>>,
     sub_process_consumer_list:
         Arc<Mutex<HashMap<i32, HashMap<LogChannelType, LogChannel>>>>,
+    executable_consumer_list:
+        Arc<Mutex<HashMap<String, HashMap<LogChannelType, LogChannel>>>>,
+    /// Sockets of the nested auraeds, keyed by the name of their cell.
+    cell_sockets: Arc<Mutex<HashMap<String, AuraeSocket>>>,
 }
 
 type PerfEvents = (
@@ -78,6 +89,8 @@ impl ObserveService {
             proc_cache,
             posix_signals: perf_events.2,
             sub_process_consumer_list: Arc::new(Mutex::new(HashMap::new())),
+            executable_consumer_list: Arc::new(Mutex::new(HashMap::new())),
+            cell_sockets: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -130,6 +143,106 @@ impl ObserveService {
         Ok(())
     }
 
+    /// Makes a log channel of an executable available by the executable's name.
+    /// Unlike PIDs, names are reused, so a previously registered channel is replaced.
+    pub async fn register_executable_channel(
+        &self,
+        executable_name: String,
+        channel_type: LogChannelType,
+        channel: LogChannel,
+    ) {
+        info!("Registering channel for executable {executable_name} {channel_type:?}");
+        let mut consumer_list = self.executable_consumer_list.lock().await;
+        let _ = consumer_list
+            .entry(executable_name)
+            .or_default()
+            .insert(channel_type, channel);
+    }
+
+    pub async fn unregister_executable_channels(&self, executable_name: &str) {
+        info!("Unregistering channels for executable {executable_name}");
+        let mut consumer_list = self.executable_consumer_list.lock().await;
+        let _ = consumer_list.remove(executable_name);
+    }
+
+    /// Registers the socket of the auraed nested in a cell, so that log requests for the cell
+    /// can be proxied to it.
+    pub async fn register_cell_socket(
+        &self,
+        cell_name: String,
+        socket: AuraeSocket,
+    ) {
+        info!("Registering socket for cell {cell_name}");
+        let mut cell_sockets = self.cell_sockets.lock().await;
+        let _ = cell_sockets.insert(cell_name, socket);
+    }
+
+    /// Removes the socket of a cell, and of all cells nested in it.
+    pub async fn unregister_cell_socket(&self, cell_name: &str) {
+        info!("Unregistering socket for cell {cell_name}");
+        let prefix = format!("{cell_name}/");
+        let mut cell_sockets = self.cell_sockets.lock().await;
+        cell_sockets
+            .retain(|name, _| name != cell_name && !name.starts_with(&prefix));
+    }
+
+    async fn get_sub_process_stream_in_cell(
+        &self,
+        cell_name: String,
+        request: GetSubProcessStreamRequest,
+    ) -> Result<
+        Response<ReceiverStream<Result<GetSubProcessStreamResponse, Status>>>,
+        Status,
+    > {
+        let client_socket = self
+            .cell_sockets
+            .lock()
+            .await
+            .get(&cell_name)
+            .cloned()
+            .ok_or_else(|| ObserveServiceError::CellNotFound {
+                cell_name: cell_name.clone(),
+            })?;
+
+        let client = Client::new_no_tls(client_socket).await.map_err(|e| {
+            ObserveServiceError::FailedToConnectToCell {
+                cell_name: cell_name.clone(),
+                source: e,
+            }
+        })?;
+
+        let mut stream =
+            client.get_sub_process_stream(request).await?.into_inner();
+
+        let (tx, rx) =
+            mpsc::channel::<Result<GetSubProcessStreamResponse, Status>>(4);
+
+        let _ignored = tokio::spawn(async move {
+            loop {
+                match stream.message().await {
+                    Ok(Some(mut resp)) => {
+                        // Executables do not know which cell they run in
+                        if let Some(item) = resp.item.as_mut() {
+                            item.cell_name.clone_from(&cell_name);
+                        }
+                        if tx.send(Ok(resp)).await.is_err() {
+                            // receiver is gone
+                            break;
+                        }
+                    }
+                    // the nested stream has ended
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
     fn get_aurae_daemon_log_stream(&self) -> Receiver<LogItem> {
         self.aurae_logger.subscribe()
     }
@@ -148,6 +261,82 @@ impl ObserveService {
 
         ReceiverStream::new(events)
     }
+
+    async fn get_cell_events_stream(
+        &self,
+        cell_name: String,
+    ) -> Result<
+        ReceiverStream<Result<GetCellEventsStreamResponse, Status>>,
+        ObserveServiceError,
+    > {
+        // Cells are registered when they are allocated
+        if !self.cell_sockets.lock().await.contains_key(&cell_name) {
+            return Err(ObserveServiceError::CellNotFound { cell_name });
+        }
+
+        let mut watcher = CgroupEventsWatcher::new(
+            Path::new(DEFAULT_CGROUP_ROOT).join(&cell_name),
+        )
+        .map_err(|e| ObserveServiceError::FailedToWatchCell {
+            cell_name: cell_name.clone(),
+            source: e,
+        })?;
+
+        let (tx, rx) =
+            mpsc::channel::<Result<GetCellEventsStreamResponse, Status>>(4);
+
+        let _ignored = tokio::spawn(async move {
+            loop {
+                let events = match watcher.next().await {
+                    Ok(Some(events)) => events,
+                    // the cell has been freed
+                    Ok(None) => break,
+                    Err(e) => {
+                        let e = ObserveServiceError::FailedToWatchCell {
+                            cell_name: cell_name.clone(),
+                            source: e,
+                        };
+                        let _ = tx.send(Err(e.into())).await;
+                        break;
+                    }
+                };
+
+                for event in events {
+                    let resp =
+                        map_get_cell_events_stream_response(&cell_name, event);
+                    if tx.send(Ok(resp)).await.is_err() {
+                        // receiver is gone
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(ReceiverStream::new(rx))
+    }
+}
+
+fn map_get_cell_events_stream_response(
+    cell_name: &str,
+    event: CgroupEvent,
+) -> GetCellEventsStreamResponse {
+    let (event_type, count) = match event {
+        CgroupEvent::Populated => (CellEventType::Populated, 0),
+        CgroupEvent::Unpopulated => (CellEventType::Unpopulated, 0),
+        CgroupEvent::MemoryHigh { count } => (CellEventType::MemoryHigh, count),
+        CgroupEvent::MemoryMax { count } => (CellEventType::MemoryMax, count),
+        CgroupEvent::Oom { count } => (CellEventType::Oom, count),
+        CgroupEvent::OomKill { count } => (CellEventType::OomKill, count),
+    };
+
+    GetCellEventsStreamResponse {
+        event: Some(CellEvent {
+            cell_name: cell_name.to_string(),
+            event_type: event_type.into(),
+            count,
+            timestamp: Some(get_timestamp()),
+        }),
+    }
 }
 
 fn map_get_posix_signals_stream_response(
@@ -198,16 +387,60 @@ impl observe_service_server::ObserveService for ObserveService {
         &self,
         request: Request<GetSubProcessStreamRequest>,
     ) -> Result<Response<Self::GetSubProcessStreamStream>, Status> {
-        let channel = LogChannelType::try_from(request.get_ref().channel_type)
-            .map_err(|_| ObserveServiceError::InvalidLogChannelType {
-                channel_type: request.get_ref().channel_type,
-            })?;
-        let pid: i32 = request.get_ref().process_id;
+        let GetSubProcessStreamRequest {
+            process_id: pid,
+            channel_type,
+            since,
+            tail,
+            follow,
+            cell_name,
+            executable_name,
+        } = request.into_inner();
+
+        // The executable runs in a nested auraed, which holds its logs
+        if let Some(cell_name) = cell_name {
+            return self
+                .get_sub_process_stream_in_cell(
+                    cell_name,
+                    GetSubProcessStreamRequest {
+                        process_id: pid,
+                        channel_type,
+                        since,
+                        tail,
+                        follow,
+                        cell_name: None,
+                        executable_name,
+                    },
+                )
+                .await;
+        }
+
+        let channel = LogChannelType::try_from(channel_type).map_err(|_| {
+            ObserveServiceError::InvalidLogChannelType { channel_type }
+        })?;
 
         println!("Requested Channel {channel:?}");
-        println!("Requested Process ID {pid}");
 
-        let mut log_consumer = {
+        let log_channel = if let Some(executable_name) = executable_name {
+            debug!("Requested Executable {executable_name}");
+
+            let consumer_list = self.executable_consumer_list.lock().await;
+            consumer_list
+                .get(&executable_name)
+                .ok_or_else(|| ObserveServiceError::NoChannelsForExecutable {
+                    executable_name: executable_name.clone(),
+                })?
+                .get(&channel)
+                .ok_or_else(|| {
+                    ObserveServiceError::ExecutableChannelNotRegistered {
+                        executable_name: executable_name.clone(),
+                        channel_type: channel,
+                    }
+                })?
+                .clone()
+        } else {
+            println!("Requested Process ID {pid}");
+
             let mut consumer_list = self.sub_process_consumer_list.lock().await;
             consumer_list
                 .get_mut(&pid)
@@ -218,8 +451,20 @@ impl observe_service_server::ObserveService for ObserveService {
                     channel_type: channel,
                 })?
                 .clone()
-        }
-        .subscribe();
+        };
+
+        // Without `since` or `tail`, only new lines are streamed
+        let (history, mut log_consumer) = if since.is_none() && tail.is_none() {
+            (vec![], log_channel.follow())
+        } else {
+            log_channel.history(since, tail.map(|x| x as usize)).await.map_err(
+                |e| ObserveServiceError::FailedToReadLogHistory {
+                    channel: log_channel.name.clone(),
+                    source: e,
+                },
+            )?
+        };
+        let follow = follow.unwrap_or(true);
 
         let (tx, rx) =
             mpsc::channel::<Result<GetSubProcessStreamResponse, Status>>(4);
@@ -227,10 +472,20 @@ impl observe_service_server::ObserveService for ObserveService {
         // TODO: error handling. Warning: recursively logging if error message is also send to this grpc api endpoint
         //  .. thus disabled logging here.
         let _ignored = tokio::spawn(async move {
-            // Log consumer will error if:
-            //  the producer is closed (no more logs)
-            //  the receiver is lagging
-            while let Ok(log_item) = log_consumer.recv().await {
+            for log_item in history {
+                let resp = GetSubProcessStreamResponse { item: Some(log_item) };
+                if tx.send(Ok(resp)).await.is_err() {
+                    // receiver is gone
+                    return;
+                }
+            }
+
+            if !follow {
+                return;
+            }
+
+            // Ends once the producer is closed (no more logs)
+            while let Some(log_item) = log_consumer.recv().await {
                 let resp = GetSubProcessStreamResponse { item: Some(log_item) };
                 if tx.send(Ok(resp)).await.is_err() {
                     // receiver is gone
@@ -263,12 +518,25 @@ impl observe_service_server::ObserveService for ObserveService {
             .await,
         ))
     }
+
+    type GetCellEventsStreamStream =
+        ReceiverStream<Result<GetCellEventsStreamResponse, Status>>;
+
+    async fn get_cell_events_stream(
+        &self,
+        request: Request<GetCellEventsStreamRequest>,
+    ) -> Result<Response<Self::GetCellEventsStreamStream>, Status> {
+        let GetCellEventsStreamRequest { cell_name } = request.into_inner();
+
+        Ok(Response::new(self.get_cell_events_stream(cell_name).await?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ObserveService;
+    use super::{ObserveService, ObserveServiceError};
     use crate::logging::log_channel::LogChannel;
+    use client::AuraeSocket;
     use proto::observe::LogChannelType;
     use std::sync::Arc;
 
@@ -373,4 +641,64 @@ mod tests {
 
         svc.sub_process_consumer_list.lock().await.clear();
     }
+
+    #[tokio::test]
+    async fn test_register_executable_channel_replaces_channel() {
+        let svc = ObserveService::new(
+            Arc::new(LogChannel::new(String::from("auraed"))),
+            (None, None, None),
+        );
+        svc.register_executable_channel(
+            String::from("exe"),
+            LogChannelType::Stdout,
+            LogChannel::new(String::from("foo")),
+        )
+        .await;
+        svc.register_executable_channel(
+            String::from("exe"),
+            LogChannelType::Stdout,
+            LogChannel::new(String::from("bar")),
+        )
+        .await;
+
+        {
+            let consumer_list = svc.executable_consumer_list.lock().await;
+            let channel = &consumer_list["exe"][&LogChannelType::Stdout];
+            assert_eq!(channel.name, "bar");
+        }
+
+        svc.unregister_executable_channels("exe").await;
+        assert!(svc.executable_consumer_list.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unregister_cell_socket_removes_nested_cells() {
+        let svc = ObserveService::new(
+            Arc::new(LogChannel::new(String::from("auraed"))),
+            (None, None, None),
+        );
+        for cell_name in ["a", "a/b", "a/b/c", "ab"] {
+            svc.register_cell_socket(
+                String::from(cell_name),
+                AuraeSocket::Path(format!("/tmp/{cell_name}.sock").into()),
+            )
+            .await;
+        }
+
+        svc.unregister_cell_socket("a").await;
+
+        let cell_sockets = svc.cell_sockets.lock().await;
+        assert_eq!(cell_sockets.keys().collect::<Vec<_>>(), vec!["ab"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_cell_events_stream_unknown_cell_error() {
+        let svc = ObserveService::new(
+            Arc::new(LogChannel::new(String::from("auraed"))),
+            (None, None, None),
+        );
+
+        let res = svc.get_cell_events_stream(String::from("ae-missing")).await;
+        assert!(matches!(res, Err(ObserveServiceError::CellNotFound { .. })));
+    }
 }
@@ -0,0 +1,64 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::CellServiceAllocateRequestBuilder;
+use proto::cells::CellServiceFreeRequest;
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_allocate_must_connect_network_isolated_cells() {
+    skip_if_not_root!("cell_allocate_must_connect_network_isolated_cells");
+    skip_if_seccomp!("cell_allocate_must_connect_network_isolated_cells");
+
+    let client = common::auraed_client().await;
+
+    let mut cells = vec![];
+    for _ in 0..2 {
+        let allocated = retry!(
+            client
+                .allocate(
+                    CellServiceAllocateRequestBuilder::new()
+                        .isolate_network()
+                        .build()
+                )
+                .await
+        )
+        .unwrap()
+        .into_inner();
+
+        // Each cell gets its own address from the default pool
+        assert_eq!(allocated.ip_addresses.len(), 1);
+        let address: ipnetwork::Ipv4Network =
+            allocated.ip_addresses[0].parse().expect("valid address");
+        assert_eq!(address.network(), "10.88.0.0".parse().unwrap());
+        assert_eq!(address.prefix(), 16);
+
+        cells.push((allocated.cell_name, address));
+    }
+    assert_ne!(cells[0].1, cells[1].1);
+
+    // Free the cells to release their addresses
+    for (cell_name, _) in cells {
+        let _ = retry!(
+            client
+                .free(CellServiceFreeRequest { cell_name: cell_name.clone() })
+                .await
+        )
+        .unwrap();
+    }
+}
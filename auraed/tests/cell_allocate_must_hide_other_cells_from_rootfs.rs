@@ -0,0 +1,121 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::CellServiceAllocateRequestBuilder;
+use proto::cells::{
+    CellServiceExecRequest, CellServiceExecResponse, CellServiceExecStart,
+    Mount, MountType,
+};
+use std::path::Path;
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_allocate_must_hide_other_cells_from_rootfs() {
+    skip_if_not_root!("cell_allocate_must_hide_other_cells_from_rootfs");
+    skip_if_seccomp!("cell_allocate_must_hide_other_cells_from_rootfs");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a sibling cell, whose nested auraed has a socket in the runtime directory
+    let sibling_cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Allocate a cell with an empty rootfs, and a read only view of the host binaries
+    let rootfs = std::env::temp_dir()
+        .join(format!("aurae-rootfs-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&rootfs).unwrap();
+
+    let mut request = CellServiceAllocateRequestBuilder::new();
+    let _ =
+        request.isolate_process().rootfs(rootfs.to_string_lossy().to_string());
+    for dir in ["/bin", "/sbin", "/lib", "/lib64", "/usr"] {
+        if Path::new(dir).exists() {
+            let _ = request.mount(Mount {
+                r#type: MountType::Bind as i32,
+                source: dir.into(),
+                target: dir.into(),
+                readonly: true,
+                size: None,
+            });
+        }
+    }
+
+    let cell_name = retry!(client.allocate(request.build()).await)
+        .unwrap()
+        .into_inner()
+        .cell_name;
+
+    // Only the socket directory of the cell itself is in the runtime directory
+    let entries = list(&client, &cell_name, "/var/run/aurae").await;
+    let sockets: Vec<_> =
+        entries.iter().filter(|x| x.starts_with("aurae")).collect();
+    assert_eq!(sockets.len(), 1, "{entries:?}");
+    assert!(sockets[0].starts_with("aurae-"), "{entries:?}");
+    assert!(!sockets[0].ends_with(".sock"), "{entries:?}");
+
+    // Only the logs of the cell itself are in the logs directory
+    let entries = list(&client, &cell_name, "/var/run/aurae/logs/cells").await;
+    assert_eq!(entries, vec![cell_name.clone()]);
+    assert!(!entries.contains(&sibling_cell_name));
+
+    // The secret key of the server is not in the rootfs
+    let entries = list(&client, &cell_name, "/etc/aurae/pki").await;
+    assert!(entries.is_empty(), "{entries:?}");
+}
+
+/// Lists the entries of `dir` from inside a cell, which are empty if `dir` does not exist.
+async fn list(
+    client: &client::Client,
+    cell_name: &str,
+    dir: &str,
+) -> Vec<String> {
+    let requests = vec![CellServiceExecRequest {
+        start: Some(CellServiceExecStart {
+            cell_name: Some(cell_name.to_string()),
+            args: vec![
+                "sh".into(),
+                "-c".into(),
+                format!("ls -A {dir} 2>/dev/null || true"),
+            ],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+
+    let mut responses =
+        retry!(client.exec(tokio_stream::iter(requests.clone())).await)
+            .unwrap()
+            .into_inner();
+
+    let mut stdout = vec![];
+    while let Some(CellServiceExecResponse { stdout: out, .. }) =
+        responses.message().await.expect("exec response")
+    {
+        stdout.extend_from_slice(&out);
+    }
+
+    String::from_utf8(stdout)
+        .expect("utf-8 stdout")
+        .lines()
+        .map(String::from)
+        .collect()
+}
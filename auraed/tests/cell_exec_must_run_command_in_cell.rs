@@ -0,0 +1,140 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::CellServiceAllocateRequestBuilder;
+use proto::cells::{
+    CellServiceExecRequest, CellServiceExecResponse, CellServiceExecStart,
+    TerminalSize,
+};
+use std::time::Duration;
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_exec_must_run_command_in_cell() {
+    skip_if_not_root!("cell_exec_must_run_command_in_cell");
+    skip_if_seccomp!("cell_exec_must_run_command_in_cell");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Run a command that reads its stdin, which is closed once the requests end
+    let requests = vec![
+        CellServiceExecRequest {
+            start: Some(CellServiceExecStart {
+                cell_name: Some(cell_name.clone()),
+                args: vec![
+                    "sh".into(),
+                    "-c".into(),
+                    "read line; echo out:$line; echo err >&2; exit 3".into(),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        CellServiceExecRequest {
+            stdin: b"hello\n".to_vec().into(),
+            ..Default::default()
+        },
+    ];
+
+    let responses =
+        retry!(client.exec(tokio_stream::iter(requests.clone())).await)
+            .unwrap()
+            .into_inner();
+
+    let (stdout, stderr, code) = collect(responses).await;
+    assert_eq!(stdout, "out:hello\n");
+    assert_eq!(stderr, "err\n");
+    assert_eq!(code, Some(3));
+
+    // Run a command that leaves its output open in a background job
+    let requests = vec![CellServiceExecRequest {
+        start: Some(CellServiceExecStart {
+            cell_name: Some(cell_name.clone()),
+            args: vec!["sh".into(), "-c".into(), "sleep 30 & echo out".into()],
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+
+    let responses =
+        retry!(client.exec(tokio_stream::iter(requests.clone())).await)
+            .unwrap()
+            .into_inner();
+
+    // The exit status is sent without waiting for the background job
+    let (stdout, _, code) =
+        tokio::time::timeout(Duration::from_secs(10), collect(responses))
+            .await
+            .expect("exit status before the background job exits");
+    assert_eq!(stdout, "out\n");
+    assert_eq!(code, Some(0));
+
+    // Run a command with a terminal of the requested size
+    let requests = vec![CellServiceExecRequest {
+        start: Some(CellServiceExecStart {
+            cell_name: Some(cell_name.clone()),
+            args: vec!["sh".into(), "-c".into(), "stty size".into()],
+            tty: true,
+            terminal_size: Some(TerminalSize { rows: 24, columns: 80 }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+
+    let responses =
+        retry!(client.exec(tokio_stream::iter(requests.clone())).await)
+            .unwrap()
+            .into_inner();
+
+    // The terminal combines the output of the command
+    let (stdout, stderr, code) = collect(responses).await;
+    assert_eq!(stdout.trim_end(), "24 80");
+    assert!(stderr.is_empty());
+    assert_eq!(code, Some(0));
+}
+
+/// Collects the stdout and stderr of a command, and its exit code.
+async fn collect(
+    mut responses: tonic::Streaming<CellServiceExecResponse>,
+) -> (String, String, Option<i32>) {
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    let mut code = None;
+
+    while let Some(res) = responses.message().await.expect("exec response") {
+        stdout.extend_from_slice(&res.stdout);
+        stderr.extend_from_slice(&res.stderr);
+        if let Some(exit_status) = res.exit_status {
+            code = exit_status.code;
+        }
+    }
+
+    (
+        String::from_utf8(stdout).expect("utf-8 stdout"),
+        String::from_utf8(stderr).expect("utf-8 stderr"),
+        code,
+    )
+}
@@ -0,0 +1,91 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{
+    CellServiceStopRequest, CellServiceWaitRequest, ExecutableState,
+    RestartMode, RestartPolicy,
+};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_executable_must_crash_loop_after_max_restarts() {
+    skip_if_not_root!("cell_executable_must_crash_loop_after_max_restarts");
+    skip_if_seccomp!("cell_executable_must_crash_loop_after_max_restarts");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable that always fails, and is restarted on failure
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let _ = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .executable_name(exe_name.clone())
+                    .command("exit 1".into())
+                    .restart_policy(RestartPolicy {
+                        mode: Some(RestartMode::OnFailure.into()),
+                        max_restarts: Some(2),
+                        initial_backoff_ms: Some(10),
+                        max_backoff_ms: Some(100),
+                    })
+                    .build(),
+            )
+            .await
+    )
+    .unwrap();
+
+    // Wait for the executable to give up restarting
+    let executable = retry!(
+        client
+            .wait(CellServiceWaitRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status");
+
+    assert_eq!(executable.state(), ExecutableState::CrashLoop);
+    assert_eq!(executable.restart_count, 2);
+    assert_eq!(executable.exit_status.and_then(|x| x.code), Some(1));
+
+    let _ = retry!(
+        client
+            .stop(CellServiceStopRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap();
+}
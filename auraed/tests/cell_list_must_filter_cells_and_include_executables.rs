@@ -0,0 +1,153 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{CellGraphNode, CellServiceListRequest};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_list_must_filter_cells_and_include_executables() {
+    skip_if_not_root!("cell_list_must_filter_cells_and_include_executables");
+    skip_if_seccomp!("cell_list_must_filter_cells_and_include_executables");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell, with a nested cell, and another cell
+    let parent_cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    let nested_cell_name = retry!(
+        client
+            .allocate(
+                CellServiceAllocateRequestBuilder::new()
+                    .parent_cell_name(parent_cell_name.clone())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    let other_cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable in the nested cell
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let _ = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(nested_cell_name.clone())
+                    .executable_name(exe_name.clone())
+                    .command("sleep 60".into())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap();
+
+    // Only the subtree of the parent cell is listed, with the executables
+    let cells = retry!(
+        client
+            .list(CellServiceListRequest {
+                cell_name: Some(parent_cell_name.clone()),
+                include_executables: true,
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cells;
+
+    assert_eq!(
+        cell_names(&cells),
+        [parent_cell_name.clone(), nested_cell_name.clone()]
+    );
+    assert!(!cell_names(&cells).contains(&other_cell_name));
+
+    let parent = &cells[0];
+    assert_eq!(parent.executables_error, None);
+    assert!(parent.executables.is_empty());
+
+    let nested = &parent.children[0];
+    assert_eq!(nested.executables_error, None);
+    let executables: Vec<_> =
+        nested.executables.iter().map(|x| x.name.clone()).collect();
+    assert_eq!(executables, [exe_name.clone()]);
+
+    let allocation = nested.allocation.as_ref().expect("allocation");
+    assert!(allocation.nested_auraed_running);
+
+    // The nested cell is not listed below a depth of 0
+    let cells = retry!(
+        client
+            .list(CellServiceListRequest {
+                cell_name: Some(parent_cell_name.clone()),
+                depth: Some(0),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cells;
+
+    assert_eq!(cell_names(&cells), [parent_cell_name.clone()]);
+
+    // The nested cell is listed along with its parent when its name matches
+    let cells = retry!(
+        client
+            .list(CellServiceListRequest {
+                name_pattern: Some(format!("*/{}", leaf(&nested_cell_name))),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cells;
+
+    assert_eq!(cell_names(&cells), [parent_cell_name, nested_cell_name]);
+    // Executables are only listed when requested
+    assert!(cells[0].children[0].executables.is_empty());
+}
+
+fn cell_names(cells: &[CellGraphNode]) -> Vec<String> {
+    let mut names = vec![];
+    for node in cells {
+        names.extend(node.cell.as_ref().map(|x| x.name.clone()));
+        names.extend(cell_names(&node.children));
+    }
+    names
+}
+
+fn leaf(cell_name: &str) -> &str {
+    cell_name.rsplit('/').next().expect("cell name")
+}
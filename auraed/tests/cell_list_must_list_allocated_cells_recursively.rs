@@ -74,9 +74,13 @@ async fn cells_list_must_list_allocated_cells_recursively() {
     .cell_name;
 
     // List all cells
-    let list_response = retry!(client.list(CellServiceListRequest {}).await)
-        .unwrap()
-        .into_inner();
+    let mut list_response =
+        retry!(client.list(CellServiceListRequest::default()).await)
+            .unwrap()
+            .into_inner();
+
+    // Each cell runs at least its nested auraed. Take the pids and allocation out, as they vary.
+    take_pids(&mut list_response.cells);
 
     // The expected response
     let mut expected = CellServiceListResponse {
@@ -87,10 +91,25 @@ async fn cells_list_must_list_allocated_cells_recursively() {
                     cpu: None,
                     cpuset: None,
                     memory: None,
+                    io: None,
+                    pids: None,
+                    hugetlb: None,
+                    rdma: None,
                     isolate_process: false,
                     isolate_network: false,
+                    isolate_user: false,
+                    uid_map: vec![],
+                    gid_map: vec![],
+                    rootfs: None,
+                    mounts: vec![],
+                    security_profile: None,
                 }),
                 children: vec![],
+                pids: None,
+                frozen: false,
+                executables: vec![],
+                executables_error: None,
+                allocation: None,
             },
             CellGraphNode {
                 cell: Some(Cell {
@@ -98,8 +117,18 @@ async fn cells_list_must_list_allocated_cells_recursively() {
                     cpu: None,
                     cpuset: None,
                     memory: None,
+                    io: None,
+                    pids: None,
+                    hugetlb: None,
+                    rdma: None,
                     isolate_process: false,
                     isolate_network: false,
+                    isolate_user: false,
+                    uid_map: vec![],
+                    gid_map: vec![],
+                    rootfs: None,
+                    mounts: vec![],
+                    security_profile: None,
                 }),
                 children: vec![CellGraphNode {
                     cell: Some(Cell {
@@ -107,8 +136,18 @@ async fn cells_list_must_list_allocated_cells_recursively() {
                         cpu: None,
                         cpuset: None,
                         memory: None,
+                        io: None,
+                        pids: None,
+                        hugetlb: None,
+                        rdma: None,
                         isolate_process: false,
                         isolate_network: false,
+                        isolate_user: false,
+                        uid_map: vec![],
+                        gid_map: vec![],
+                        rootfs: None,
+                        mounts: vec![],
+                        security_profile: None,
                     }),
                     children: vec![CellGraphNode {
                         cell: Some(Cell {
@@ -116,12 +155,37 @@ async fn cells_list_must_list_allocated_cells_recursively() {
                             cpu: None,
                             cpuset: None,
                             memory: None,
+                            io: None,
+                            pids: None,
+                            hugetlb: None,
+                            rdma: None,
                             isolate_process: false,
                             isolate_network: false,
+                            isolate_user: false,
+                            uid_map: vec![],
+                            gid_map: vec![],
+                            rootfs: None,
+                            mounts: vec![],
+                            security_profile: None,
                         }),
                         children: vec![],
+                        pids: None,
+                        frozen: false,
+                        executables: vec![],
+                        executables_error: None,
+                        allocation: None,
                     }],
+                    pids: None,
+                    frozen: false,
+                    executables: vec![],
+                    executables_error: None,
+                    allocation: None,
                 }],
+                pids: None,
+                frozen: false,
+                executables: vec![],
+                executables_error: None,
+                allocation: None,
             },
         ],
     };
@@ -132,4 +196,16 @@ async fn cells_list_must_list_allocated_cells_recursively() {
         expected.cells.swap(0, 1);
         assert_eq!(list_response, expected);
     }
+}
+
+fn take_pids(cells: &mut [CellGraphNode]) {
+    for cell in cells {
+        let pids = cell.pids.take().expect("pids");
+        assert!(pids.current >= 1);
+        assert_eq!(pids.max, None);
+        let allocation = cell.allocation.take().expect("allocation");
+        assert!(allocation.nested_auraed_running);
+        assert!(allocation.nested_auraed_pid > 0);
+        take_pids(&mut cell.children);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{
+    CellServiceListRequest, CellServicePauseRequest, CellServiceResumeRequest,
+};
+use test_helpers::*;
+use tonic::Code;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_pause_must_freeze_cell_until_resumed() {
+    skip_if_not_root!("cell_pause_must_freeze_cell_until_resumed");
+    skip_if_seccomp!("cell_pause_must_freeze_cell_until_resumed");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Pause the cell
+    let _ = retry!(
+        client
+            .pause(CellServicePauseRequest { cell_name: cell_name.clone() })
+            .await
+    )
+    .unwrap();
+    assert!(is_frozen(&client, &cell_name).await);
+
+    // The nested auraed of a paused cell can't start executables
+    let status = client
+        .start(
+            CellServiceStartRequestBuilder::new()
+                .cell_name(cell_name.clone())
+                .build(),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    // Resume the cell
+    let _ = retry!(
+        client
+            .resume(CellServiceResumeRequest { cell_name: cell_name.clone() })
+            .await
+    )
+    .unwrap();
+    assert!(!is_frozen(&client, &cell_name).await);
+
+    // Executables can be started again
+    let _ = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap();
+}
+
+async fn is_frozen(client: &client::Client, cell_name: &str) -> bool {
+    let listed = retry!(client.list(CellServiceListRequest::default()).await)
+        .unwrap()
+        .into_inner();
+
+    listed
+        .cells
+        .into_iter()
+        .find(|node| {
+            node.cell.as_ref().is_some_and(|cell| cell.name == cell_name)
+        })
+        .expect("cell")
+        .frozen
+}
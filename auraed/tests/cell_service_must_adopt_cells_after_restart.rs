@@ -0,0 +1,205 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use backoff::backoff::Backoff;
+use client::{
+    cells::cell_service::CellServiceClient,
+    observe::observe_service::ObserveServiceClient, AuraeConfig, AuraeSocket,
+    AuthConfig, Client, ClientError, SystemConfig,
+};
+use common::cells::CellServiceAllocateRequestBuilder;
+use nix::{
+    sys::signal::{self, Signal},
+    unistd::Pid,
+};
+use proto::{
+    cells::{
+        CellServiceFreeRequest, CellServiceListRequest,
+        CellServiceStartRequest, CellServiceStopRequest, Executable,
+    },
+    observe::{GetSubProcessStreamRequest, LogChannelType},
+};
+use std::{
+    path::Path,
+    process::{Child, Command},
+    time::Duration,
+};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_service_must_adopt_cells_after_restart() {
+    skip_if_not_root!("cell_service_must_adopt_cells_after_restart");
+    skip_if_seccomp!("cell_service_must_adopt_cells_after_restart");
+
+    let socket =
+        std::env::temp_dir().join(format!("{}.socket", uuid::Uuid::new_v4()));
+    let state_dir = std::env::temp_dir()
+        .join(format!("aurae-state-{}", uuid::Uuid::new_v4()));
+
+    let (mut auraed, client) = spawn_auraed(&socket, &state_dir).await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable outside of a cell
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let _ = retry!(
+        client
+            .start(CellServiceStartRequest {
+                executable: Some(Executable {
+                    name: exe_name.clone(),
+                    command: "echo hello; sleep 60".into(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap();
+    assert_eq!(logged_lines(&client, &exe_name, 1).await, ["hello"]);
+
+    // Kill auraed without giving it a chance to free the cell
+    auraed.kill().expect("failed to kill auraed");
+    let _ = auraed.wait();
+
+    let (mut auraed, client) = spawn_auraed(&socket, &state_dir).await;
+
+    // The cell was adopted by the new auraed
+    assert!(is_listed(&client, &cell_name).await);
+
+    // The executable was started again, after the output of its earlier run
+    assert_eq!(logged_lines(&client, &exe_name, 2).await, ["hello", "hello"]);
+
+    // Stop auraed gracefully, which leaves the cell running
+    signal::kill(Pid::from_raw(auraed.id() as i32), Signal::SIGTERM)
+        .expect("failed to terminate auraed");
+    let _ = auraed.wait();
+
+    let (mut auraed, client) = spawn_auraed(&socket, &state_dir).await;
+
+    // The cell was adopted again
+    assert!(is_listed(&client, &cell_name).await);
+
+    let _ = retry!(
+        client
+            .free(CellServiceFreeRequest { cell_name: cell_name.clone() })
+            .await
+    )
+    .unwrap();
+
+    let _ = retry!(
+        client
+            .stop(CellServiceStopRequest {
+                executable_name: exe_name.clone(),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap();
+
+    auraed.kill().expect("failed to kill auraed");
+    let _ = auraed.wait();
+}
+
+/// Waits for the executable to have logged `count` lines to stdout, and returns them.
+async fn logged_lines(
+    client: &Client,
+    exe_name: &str,
+    count: usize,
+) -> Vec<String> {
+    let request = GetSubProcessStreamRequest {
+        channel_type: LogChannelType::Stdout.into(),
+        tail: Some(count as u32 + 1),
+        follow: Some(false),
+        executable_name: Some(exe_name.into()),
+        ..Default::default()
+    };
+
+    let mut lines = vec![];
+    for _ in 0..50 {
+        let mut stream =
+            retry!(client.get_sub_process_stream(request.clone()).await)
+                .unwrap()
+                .into_inner();
+
+        lines.clear();
+        while let Some(res) = stream.message().await.unwrap() {
+            lines.push(res.item.expect("log item").line);
+        }
+
+        if lines.len() >= count {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    lines
+}
+
+async fn is_listed(client: &Client, cell_name: &str) -> bool {
+    let cells = retry!(client.list(CellServiceListRequest::default()).await)
+        .unwrap()
+        .into_inner()
+        .cells;
+
+    cells
+        .iter()
+        .filter_map(|node| node.cell.as_ref())
+        .any(|cell| cell.name == cell_name)
+}
+
+async fn spawn_auraed(socket: &Path, state_dir: &Path) -> (Child, Client) {
+    let auraed = Command::new(env!("CARGO_BIN_EXE_auraed"))
+        .arg("--socket")
+        .arg(socket)
+        .arg("--state-dir")
+        .arg(state_dir)
+        .spawn()
+        .expect("failed to spawn auraed");
+
+    let client_config = AuraeConfig {
+        auth: AuthConfig {
+            ca_crt: "/etc/aurae/pki/ca.crt".to_string(),
+            client_crt: "/etc/aurae/pki/_signed.client.nova.crt".to_string(),
+            client_key: "/etc/aurae/pki/client.nova.key".to_string(),
+        },
+        system: SystemConfig { socket: AuraeSocket::Path(socket.into()) },
+    };
+
+    let mut retry_strategy = common::default_retry_strategy();
+
+    let client = loop {
+        match Client::new(client_config.clone()).await {
+            Ok(client) => break Ok(client),
+            e @ Err(ClientError::ConnectionError(_)) => {
+                if let Some(delay) = retry_strategy.next_backoff() {
+                    tokio::time::sleep(delay).await
+                } else {
+                    break e;
+                }
+            }
+            e => break e,
+        }
+    }
+    .expect("failed to create client");
+
+    (auraed, client)
+}
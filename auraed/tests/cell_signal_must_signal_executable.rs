@@ -0,0 +1,88 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{CellServiceSignalRequest, CellServiceWaitRequest};
+use test_helpers::*;
+use tonic::Code;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_signal_must_signal_executable() {
+    skip_if_not_root!("cell_signal_must_signal_executable");
+    skip_if_seccomp!("cell_signal_must_signal_executable");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable that exits when it receives SIGUSR1
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let pid = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .executable_name(exe_name.clone())
+                    .command(
+                        "trap 'exit 7' USR1; while true; do sleep 0.1; done"
+                            .into()
+                    )
+                    .build(),
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .pid;
+
+    // Send SIGUSR1 to the executable
+    let request = CellServiceSignalRequest {
+        cell_name: Some(cell_name.clone()),
+        executable_name: Some(exe_name.clone()),
+        signal: 10,
+    };
+    let pids =
+        retry!(client.signal(request.clone()).await).unwrap().into_inner().pids;
+    assert_eq!(pids, vec![pid]);
+
+    // The executable runs its handler and exits
+    let executable = retry!(
+        client
+            .wait(CellServiceWaitRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status");
+    assert_eq!(executable.exit_status.and_then(|x| x.code), Some(7));
+
+    // An executable that is no longer running can not be signaled
+    let status = client.signal(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+}
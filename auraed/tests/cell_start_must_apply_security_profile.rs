@@ -0,0 +1,121 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use client::cells::cell_service::CellServiceClient;
+use client::Client;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{Capabilities, CellServiceWaitRequest, SecurityProfile};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_start_must_apply_security_profile() {
+    skip_if_not_root!("cell_start_must_apply_security_profile");
+    skip_if_seccomp!("cell_start_must_apply_security_profile");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell whose executables may only hold CAP_CHOWN
+    let cell_name = retry!(
+        client
+            .allocate(
+                CellServiceAllocateRequestBuilder::new()
+                    .security_profile(SecurityProfile {
+                        capabilities: Some(Capabilities {
+                            bounding: vec!["CAP_CHOWN".into()],
+                            ambient: vec![],
+                        }),
+                        no_new_privs: true,
+                        seccomp: None,
+                    })
+                    .build()
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // An executable without a profile gets the one of its cell
+    let code = start_and_wait(
+        &client,
+        &cell_name,
+        CellServiceStartRequestBuilder::new().command(
+            "grep -q '^NoNewPrivs:[[:space:]]*1$' /proc/self/status || exit 2
+             grep -q '^CapBnd:[[:space:]]*0000000000000001$' /proc/self/status || exit 3"
+                .into(),
+        ),
+    )
+    .await;
+    assert_eq!(code, Some(0));
+
+    // An executable with a profile only gets its own
+    let dir = format!("/tmp/ae-e2e-{}", uuid::Uuid::new_v4());
+    let code = start_and_wait(
+        &client,
+        &cell_name,
+        CellServiceStartRequestBuilder::new()
+            .command(format!(
+                "grep -q '^NoNewPrivs:[[:space:]]*0$' /proc/self/status || exit 2
+                 mkdir {dir} && exit 3"
+            ))
+            .security_profile(SecurityProfile {
+                capabilities: None,
+                no_new_privs: false,
+                seccomp: Some(
+                    r#"{
+                        "defaultAction": "SCMP_ACT_ALLOW",
+                        "syscalls": [
+                            { "names": ["mkdir", "mkdirat"], "action": "SCMP_ACT_ERRNO" }
+                        ]
+                    }"#
+                    .into(),
+                ),
+            }),
+    )
+    .await;
+    assert_eq!(code, Some(1), "mkdir must be denied by the seccomp profile");
+}
+
+async fn start_and_wait(
+    client: &Client,
+    cell_name: &str,
+    request: &mut CellServiceStartRequestBuilder,
+) -> Option<i32> {
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let request = request
+        .cell_name(cell_name.to_string())
+        .executable_name(exe_name.clone())
+        .build();
+
+    let _ = retry!(client.start(request.clone()).await).unwrap();
+
+    retry!(
+        client
+            .wait(CellServiceWaitRequest {
+                cell_name: Some(cell_name.to_string()),
+                executable_name: exe_name.clone(),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status")
+    .exit_status
+    .and_then(|x| x.code)
+}
@@ -0,0 +1,91 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::CellServiceAllocateRequestBuilder;
+use proto::cells::{CellServiceStatsRequest, CellServiceWatchStatsRequest};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_stats_must_return_stats_of_cells_recursively() {
+    skip_if_not_root!("cell_stats_must_return_stats_of_cells_recursively");
+    skip_if_seccomp!("cell_stats_must_return_stats_of_cells_recursively");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let parent_cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Allocate a nested cell
+    let nested_cell_name = retry!(
+        client
+            .allocate(
+                CellServiceAllocateRequestBuilder::new()
+                    .parent_cell_name(parent_cell_name.clone())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    let stats = retry!(
+        client
+            .stats(CellServiceStatsRequest {
+                cell_name: Some(parent_cell_name.clone()),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner();
+
+    assert_eq!(stats.cells.len(), 1);
+    let parent = &stats.cells[0];
+    assert_eq!(parent.cell_name, parent_cell_name);
+    assert_eq!(parent.children.len(), 1);
+    let nested = &parent.children[0];
+    assert_eq!(nested.cell_name, nested_cell_name);
+
+    // Each cell runs at least its own nested auraed, and a parent includes its children
+    let parent_pids = parent.stats.as_ref().unwrap().pids.as_ref().unwrap();
+    let nested_pids = nested.stats.as_ref().unwrap().pids.as_ref().unwrap();
+    assert!(nested_pids.current >= 1);
+    assert!(parent_pids.current > nested_pids.current);
+
+    // Watch the stats of the nested cell
+    let mut stream = retry!(
+        client
+            .watch_stats(CellServiceWatchStatsRequest {
+                cell_name: Some(nested_cell_name.clone()),
+                interval_ms: Some(100),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner();
+
+    let first = stream.message().await.unwrap().expect("stats");
+    let second = stream.message().await.unwrap().expect("stats");
+    assert_eq!(first.cells[0].cell_name, nested_cell_name);
+    assert!(second.collected_at > first.collected_at);
+}
@@ -0,0 +1,120 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::CellServiceStopRequest;
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_stop_must_stop_executable_gracefully() {
+    skip_if_not_root!("cell_stop_must_stop_executable_gracefully");
+    skip_if_seccomp!("cell_stop_must_stop_executable_gracefully");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable that exits cleanly on SIGTERM, and one that ignores it
+    let graceful_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let stubborn_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    for (exe_name, command) in [
+        (&graceful_name, "trap 'exit 42' TERM; while true; do sleep 0.1; done"),
+        (&stubborn_name, "trap '' TERM; while true; do sleep 0.1; done"),
+    ] {
+        let _ = retry!(
+            client
+                .start(
+                    CellServiceStartRequestBuilder::new()
+                        .cell_name(cell_name.clone())
+                        .executable_name(exe_name.clone())
+                        .command(command.into())
+                        .build(),
+                )
+                .await
+        )
+        .unwrap();
+    }
+
+    // The default signal is SIGTERM, which lets the executable run its handler
+    let executable = retry!(
+        client
+            .stop(CellServiceStopRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: graceful_name.clone(),
+                signal: None,
+                grace_period_ms: None,
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status");
+    let exit_status = executable.exit_status.expect("exit status");
+    assert_eq!(exit_status.code, Some(42));
+    assert_eq!(exit_status.signal, None);
+
+    // An executable that outlives the grace period is sent SIGKILL
+    let stopped = {
+        let client = client.clone();
+        let stubborn_name = stubborn_name.clone();
+        let cell_name = cell_name.clone();
+        tokio::spawn(async move {
+            client
+                .stop(CellServiceStopRequest {
+                    cell_name: Some(cell_name),
+                    executable_name: stubborn_name,
+                    signal: None,
+                    grace_period_ms: Some(2000),
+                })
+                .await
+        })
+    };
+
+    // The name stays reserved during the grace period
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    let status = client
+        .start(
+            CellServiceStartRequestBuilder::new()
+                .cell_name(cell_name.clone())
+                .executable_name(stubborn_name.clone())
+                .command("sleep 1".into())
+                .build(),
+        )
+        .await
+        .expect_err("name of a stopping executable was reused");
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let executable = stopped
+        .await
+        .unwrap()
+        .unwrap()
+        .into_inner()
+        .executable
+        .expect("executable status");
+    let exit_status = executable.exit_status.expect("exit status");
+    assert_eq!(exit_status.code, None);
+    assert_eq!(exit_status.signal, Some(9));
+}
@@ -0,0 +1,109 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::cells::cell_service::CellServiceClient;
+use common::cells::CellServiceAllocateRequestBuilder;
+use proto::cells::{
+    CellGraphNode, CellServiceListRequest, CellServiceUpdateRequest,
+    PidsController,
+};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_update_must_update_resources_of_nested_cell() {
+    skip_if_not_root!("cell_update_must_update_resources_of_nested_cell");
+    skip_if_seccomp!("cell_update_must_update_resources_of_nested_cell");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let parent_cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Allocate a nested cell
+    let nested_cell_name = retry!(
+        client
+            .allocate(
+                CellServiceAllocateRequestBuilder::new()
+                    .parent_cell_name(parent_cell_name.clone())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Limit the pids of the nested cell
+    let _ = retry!(
+        client.update(update_request(nested_cell_name.clone(), Some(64))).await
+    )
+    .unwrap();
+
+    let nested = list_nested_cell(&client, &parent_cell_name).await;
+    assert_eq!(nested.cell.unwrap().pids.unwrap().max, Some(64));
+    assert_eq!(nested.pids.unwrap().max, Some(64));
+
+    // Controllers that are no longer set are reset
+    let _ = retry!(
+        client.update(update_request(nested_cell_name.clone(), None)).await
+    )
+    .unwrap();
+
+    let nested = list_nested_cell(&client, &parent_cell_name).await;
+    assert!(nested.cell.unwrap().pids.is_none());
+    assert_eq!(nested.pids.unwrap().max, None);
+}
+
+fn update_request(
+    cell_name: String,
+    max: Option<i64>,
+) -> CellServiceUpdateRequest {
+    CellServiceUpdateRequest {
+        cell_name,
+        cpu: None,
+        cpuset: None,
+        memory: None,
+        io: None,
+        pids: max.map(|max| PidsController { max: Some(max) }),
+        hugetlb: None,
+        rdma: None,
+    }
+}
+
+async fn list_nested_cell(
+    client: &client::Client,
+    parent_cell_name: &str,
+) -> CellGraphNode {
+    let listed = retry!(client.list(CellServiceListRequest::default()).await)
+        .unwrap()
+        .into_inner();
+
+    let parent = listed
+        .cells
+        .into_iter()
+        .find(|node| {
+            node.cell.as_ref().is_some_and(|cell| cell.name == parent_cell_name)
+        })
+        .expect("parent cell");
+
+    parent.children.into_iter().next().expect("nested cell")
+}
@@ -0,0 +1,110 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use client::cells::cell_service::CellServiceClient;
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::cells::{
+    CellServiceListExecutablesRequest, CellServiceStopRequest,
+    CellServiceWaitRequest, ExecutableState,
+};
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn cell_wait_must_return_exit_status_of_exited_executable() {
+    skip_if_not_root!("cell_wait_must_return_exit_status_of_exited_executable");
+    skip_if_seccomp!("cell_wait_must_return_exit_status_of_exited_executable");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable that exits on its own
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let pid = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .executable_name(exe_name.clone())
+                    .command("exit 3".into())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .pid;
+
+    // Wait for the executable to exit
+    let executable = retry!(
+        client
+            .wait(CellServiceWaitRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status");
+
+    assert_eq!(executable.pid, pid);
+    assert_eq!(executable.state(), ExecutableState::Exited);
+    let exit_status = executable.exit_status.expect("exit status");
+    assert_eq!(exit_status.code, Some(3));
+    assert_eq!(exit_status.signal, None);
+    assert!(executable.exited_at.expect("exited at") >= executable.started_at);
+
+    // The exited executable is still listed until it is stopped
+    let executables = retry!(
+        client
+            .list_executables(CellServiceListExecutablesRequest {
+                cell_name: Some(cell_name.clone()),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executables;
+    assert_eq!(executables.len(), 1);
+    assert_eq!(executables[0].name, exe_name);
+    assert_eq!(executables[0].state(), ExecutableState::Exited);
+
+    // Stopping an exited executable returns its final status
+    let executable = retry!(
+        client
+            .stop(CellServiceStopRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .executable
+    .expect("executable status");
+    assert_eq!(executable.exit_status.and_then(|x| x.code), Some(3));
+}
@@ -16,6 +16,7 @@
 
 use proto::cells::{
     Cell, CellServiceAllocateRequest, CellServiceStartRequest, Executable,
+    MemoryController, Mount, RestartPolicy, SecurityProfile,
 };
 
 fn generate_cell_name(parent_name: Option<&str>) -> String {
@@ -29,11 +30,24 @@ fn generate_cell_name(parent_name: Option<&str>) -> String {
 struct CellBuilder {
     parent: Option<String>,
     isolate_process: bool,
+    isolate_network: bool,
+    memory_max: Option<i64>,
+    rootfs: Option<String>,
+    mounts: Vec<Mount>,
+    security_profile: Option<SecurityProfile>,
 }
 
 impl CellBuilder {
     pub fn new() -> Self {
-        Self { parent: None, isolate_process: false }
+        Self {
+            parent: None,
+            isolate_process: false,
+            isolate_network: false,
+            memory_max: None,
+            rootfs: None,
+            mounts: vec![],
+            security_profile: None,
+        }
     }
 
     pub fn parent_cell_name(&mut self, parent_cell_name: String) -> &mut Self {
@@ -46,15 +60,61 @@ impl CellBuilder {
         self
     }
 
+    pub fn isolate_network(&mut self) -> &mut Self {
+        self.isolate_network = true;
+        self
+    }
+
+    pub fn memory_max(&mut self, memory_max: i64) -> &mut Self {
+        self.memory_max = Some(memory_max);
+        self
+    }
+
+    pub fn rootfs(&mut self, rootfs: String) -> &mut Self {
+        self.rootfs = Some(rootfs);
+        self
+    }
+
+    pub fn mount(&mut self, mount: Mount) -> &mut Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    pub fn security_profile(
+        &mut self,
+        security_profile: SecurityProfile,
+    ) -> &mut Self {
+        self.security_profile = Some(security_profile);
+        self
+    }
+
     pub fn build(&self) -> Cell {
         let cell_name = generate_cell_name(self.parent.as_deref());
         Cell {
             name: cell_name,
             cpu: None,
             cpuset: None,
-            memory: None,
-            isolate_network: false,
+            memory: self.memory_max.map(|max| MemoryController {
+                min: None,
+                low: None,
+                high: None,
+                max: Some(max),
+                swap_max: None,
+                swap_high: None,
+                oom_group: None,
+            }),
+            io: None,
+            pids: None,
+            hugetlb: None,
+            rdma: None,
+            isolate_network: self.isolate_network,
             isolate_process: self.isolate_process,
+            isolate_user: false,
+            uid_map: vec![],
+            gid_map: vec![],
+            rootfs: self.rootfs.clone(),
+            mounts: self.mounts.clone(),
+            security_profile: self.security_profile.clone(),
         }
     }
 }
@@ -78,6 +138,34 @@ impl CellServiceAllocateRequestBuilder {
         self
     }
 
+    pub fn isolate_network(&mut self) -> &mut Self {
+        let _ = self.cell_builder.isolate_network();
+        self
+    }
+
+    pub fn memory_max(&mut self, memory_max: i64) -> &mut Self {
+        let _ = self.cell_builder.memory_max(memory_max);
+        self
+    }
+
+    pub fn rootfs(&mut self, rootfs: String) -> &mut Self {
+        let _ = self.cell_builder.rootfs(rootfs);
+        self
+    }
+
+    pub fn mount(&mut self, mount: Mount) -> &mut Self {
+        let _ = self.cell_builder.mount(mount);
+        self
+    }
+
+    pub fn security_profile(
+        &mut self,
+        security_profile: SecurityProfile,
+    ) -> &mut Self {
+        let _ = self.cell_builder.security_profile(security_profile);
+        self
+    }
+
     pub fn build(&self) -> CellServiceAllocateRequest {
         CellServiceAllocateRequest { cell: Some(self.cell_builder.build()) }
     }
@@ -87,6 +175,8 @@ struct ExecutableBuilder {
     name: String,
     command: String,
     description: String,
+    restart_policy: Option<RestartPolicy>,
+    security_profile: Option<SecurityProfile>,
 }
 
 impl ExecutableBuilder {
@@ -95,6 +185,8 @@ impl ExecutableBuilder {
             name: format!("ae-sleeper-{}", uuid::Uuid::new_v4()),
             command: "tail -f /dev/null".to_string(),
             description: String::from("description"),
+            restart_policy: None,
+            security_profile: None,
         }
     }
 
@@ -103,11 +195,35 @@ impl ExecutableBuilder {
         self
     }
 
+    pub fn command(&mut self, command: String) -> &mut Self {
+        self.command = command;
+        self
+    }
+
+    pub fn restart_policy(
+        &mut self,
+        restart_policy: RestartPolicy,
+    ) -> &mut Self {
+        self.restart_policy = Some(restart_policy);
+        self
+    }
+
+    pub fn security_profile(
+        &mut self,
+        security_profile: SecurityProfile,
+    ) -> &mut Self {
+        self.security_profile = Some(security_profile);
+        self
+    }
+
     pub fn build(&self) -> Executable {
         Executable {
             name: self.name.clone(),
             command: self.command.clone(),
             description: self.description.clone(),
+            restart_policy: self.restart_policy.clone(),
+            security_profile: self.security_profile.clone(),
+            ..Default::default()
         }
     }
 }
@@ -139,6 +255,27 @@ impl CellServiceStartRequestBuilder {
         self
     }
 
+    pub fn command(&mut self, command: String) -> &mut Self {
+        let _ = self.executable_builder.command(command);
+        self
+    }
+
+    pub fn restart_policy(
+        &mut self,
+        restart_policy: RestartPolicy,
+    ) -> &mut Self {
+        let _ = self.executable_builder.restart_policy(restart_policy);
+        self
+    }
+
+    pub fn security_profile(
+        &mut self,
+        security_profile: SecurityProfile,
+    ) -> &mut Self {
+        let _ = self.executable_builder.security_profile(security_profile);
+        self
+    }
+
     pub fn uid(&mut self, uid: u32) -> &mut Self {
         self.uid = Some(uid);
         self
@@ -83,8 +83,12 @@ async fn run_auraed() -> Client {
     };
 
     tokio::spawn(async move {
+        // Cells and executables left behind by other test runs are not adopted
+        let state_dir = std::env::temp_dir()
+            .join(format!("aurae-state-{}", uuid::Uuid::new_v4()));
         let runtime = AuraedRuntime {
             auraed: AuraedPath::from_path("auraed"),
+            state_dir,
             ..Default::default()
         };
         auraed::run(runtime, Some(socket), false, false).await.unwrap()
@@ -66,4 +66,4 @@ impl GetPosixSignalsStreamRequestBuilder {
     pub fn build(&self) -> GetPosixSignalsStreamRequest {
         GetPosixSignalsStreamRequest { workload: self.workload.clone() }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,95 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::{
+    cells::cell_service::CellServiceClient,
+    observe::observe_service::ObserveServiceClient,
+};
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::observe::{CellEventType, GetCellEventsStreamRequest};
+use std::time::Duration;
+use test_helpers::*;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn observe_get_cell_events_stream_must_report_oom_kills() {
+    skip_if_not_root!("observe_get_cell_events_stream_must_report_oom_kills");
+    skip_if_seccomp!("observe_get_cell_events_stream_must_report_oom_kills");
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell with 32 MiB of memory
+    let cell_name = retry!(
+        client
+            .allocate(
+                CellServiceAllocateRequestBuilder::new()
+                    .memory_max(32 * 1024 * 1024)
+                    .build()
+            )
+            .await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Watch the events of the cell
+    let mut stream = retry!(
+        client
+            .get_cell_events_stream(GetCellEventsStreamRequest {
+                cell_name: cell_name.clone(),
+            })
+            .await
+    )
+    .unwrap()
+    .into_inner();
+
+    // Start an executable that uses more memory than the cell allows
+    let _ = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .command(
+                        "dd if=/dev/zero of=/dev/null bs=128M count=1".into()
+                    )
+                    .build(),
+            )
+            .await
+    )
+    .unwrap();
+
+    let oom_kill = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let event = stream
+                .message()
+                .await
+                .unwrap()
+                .expect("stream ended")
+                .event
+                .expect("event");
+            if event.event_type() == CellEventType::OomKill {
+                break event;
+            }
+        }
+    })
+    .await
+    .expect("oom kill event");
+
+    assert_eq!(oom_kill.cell_name, cell_name);
+    assert!(oom_kill.count >= 1);
+}
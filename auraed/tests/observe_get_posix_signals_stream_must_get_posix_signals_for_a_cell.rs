@@ -98,6 +98,8 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_a_cell() {
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell1_name.clone()),
                 executable_name: exe1_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
@@ -108,6 +110,8 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_a_cell() {
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell2_name.clone()),
                 executable_name: exe2_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
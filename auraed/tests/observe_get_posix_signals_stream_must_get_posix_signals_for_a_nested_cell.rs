@@ -129,6 +129,8 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_a_nested_cel
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell1_name.clone()),
                 executable_name: exe1_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
@@ -139,6 +141,8 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_a_nested_cel
             .stop(CellServiceStopRequest {
                 cell_name: Some(nested_cell_name.clone()),
                 executable_name: nested_exe_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
@@ -149,6 +153,8 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_a_nested_cel
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell2_name.clone()),
                 executable_name: exe2_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
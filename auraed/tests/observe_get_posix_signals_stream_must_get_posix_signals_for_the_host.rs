@@ -66,12 +66,14 @@ async fn observe_get_posix_signal_stream_must_get_posix_signals_for_the_host() {
     )
     .await;
 
-    // Stop the executable (should trigger SIGKILL)
+    // Stop the executable (with SIGKILL)
     let _ = retry!(
         client
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell_name.clone()),
                 executable_name: exe_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
@@ -73,12 +73,14 @@ async fn observe_get_posix_signal_stream_must_map_host_pids_to_namespace_pids()
     )
     .await;
 
-    // Stop the executable (should trigger SIGKILL)
+    // Stop the executable (with SIGKILL)
     let _ = retry!(
         client
             .stop(CellServiceStopRequest {
                 cell_name: Some(cell_name.clone()),
                 executable_name: exe_name.clone(),
+                signal: Some(9),
+                grace_period_ms: None,
             })
             .await
     );
@@ -0,0 +1,143 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+
+use client::{
+    cells::cell_service::CellServiceClient,
+    observe::observe_service::ObserveServiceClient,
+};
+use common::cells::{
+    CellServiceAllocateRequestBuilder, CellServiceStartRequestBuilder,
+};
+use proto::{
+    cells::CellServiceStopRequest,
+    observe::{GetSubProcessStreamRequest, LogChannelType, LogSeverity},
+};
+use std::time::Duration;
+use test_helpers::*;
+use tonic::Code;
+
+mod common;
+
+#[test_helpers_macros::shared_runtime_test]
+async fn observe_get_sub_process_stream_must_stream_logs_of_executable_in_cell()
+{
+    skip_if_not_root!(
+        "observe_get_sub_process_stream_must_stream_logs_of_executable_in_cell"
+    );
+    skip_if_seccomp!(
+        "observe_get_sub_process_stream_must_stream_logs_of_executable_in_cell"
+    );
+
+    let client = common::auraed_client().await;
+
+    // Allocate a cell
+    let cell_name = retry!(
+        client.allocate(CellServiceAllocateRequestBuilder::new().build()).await
+    )
+    .unwrap()
+    .into_inner()
+    .cell_name;
+
+    // Start an executable in the cell that logs two lines
+    let exe_name = format!("ae-e2e-{}", uuid::Uuid::new_v4());
+    let _ = retry!(
+        client
+            .start(
+                CellServiceStartRequestBuilder::new()
+                    .cell_name(cell_name.clone())
+                    .executable_name(exe_name.clone())
+                    .command("echo hello; echo aurae; sleep 60".into())
+                    .build(),
+            )
+            .await
+    )
+    .unwrap();
+
+    // The root auraed does not know the executable's pid, so address the logs by name
+    let request = GetSubProcessStreamRequest {
+        channel_type: LogChannelType::Stdout.into(),
+        tail: Some(2),
+        follow: Some(false),
+        cell_name: Some(cell_name.clone()),
+        executable_name: Some(exe_name.clone()),
+        ..Default::default()
+    };
+
+    let mut items = vec![];
+    for _ in 0..50 {
+        let mut stream =
+            retry!(client.get_sub_process_stream(request.clone()).await)
+                .unwrap()
+                .into_inner();
+
+        items.clear();
+        while let Some(res) = stream.message().await.unwrap() {
+            items.push(res.item.expect("log item"));
+        }
+
+        if items.len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let lines: Vec<_> = items.iter().map(|x| x.line.as_str()).collect();
+    assert_eq!(lines, vec!["hello", "aurae"]);
+
+    // The items identify where they were logged
+    for item in &items {
+        assert_eq!(item.cell_name, cell_name);
+        assert_eq!(item.executable_name, exe_name);
+        assert_eq!(item.stream(), LogChannelType::Stdout);
+        assert_eq!(item.severity(), LogSeverity::Info);
+    }
+    let timestamps: Vec<_> = items
+        .iter()
+        .map(|x| x.timestamp.as_ref().map(|x| (x.seconds, x.nanos)))
+        .collect();
+    assert!(timestamps[0].is_some());
+    assert!(timestamps[0] <= timestamps[1]);
+
+    // Passing the timestamp of the last line received resumes from that line
+    let resumed = GetSubProcessStreamRequest {
+        since: items[1].timestamp.clone(),
+        tail: None,
+        ..request.clone()
+    };
+    let mut stream =
+        retry!(client.get_sub_process_stream(resumed.clone()).await)
+            .unwrap()
+            .into_inner();
+    let mut lines = vec![];
+    while let Some(res) = stream.message().await.unwrap() {
+        lines.push(res.item.expect("log item").line);
+    }
+    assert_eq!(lines, vec!["aurae"]);
+
+    let _ = retry!(
+        client
+            .stop(CellServiceStopRequest {
+                cell_name: Some(cell_name.clone()),
+                executable_name: exe_name.clone(),
+                signal: Some(9),
+                ..Default::default()
+            })
+            .await
+    )
+    .unwrap();
+
+    // Logs of executables that have been stopped can no longer be addressed by name
+    let status = client.get_sub_process_stream(request).await.unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+}
@@ -130,6 +130,7 @@ async fn vms_with_auraed() {
         CellServiceStopRequest {
             cell_name: Some(cell_name.clone()),
             executable_name: "sleeper".into(),
+            ..Default::default()
         },
     )
     .await
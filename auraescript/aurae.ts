@@ -1,3 +1,5 @@
+import type { Cell, Executable } from "./cells.ts";
+
 type CreateClientDefault = {
     kind: "default";
 };
@@ -50,3 +52,48 @@ export function createClient(opts: CreateClient = { kind: "default" }): Promise<
     // @ts-ignore
     return Deno.core.ops.as__client_new(config);
 }
+
+// The fields of a cell, or an executable, in the manifest default to the
+// defaults of the proto types. The names of nested cells are relative to
+// their parent.
+export type ExecutableManifest = Partial<Executable> & {
+    name: string;
+    uid?: number;
+    gid?: number;
+};
+export type CellManifest = Partial<Cell> & {
+    name: string;
+    executables?: ExecutableManifest[];
+    cells?: CellManifest[];
+};
+export type Manifest = {
+    cells: CellManifest[];
+};
+
+export type Change =
+    | { action: "allocate"; cell: Cell }
+    | { action: "update"; cell: Cell }
+    | { action: "free"; cellName: string }
+    | { action: "start"; cellName: string; executable: Executable; uid?: number; gid?: number }
+    | { action: "stop"; cellName: string; executableName: string };
+export type Plan = {
+    changes: Change[];
+};
+
+type ApplyOpts = {
+    // The client to apply the manifest with. Defaults to the default client.
+    client?: number;
+    // Only return the changes, without applying them.
+    dryRun?: boolean;
+    // Free the cells, and stop the executables, that are not in the manifest.
+    prune?: boolean;
+};
+
+// Makes the cells and executables of the node match the manifest, by applying
+// only the changes. Returns the changes.
+export function apply(manifest: Manifest, opts: ApplyOpts = {}): Promise<Plan> {
+    // @ts-ignore
+    return Deno.core.ops.as__manifest__apply(
+        opts.client, manifest, opts.dryRun ?? false, opts.prune ?? false
+    );
+}
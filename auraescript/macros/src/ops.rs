@@ -69,8 +69,8 @@ pub(crate) fn ops_generator(input: TokenStream) -> TokenStream {
             let client_ident =
                 Ident::new(&format!("{}Client", s.name()), file_path_span);
 
-            // TODO: support streaming
-            let methods = s.method.iter().filter(|m| !m.client_streaming() && !m.server_streaming());
+            // TODO: support client streaming
+            let methods = s.method.iter().filter(|m| !m.client_streaming());
 
             let op_idents = methods.clone()
                 .map(|m| {
@@ -80,8 +80,31 @@ pub(crate) fn ops_generator(input: TokenStream) -> TokenStream {
                     )
                 });
 
+            // Resolves the client to use for the call, either the default client or the one
+            // referenced by the client_rid passed in from typescript.
+            let get_client = quote! {
+                match client_rid {
+                    None => ::deno_core::RcRef::new(::client::Client::default().await
+                        .map_err(|e| ::deno_error::JsErrorBox::generic(
+                            format!("Failed to create default client: {:?}",e.to_string()))
+                        )?),
+                    Some(client_rid) => {
+                        let as_client = {
+                            let op_state = &op_state.borrow();
+                            let rt = &op_state.resource_table; // get `ResourceTable` from JsRuntime `OpState`
+                            rt.get::<crate::builtin::auraescript_client::AuraeScriptClient>(client_rid) // get `Client` from its rid
+                        .map_err(|e| ::deno_error::JsErrorBox::generic(
+                                    format!("Failed to get client: {:?}",e.to_string()))) // fix client error
+                                ?.clone()
+                        };
+                        ::deno_core::RcRef::map(as_client, |v| &v.0)
+                    }
+                }
+            };
+
             // generate a fn for each deno op
             let op_functions: Vec<proc_macro2::TokenStream> = methods
+                .clone()
                 .zip(op_idents.clone())
                 .map(|(m, op_ident)| {
                     let input_type = proto_reader::helpers::to_unqualified_type(m.input_type());
@@ -90,54 +113,104 @@ pub(crate) fn ops_generator(input: TokenStream) -> TokenStream {
                     let output_type = Ident::new(output_type, file_path_span);
                     let name = Ident::new(&m.name().to_snake_case(), file_path_span);
 
-                    // Magic OpState from deno (https://github.com/denoland/deno/blob/b6ac54815c1bcfa44a45b3f2c1c982829482477f/ops/lib.rs#L295)
-                    quote! {
-                        #[::deno_core::op2(async)]
-                        #[serde]
-                        pub(crate) async fn #op_ident(
-                            op_state: Rc<RefCell<OpState>>, // Auto filled by deno macro, call from typescript ignoring this parameter
-                            #[smi] client_rid: Option<::deno_core::ResourceId>,
-                            #[serde] req: ::proto::#module::#input_type,
-                        ) -> std::result::Result<
-                            ::proto::#module::#output_type,
-                            ::deno_error::JsErrorBox
-                        > {
-                            let client = match client_rid {
-                                None => ::deno_core::RcRef::new(::client::Client::default().await
-                                    .map_err(|e| ::deno_error::JsErrorBox::generic(
-                                        format!("Failed to create default client: {:?}",e.to_string()))
-                                    )?),
-                                Some(client_rid) => {
-                                    let as_client = {
-                                        let op_state = &op_state.borrow();
-                                        let rt = &op_state.resource_table; // get `ResourceTable` from JsRuntime `OpState`
-                                        rt.get::<crate::builtin::auraescript_client::AuraeScriptClient>(client_rid) // get `Client` from its rid
-                                    .map_err(|e| ::deno_error::JsErrorBox::generic(
-                                                format!("Failed to get client: {:?}",e.to_string()))) // fix client error
-                                            ?.clone()
-                                    };
-                                    ::deno_core::RcRef::map(as_client, |v| &v.0)
-                                }
-                            };
-                            let res = ::client::#module::#service_name_in_snake_case::#client_ident::#name(
-                                &(*client),
-                                req
-                            ).await.map_err(|e| ::deno_error::JsErrorBox::generic(
-                                    format!("Failed call method {:?},{:?}: {:?}",
-                                        stringify!(#service_name_in_snake_case),
-                                        stringify!(#name),
-                                        e.to_string())))?;
-
-                            Ok(res.into_inner())
+                    let call = quote! {
+                        ::client::#module::#service_name_in_snake_case::#client_ident::#name(
+                            &(*client),
+                            req
+                        ).await.map_err(|e| ::deno_error::JsErrorBox::generic(
+                                format!("Failed call method {:?},{:?}: {:?}",
+                                    stringify!(#service_name_in_snake_case),
+                                    stringify!(#name),
+                                    e.to_string())))?
+                    };
+
+                    if m.server_streaming() {
+                        // The first op makes the call and stores the response stream as a resource,
+                        // the second op is called from typescript to read the next message from it.
+                        let next_op_ident = Ident::new(
+                            &next_op_name(&op_ident.to_string()),
+                            file_path_span,
+                        );
+
+                        // Magic OpState from deno (https://github.com/denoland/deno/blob/b6ac54815c1bcfa44a45b3f2c1c982829482477f/ops/lib.rs#L295)
+                        quote! {
+                            #[::deno_core::op2(async)]
+                            #[smi]
+                            pub(crate) async fn #op_ident(
+                                op_state: Rc<RefCell<OpState>>, // Auto filled by deno macro, call from typescript ignoring this parameter
+                                #[smi] client_rid: Option<::deno_core::ResourceId>,
+                                #[serde] req: ::proto::#module::#input_type,
+                            ) -> std::result::Result<
+                                ::deno_core::ResourceId,
+                                ::deno_error::JsErrorBox
+                            > {
+                                let client = #get_client;
+                                let res = #call;
+
+                                let stream = crate::builtin::auraescript_stream::AuraeScriptStream::new(
+                                    res.into_inner()
+                                );
+                                let rid = op_state.borrow_mut().resource_table.add(stream);
+                                Ok(rid)
+                            }
+
+                            #[::deno_core::op2(async)]
+                            #[serde]
+                            pub(crate) async fn #next_op_ident(
+                                op_state: Rc<RefCell<OpState>>, // Auto filled by deno macro, call from typescript ignoring this parameter
+                                #[smi] stream_rid: ::deno_core::ResourceId,
+                            ) -> std::result::Result<
+                                Option<::proto::#module::#output_type>,
+                                ::deno_error::JsErrorBox
+                            > {
+                                let stream = {
+                                    let op_state = &op_state.borrow();
+                                    let rt = &op_state.resource_table; // get `ResourceTable` from JsRuntime `OpState`
+                                    rt.get::<crate::builtin::auraescript_stream::AuraeScriptStream<::proto::#module::#output_type>>(stream_rid)
+                                        .map_err(|e| ::deno_error::JsErrorBox::generic(
+                                            format!("Failed to get stream: {:?}",e.to_string())))?
+                                };
+
+                                stream.next().await
+                            }
+                        }
+                    } else {
+                        // Magic OpState from deno (https://github.com/denoland/deno/blob/b6ac54815c1bcfa44a45b3f2c1c982829482477f/ops/lib.rs#L295)
+                        quote! {
+                            #[::deno_core::op2(async)]
+                            #[serde]
+                            pub(crate) async fn #op_ident(
+                                op_state: Rc<RefCell<OpState>>, // Auto filled by deno macro, call from typescript ignoring this parameter
+                                #[smi] client_rid: Option<::deno_core::ResourceId>,
+                                #[serde] req: ::proto::#module::#input_type,
+                            ) -> std::result::Result<
+                                ::proto::#module::#output_type,
+                                ::deno_error::JsErrorBox
+                            > {
+                                let client = #get_client;
+                                let res = #call;
+
+                                Ok(res.into_inner())
+                            }
                         }
                     }
                 })
                 .collect();
 
             // generate a OpDecl for each function for conveniently adding to the deno runtime
-            let op_decls: Vec<proc_macro2::TokenStream> = op_idents.map(|op_ident| {
-                quote! {
-                    #op_ident()
+            let op_decls: Vec<proc_macro2::TokenStream> = methods.zip(op_idents).map(|(m, op_ident)| {
+                if m.server_streaming() {
+                    let next_op_ident = Ident::new(
+                        &next_op_name(&op_ident.to_string()),
+                        file_path_span,
+                    );
+                    quote! {
+                        #op_ident(), #next_op_ident()
+                    }
+                } else {
+                    quote! {
+                        #op_ident()
+                    }
                 }
             }).collect();
 
@@ -267,14 +340,58 @@ export class {service_name}Client implements {service_name} {{
         let output_type =
             proto_reader::helpers::to_unqualified_type(m.output_type());
 
-        ts_funcs.push_str(&format!(
-            r#"
+        if m.client_streaming() {
+            // TODO: support client streaming
+            // The method is still needed for the class to implement the service
+            let output_type = if m.server_streaming() {
+                format!("AsyncIterable<{output_type}>")
+            } else {
+                format!("Promise<{output_type}>")
+            };
+
+            ts_funcs.push_str(&format!(
+                r#"
+{fn_name}(_request: AsyncIterable<{input_type}>): {output_type} {{
+    throw new Error("{method_name} is not supported in AuraeScript");
+}}
+        "#
+            ));
+        } else if m.server_streaming() {
+            let next_op_name = next_op_name(&op_name);
+
+            // Server streams are exposed as async iterators (e.g., `for await (const res of stream)`).
+            // The stream resource is closed when the iterator finishes or is exited early.
+            ts_funcs.push_str(&format!(
+                r#"
+async *{fn_name}(request: {input_type}): AsyncIterable<{output_type}> {{
+    // @ts-ignore
+    const rid: number = await Deno.core.ops.{op_name}(this.client, request);
+    try {{
+        while (true) {{
+            // @ts-ignore
+            const res: {output_type} | null = await Deno.core.ops.{next_op_name}(rid);
+            if (res === null) {{
+                return;
+            }}
+            yield res;
+        }}
+    }} finally {{
+        // @ts-ignore
+        Deno.core.tryClose(rid);
+    }}
+}}
+        "#
+            ));
+        } else {
+            ts_funcs.push_str(&format!(
+                r#"
 {fn_name}(request: {input_type}): Promise<{output_type}> {{
     // @ts-ignore
     return Deno.core.ops.{op_name}(this.client, request);
 }}
         "#
-        ));
+            ));
+        }
     });
 
     ts_funcs.push('}');
@@ -299,3 +416,8 @@ fn op_name(module: &Path, service_name: &str, method_name: &str) -> String {
         method_name.to_snake_case()
     )
 }
+
+/// Example `ae__observe__observe_service__get_sub_process_stream__next`
+fn next_op_name(op_name: &str) -> String {
+    format!("{op_name}__next")
+}
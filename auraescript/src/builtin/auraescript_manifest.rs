@@ -0,0 +1,69 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+#![allow(non_snake_case)]
+
+use crate::builtin::auraescript_client::AuraeScriptClient;
+use client::{
+    manifest::{Manifest, Plan},
+    Client,
+};
+use deno_core::{self, op2, OpState, ResourceId};
+use deno_error::JsErrorBox;
+use std::{cell::RefCell, rc::Rc};
+
+// `Manifest` `apply`
+// Computes the changes for the node to match the manifest, and applies them
+// unless `dry_run` is set. Returns the changes.
+#[op2(async)]
+#[serde]
+pub(crate) async fn as__manifest__apply(
+    op_state: Rc<RefCell<OpState>>,
+    #[smi] client_rid: Option<ResourceId>,
+    #[serde] manifest: Manifest,
+    dry_run: bool,
+    prune: bool,
+) -> Result<Plan, JsErrorBox> {
+    let client = match client_rid {
+        None => Client::default().await.map_err(|err| {
+            JsErrorBox::new("Failed to create default client", err.to_string())
+        })?,
+        Some(client_rid) => {
+            let op_state = &op_state.borrow();
+            let rt = &op_state.resource_table; // get `ResourceTable` from JsRuntime `OpState`
+            rt.get::<AuraeScriptClient>(client_rid)
+                .map_err(|err| {
+                    JsErrorBox::new("Failed to get client", err.to_string())
+                })?
+                .0
+                .clone()
+        }
+    };
+
+    let plan = manifest.plan(&client, prune).await.map_err(|err| {
+        JsErrorBox::new("Failed to plan manifest", format!("{err:#}"))
+    })?;
+
+    if !dry_run {
+        plan.apply(&client).await.map_err(|err| {
+            JsErrorBox::new("Failed to apply manifest", format!("{err:#}"))
+        })?;
+    }
+
+    Ok(plan)
+}
+
+pub(crate) fn op_decls() -> Vec<::deno_core::OpDecl> {
+    vec![as__manifest__apply()]
+}
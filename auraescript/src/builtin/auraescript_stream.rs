@@ -0,0 +1,45 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use deno_core::{AsyncRefCell, RcRef, Resource};
+use deno_error::JsErrorBox;
+use std::{borrow::Cow, rc::Rc};
+
+// Holds the response stream of a server streaming rpc call so that it can be
+// consumed from typescript, one message at a time, using its resource id.
+pub(crate) struct AuraeScriptStream<T>(AsyncRefCell<tonic::Streaming<T>>);
+
+impl<T> AuraeScriptStream<T> {
+    pub(crate) fn new(stream: tonic::Streaming<T>) -> Self {
+        Self(AsyncRefCell::new(stream))
+    }
+
+    /// Returns the next message of the stream, or [None] when the server has
+    /// closed the stream.
+    pub(crate) async fn next(self: Rc<Self>) -> Result<Option<T>, JsErrorBox> {
+        let mut stream = RcRef::map(&self, |s| &s.0).borrow_mut().await;
+        stream.message().await.map_err(|e| {
+            JsErrorBox::generic(format!(
+                "Failed to read message from stream: {:?}",
+                e.to_string()
+            ))
+        })
+    }
+}
+
+impl<T: 'static> Resource for AuraeScriptStream<T> {
+    fn name(&self) -> Cow<str> {
+        "auraeScriptStream".into()
+    }
+}
@@ -22,6 +22,8 @@
 //! lives in this module.
 
 pub(crate) mod auraescript_client;
+pub(crate) mod auraescript_manifest;
+pub(crate) mod auraescript_stream;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const AUTHORS: &str = env!("CARGO_PKG_AUTHORS");
@@ -108,6 +108,7 @@ pub fn runtime(
 fn stdlib() -> Vec<deno_core::OpDecl> {
     let mut ops = vec![];
     ops.extend(builtin::auraescript_client::op_decls());
+    ops.extend(builtin::auraescript_manifest::op_decls());
     ops.extend(cells::op_decls());
     ops.extend(cri::op_decls());
     ops.extend(discovery::op_decls());
@@ -74,10 +74,32 @@ pub(crate) fn service(input: TokenStream) -> TokenStream {
 
             match (m.client_streaming.unwrap_or(false), m.server_streaming.unwrap_or(false)) {
                 (true, true) => {
-                    todo!("bidirectional streaming")
+                    quote! {
+                        async fn #name(
+                            &self,
+                            req: impl ::tonic::IntoStreamingRequest<
+                                Message = ::proto::#module::#input_type
+                            > + Send + 'static
+                        ) -> Result<
+                            ::tonic::Response<
+                                ::tonic::Streaming<::proto::#module::#output_type>
+                            >,
+                            ::tonic::Status
+                        >
+                    }
                 }
                 (true, false) => {
-                    todo!("client streaming")
+                    quote! {
+                        async fn #name(
+                            &self,
+                            req: impl ::tonic::IntoStreamingRequest<
+                                Message = ::proto::#module::#input_type
+                            > + Send + 'static
+                        ) -> Result<
+                            ::tonic::Response<::proto::#module::#output_type>,
+                            ::tonic::Status
+                        >
+                    }
                 },
                 (false, true) => {
                     quote! {
@@ -21,5 +21,6 @@ mod config;
 pub mod cri;
 pub mod discovery;
 pub mod grpc;
+pub mod manifest;
 pub mod observe;
 pub mod vms;
\ No newline at end of file
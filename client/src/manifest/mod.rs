@@ -0,0 +1,133 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+//! Declarative management of cells and executables.
+//!
+//! A [Manifest] describes the cells, nested cells and executables that should
+//! exist on an auraed node. Instead of allocating, starting and freeing in
+//! order, a [Plan] is computed by comparing the manifest with the cells and
+//! executables returned by `CellService.List`, and only the changes are
+//! applied.
+//!
+//! ```toml
+//! [[cells]]
+//! name = "app"
+//! cpu = { weight = 100 }
+//!
+//! [[cells.executables]]
+//! name = "server"
+//! command = "/usr/bin/sleep 3600"
+//!
+//! # Nested cell "app/sidecar"
+//! [[cells.cells]]
+//! name = "sidecar"
+//! isolate_process = true
+//! ```
+//!
+//! The cells and executables use the fields of the proto types, in either
+//! their original or their JSON (lowerCamelCase) names.
+
+pub use plan::{Change, Plan};
+
+use crate::{cells::cell_service::CellServiceClient, Client};
+use anyhow::{anyhow, Context, Result};
+use proto::cells::{Cell, CellServiceListRequest, Executable};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+mod plan;
+
+/// The cells, and their executables, that should exist on an auraed node.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub cells: Vec<CellManifest>,
+}
+
+/// A cell, with the executables and the nested cells it should have.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CellManifest {
+    /// The cell to allocate. Its name is relative to the parent cell, and
+    /// must not contain '/'.
+    #[serde(flatten)]
+    pub cell: Cell,
+    #[serde(default)]
+    pub executables: Vec<ExecutableManifest>,
+    #[serde(default)]
+    pub cells: Vec<CellManifest>,
+}
+
+/// An executable, and the user and group to start it as.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutableManifest {
+    #[serde(flatten)]
+    pub executable: Executable,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+}
+
+impl Manifest {
+    /// Parses a manifest file, in the format given by its extension
+    /// (`.toml` or `.json`).
+    pub fn parse_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).with_context(|| {
+            format!("could not read manifest {}", path.display())
+        })?;
+
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("toml") => Self::parse_from_toml(&content),
+            Some("json") => Self::parse_from_json(&content),
+            _ => Err(anyhow!(
+                "unsupported manifest format {}, expected .toml or .json",
+                path.display()
+            )),
+        }
+    }
+
+    pub fn parse_from_toml(manifest_toml: &str) -> Result<Self> {
+        Ok(toml::from_str(manifest_toml)?)
+    }
+
+    pub fn parse_from_json(manifest_json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(manifest_json)?)
+    }
+
+    /// Computes the changes needed for the node of `client` to match the
+    /// manifest, without applying them.
+    ///
+    /// Cells and executables that are not in the manifest are left alone,
+    /// unless `prune` is set, in which case they are freed and stopped.
+    pub async fn plan(&self, client: &Client, prune: bool) -> Result<Plan> {
+        let cells = client
+            .list(CellServiceListRequest {
+                include_executables: true,
+                ..Default::default()
+            })
+            .await
+            .context("failed to list cells")?
+            .into_inner()
+            .cells;
+
+        Plan::new(self, &cells, prune)
+    }
+
+    /// Computes the changes needed for the node of `client` to match the
+    /// manifest, and applies them. Returns the applied plan.
+    pub async fn apply(&self, client: &Client, prune: bool) -> Result<Plan> {
+        let plan = self.plan(client, prune).await?;
+        plan.apply(client).await?;
+        Ok(plan)
+    }
+}
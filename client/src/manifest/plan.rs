@@ -0,0 +1,503 @@
+/* -------------------------------------------------------------------------- *\
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ * -------------------------------------------------------------------------- *
+ * Copyright 2022 - 2024, the aurae contributors                              *
+ * SPDX-License-Identifier: Apache-2.0                                        *
+\* -------------------------------------------------------------------------- */
+use super::{CellManifest, Manifest};
+use crate::{cells::cell_service::CellServiceClient, Client};
+use anyhow::{anyhow, Context, Result};
+use proto::cells::{
+    Cell, CellGraphNode, CellServiceAllocateRequest, CellServiceFreeRequest,
+    CellServiceStartRequest, CellServiceStopRequest, CellServiceUpdateRequest,
+    Executable,
+};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+};
+
+/// A change to a cell or an executable.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(
+    tag = "action",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum Change {
+    /// Allocates a cell that does not exist.
+    Allocate { cell: Cell },
+    /// Updates the resources of a cell, when its controllers differ from the
+    /// manifest.
+    Update { cell: Cell },
+    /// Frees a cell that is not in the manifest, or whose isolation differs
+    /// from the manifest, in which case it is allocated again.
+    Free { cell_name: String },
+    /// Starts an executable that does not exist in its cell.
+    Start {
+        cell_name: String,
+        executable: Executable,
+        uid: Option<u32>,
+        gid: Option<u32>,
+    },
+    /// Stops an executable that is not in the manifest.
+    Stop { cell_name: String, executable_name: String },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Allocate { cell } => {
+                write!(f, "allocate cell {}", cell.name)
+            }
+            Change::Update { cell } => write!(f, "update cell {}", cell.name),
+            Change::Free { cell_name } => write!(f, "free cell {cell_name}"),
+            Change::Start { cell_name, executable, .. } => write!(
+                f,
+                "start executable {} in cell {cell_name}",
+                executable.name
+            ),
+            Change::Stop { cell_name, executable_name } => write!(
+                f,
+                "stop executable {executable_name} in cell {cell_name}"
+            ),
+        }
+    }
+}
+
+/// The changes that make a node match a [Manifest], in the order they are
+/// applied. Parents are allocated before their nested cells.
+///
+/// Executables are compared by name only, as the node does not return their
+/// spec. An executable whose spec changed must be stopped to be started again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Plan {
+    pub changes: Vec<Change>,
+}
+
+impl Plan {
+    /// Compares the manifest with the `cells` of a node, listed with their
+    /// executables.
+    pub fn new(
+        manifest: &Manifest,
+        cells: &[CellGraphNode],
+        prune: bool,
+    ) -> Result<Self> {
+        let mut plan = Self::default();
+        plan.diff_cells(None, &manifest.cells, cells, prune)?;
+        Ok(plan)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Applies the changes in order, stopping at the first that fails.
+    pub async fn apply(&self, client: &Client) -> Result<()> {
+        for change in &self.changes {
+            let res = match change.clone() {
+                Change::Allocate { cell } => client
+                    .allocate(CellServiceAllocateRequest { cell: Some(cell) })
+                    .await
+                    .map(|_| ()),
+                Change::Update { cell } => client
+                    .update(CellServiceUpdateRequest {
+                        cell_name: cell.name,
+                        cpu: cell.cpu,
+                        cpuset: cell.cpuset,
+                        memory: cell.memory,
+                        io: cell.io,
+                        pids: cell.pids,
+                        hugetlb: cell.hugetlb,
+                        rdma: cell.rdma,
+                    })
+                    .await
+                    .map(|_| ()),
+                Change::Free { cell_name } => client
+                    .free(CellServiceFreeRequest { cell_name })
+                    .await
+                    .map(|_| ()),
+                Change::Start { cell_name, executable, uid, gid } => client
+                    .start(CellServiceStartRequest {
+                        cell_name: Some(cell_name),
+                        executable: Some(executable),
+                        uid,
+                        gid,
+                    })
+                    .await
+                    .map(|_| ()),
+                Change::Stop { cell_name, executable_name } => client
+                    .stop(CellServiceStopRequest {
+                        cell_name: Some(cell_name),
+                        executable_name,
+                        ..Default::default()
+                    })
+                    .await
+                    .map(|_| ()),
+            };
+
+            res.with_context(|| format!("failed to {change}"))?;
+        }
+
+        Ok(())
+    }
+
+    fn diff_cells(
+        &mut self,
+        parent: Option<&str>,
+        wanted: &[CellManifest],
+        existing: &[CellGraphNode],
+        prune: bool,
+    ) -> Result<()> {
+        let mut names = HashSet::new();
+        let wanted = wanted
+            .iter()
+            .map(|manifest| {
+                let name = &manifest.cell.name;
+                if name.is_empty() || name.contains('/') {
+                    return Err(anyhow!(
+                        "invalid cell name '{name}', expected a name without '/'"
+                    ));
+                }
+
+                let name = match parent {
+                    Some(parent) => format!("{parent}/{name}"),
+                    None => name.clone(),
+                };
+                if !names.insert(name.clone()) {
+                    return Err(anyhow!("duplicate cell '{name}'"));
+                }
+
+                Ok((name, manifest))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if prune {
+            for cell in existing.iter().filter_map(|x| x.cell.as_ref()) {
+                if !names.contains(&cell.name) {
+                    self.changes
+                        .push(Change::Free { cell_name: cell.name.clone() });
+                }
+            }
+        }
+
+        for (name, manifest) in wanted {
+            let mut cell = manifest.cell.clone();
+            cell.name = name.clone();
+
+            let found = existing.iter().find_map(|node| match &node.cell {
+                Some(current) if current.name == name => Some((node, current)),
+                _ => None,
+            });
+            let Some((node, current)) = found else {
+                self.allocate(cell, manifest, prune)?;
+                continue;
+            };
+
+            if !same_isolation(&cell, current) {
+                self.changes.push(Change::Free { cell_name: name });
+                self.allocate(cell, manifest, prune)?;
+                continue;
+            }
+
+            if !same_resources(&cell, current) {
+                self.changes.push(Change::Update { cell });
+            }
+
+            if let Some(e) = &node.executables_error {
+                return Err(anyhow!(
+                    "failed to list executables of cell '{name}': {e}"
+                ));
+            }
+
+            let running: Vec<_> =
+                node.executables.iter().map(|x| x.name.clone()).collect();
+            self.diff_executables(&name, manifest, &running, prune)?;
+
+            self.diff_cells(
+                Some(&name),
+                &manifest.cells,
+                &node.children,
+                prune,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Allocates the cell, and everything in it, as none of it exists.
+    fn allocate(
+        &mut self,
+        cell: Cell,
+        manifest: &CellManifest,
+        prune: bool,
+    ) -> Result<()> {
+        let name = cell.name.clone();
+        self.changes.push(Change::Allocate { cell });
+        self.diff_executables(&name, manifest, &[], prune)?;
+        self.diff_cells(Some(&name), &manifest.cells, &[], prune)
+    }
+
+    fn diff_executables(
+        &mut self,
+        cell_name: &str,
+        manifest: &CellManifest,
+        running: &[String],
+        prune: bool,
+    ) -> Result<()> {
+        let mut names = HashSet::new();
+        for executable in &manifest.executables {
+            let name = &executable.executable.name;
+            if !names.insert(name) {
+                return Err(anyhow!(
+                    "duplicate executable '{name}' in cell '{cell_name}'"
+                ));
+            }
+        }
+
+        if prune {
+            for name in running {
+                if !names.contains(name) {
+                    self.changes.push(Change::Stop {
+                        cell_name: cell_name.to_string(),
+                        executable_name: name.clone(),
+                    });
+                }
+            }
+        }
+
+        for executable in &manifest.executables {
+            if !running.contains(&executable.executable.name) {
+                self.changes.push(Change::Start {
+                    cell_name: cell_name.to_string(),
+                    executable: executable.executable.clone(),
+                    uid: executable.uid,
+                    gid: executable.gid,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "no changes");
+        }
+
+        for change in &self.changes {
+            let symbol = match change {
+                Change::Allocate { .. } | Change::Start { .. } => '+',
+                Change::Update { .. } => '~',
+                Change::Free { .. } | Change::Stop { .. } => '-',
+            };
+            writeln!(f, "{symbol} {change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The isolation of a cell can't be changed once it is allocated.
+fn same_isolation(wanted: &Cell, current: &Cell) -> bool {
+    wanted.isolate_process == current.isolate_process
+        && wanted.isolate_network == current.isolate_network
+        && wanted.isolate_user == current.isolate_user
+        && wanted.uid_map == current.uid_map
+        && wanted.gid_map == current.gid_map
+        && wanted.rootfs == current.rootfs
+        && wanted.mounts == current.mounts
+        && wanted.security_profile == current.security_profile
+}
+
+/// The resources of a cell are changed with an update.
+fn same_resources(wanted: &Cell, current: &Cell) -> bool {
+    wanted.cpu == current.cpu
+        && wanted.cpuset == current.cpuset
+        && wanted.memory == current.memory
+        && wanted.io == current.io
+        && wanted.pids == current.pids
+        && wanted.hugetlb == current.hugetlb
+        && wanted.rdma == current.rdma
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto::cells::{CpuController, ExecutableStatus};
+
+    const MANIFEST: &str = r#"
+[[cells]]
+name = "app"
+cpu = { weight = 100 }
+
+[[cells.executables]]
+name = "server"
+command = "sleep 3600"
+
+[[cells.cells]]
+name = "sidecar"
+isolateProcess = true
+
+[[cells.cells.executables]]
+name = "proxy"
+args = ["/usr/bin/sleep", "3600"]
+uid = 1000
+"#;
+
+    fn node(
+        cell: Cell,
+        executables: &[&str],
+        children: Vec<CellGraphNode>,
+    ) -> CellGraphNode {
+        CellGraphNode {
+            cell: Some(cell),
+            children,
+            executables: executables
+                .iter()
+                .map(|x| ExecutableStatus {
+                    name: x.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn cell(name: &str) -> Cell {
+        Cell { name: name.into(), ..Default::default() }
+    }
+
+    /// The cells of a node that already matches [MANIFEST].
+    fn existing() -> Vec<CellGraphNode> {
+        let app = Cell {
+            cpu: Some(CpuController {
+                weight: Some(100),
+                ..Default::default()
+            }),
+            ..cell("app")
+        };
+        let sidecar = Cell { isolate_process: true, ..cell("app/sidecar") };
+
+        vec![node(app, &["server"], vec![node(sidecar, &["proxy"], vec![])])]
+    }
+
+    fn changes(plan: &Plan) -> Vec<String> {
+        plan.changes.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = Manifest::parse_from_toml(MANIFEST).expect("manifest");
+
+        let app = &manifest.cells[0];
+        assert_eq!(app.cell.name, "app");
+        assert_eq!(app.cell.cpu.as_ref().and_then(|x| x.weight), Some(100));
+        assert_eq!(app.executables[0].executable.command, "sleep 3600");
+
+        let sidecar = &app.cells[0];
+        assert!(sidecar.cell.isolate_process);
+        assert_eq!(sidecar.executables[0].executable.args.len(), 2);
+        assert_eq!(sidecar.executables[0].uid, Some(1000));
+    }
+
+    #[test]
+    fn test_plan_allocates_missing_cells_parents_first() {
+        let manifest = Manifest::parse_from_toml(MANIFEST).expect("manifest");
+
+        let plan = Plan::new(&manifest, &[], false).expect("plan");
+
+        assert_eq!(
+            changes(&plan),
+            [
+                "allocate cell app",
+                "start executable server in cell app",
+                "allocate cell app/sidecar",
+                "start executable proxy in cell app/sidecar",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_is_empty_when_node_matches_manifest() {
+        let manifest = Manifest::parse_from_toml(MANIFEST).expect("manifest");
+        let plan = Plan::new(&manifest, &existing(), true).expect("plan");
+
+        assert!(plan.is_empty(), "{plan}");
+    }
+
+    #[test]
+    fn test_plan_updates_resources_and_reallocates_isolation_changes() {
+        let mut manifest =
+            Manifest::parse_from_toml(MANIFEST).expect("manifest");
+        manifest.cells[0].cell.cpu =
+            Some(CpuController { weight: Some(200), ..Default::default() });
+        manifest.cells[0].cells[0].cell.isolate_network = true;
+        let plan = Plan::new(&manifest, &existing(), false).expect("plan");
+
+        assert_eq!(
+            changes(&plan),
+            [
+                "update cell app",
+                "free cell app/sidecar",
+                "allocate cell app/sidecar",
+                "start executable proxy in cell app/sidecar",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_only_frees_and_stops_when_pruning() {
+        let manifest = Manifest::parse_from_toml(MANIFEST).expect("manifest");
+        let mut cells = existing();
+        cells.push(node(cell("other"), &[], vec![]));
+        cells[0].executables.push(ExecutableStatus {
+            name: "old".into(),
+            ..Default::default()
+        });
+
+        let plan = Plan::new(&manifest, &cells, false).expect("plan");
+        assert!(plan.is_empty(), "{plan}");
+
+        let plan = Plan::new(&manifest, &cells, true).expect("plan");
+        assert_eq!(
+            changes(&plan),
+            ["free cell other", "stop executable old in cell app"]
+        );
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_names() {
+        let manifest = Manifest::parse_from_toml(
+            r#"
+[[cells]]
+name = "app/nested"
+"#,
+        )
+        .expect("manifest");
+        assert!(Plan::new(&manifest, &[], false).is_err());
+
+        let manifest = Manifest::parse_from_toml(
+            r#"
+[[cells]]
+name = "app"
+
+[[cells]]
+name = "app"
+"#,
+        )
+        .expect("manifest");
+        assert!(Plan::new(&manifest, &[], false).is_err());
+    }
+}
@@ -74,8 +74,10 @@ console.log('Allocated Nested:', nested_allocated)
 let nested_started = await cellService.start(<cells.CellServiceStartRequest>{
     cellName: nestedCellName,
     executable: cells.Executable.fromPartial({
-        command: "/usr/bin/echo 'hello world'",
-        description: "say 'hello'",
+        args: ["/usr/bin/printenv", "GREETING"],
+        env: ["GREETING=hello world"],
+        clearEnv: true,
+        description: "say 'hello' without a shell",
         name: "hello"
     })
 })
@@ -85,6 +87,14 @@ console.log('Started:', nested_started)
 let listed = await cellService.list(<cells.CellServiceListRequest>{})
 console.log('Listed:', listed)
 
+// [ List the cell and its nested cell, with their executables ]
+let listedWithExecutables = await cellService.list(<cells.CellServiceListRequest>{
+    cellName,
+    depth: 1,
+    includeExecutables: true,
+})
+console.log('Listed with executables:', listedWithExecutables)
+
 // [ Stop ]
 let stopped = await cellService.stop(<cells.CellServiceStopRequest>{
     cellName,
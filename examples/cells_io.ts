@@ -0,0 +1,60 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-io-cell";
+
+// [ Allocate ]
+// Find the MAJ:MIN of a device with `lsblk`
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        io: cells.IoController.fromPartial({
+            weight: 50,
+            max: ["8:0 rbps=1048576 wbps=1048576"], // 1MiB/s
+            latency: ["8:0 target=10000"], // 10ms
+        }),
+        name: cellName,
+    })
+});
+console.log(allocated)
+
+// [ List ]
+let listed = await cellService.list(<cells.CellServiceListRequest>{});
+console.log(JSON.stringify(listed, null, 2))
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
\ No newline at end of file
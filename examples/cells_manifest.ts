@@ -0,0 +1,70 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+
+const client = await aurae.createClient();
+
+// The cells and executables that should exist. Running the script again only
+// applies what changed since the last run.
+const manifest: aurae.Manifest = {
+    cells: [{
+        name: "ae-manifest-cell",
+        cpu: { weight: 100 },
+        executables: [{
+            name: "sleep-3600",
+            command: "/usr/bin/sleep 3600",
+        }],
+        cells: [{
+            // Allocated as "ae-manifest-cell/nested"
+            name: "nested",
+            isolateProcess: true,
+            executables: [{
+                name: "sleep-60",
+                args: ["/usr/bin/sleep", "60"],
+            }],
+        }],
+    }],
+};
+
+// [ Diff ]
+const diff = await aurae.apply(manifest, { client, dryRun: true });
+console.log('Changes:', diff.changes)
+
+// [ Apply ]
+const applied = await aurae.apply(manifest, { client });
+console.log('Applied:', applied.changes)
+
+// [ Prune ]
+// The nested cell is no longer in the manifest, so it is freed. Any other cell
+// of the node that is not in the manifest would be freed as well.
+manifest.cells[0].cells = [];
+const pruned = await aurae.apply(manifest, { client, prune: true });
+console.log('Pruned:', pruned.changes)
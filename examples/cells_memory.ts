@@ -39,8 +39,10 @@ let cellName = "ae-sleeper-cell";
 let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
     cell: cells.Cell.fromPartial({
         memory: cells.MemoryController.fromPartial({
-            high: 50000, // 50k
-            max: 100000, // 100k
+            high: 50000, // 50k, throttled above
+            max: 100000, // 100k, OOM killed above
+            swapMax: 0, // no swap
+            oomGroup: true, // OOM kill every process in the cell together
         }),
         name: cellName,
     })
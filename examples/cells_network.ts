@@ -0,0 +1,66 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-network-cell";
+
+// [ Allocate ]
+// The cell is attached to the bridge of auraed (aurae0 by default) and gets
+// an address from its pool (10.88.0.0/16 by default).
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        name: cellName,
+        isolateNetwork: true,
+    })
+});
+console.log(`${cellName} has addresses ${allocated.ipAddresses}`)
+
+// [ Start ]
+// The gateway is the first address of the pool, on the bridge of the host
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "ip addr show eth0 && ping -c 3 10.88.0.1",
+        description: "Pings the host from the cell",
+        name: "ping-host"
+    })
+})
+console.log(started)
+
+// [ Free ]
+// Releases the address of the cell
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
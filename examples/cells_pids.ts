@@ -0,0 +1,78 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-pids-cell";
+
+// [ Allocate ]
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        pids: cells.PidsController.fromPartial({
+            max: 32,
+        }),
+        name: cellName,
+    })
+});
+console.log(allocated)
+
+// [ Start ]
+// Forks beyond the limit of the cell fail instead of exhausting the host
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "for i in $(seq 1 64); do sleep 10 & done; wait",
+        description: "Starts more processes than allowed",
+        name: "forker"
+    })
+})
+console.log(started)
+
+// [ List ]
+let listed = await cellService.list(<cells.CellServiceListRequest>{});
+for (const node of listed.cells) {
+    console.log(`${node.cell?.name}: ${node.pids?.current}/${node.pids?.max ?? "max"} pids`);
+}
+
+// [ Stop ]
+let stopped = await cellService.stop(<cells.CellServiceStopRequest>{
+    cellName,
+    executableName: "forker",
+})
+console.log(stopped)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
\ No newline at end of file
@@ -0,0 +1,79 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-rootfs-cell";
+
+// [ Allocate ]
+// The cell sees /var/lib/aurae-rootfs as its root filesystem, for example an
+// unpacked alpine minirootfs, with a read only view of /srv/data from the host
+// and a fresh /tmp.
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        name: cellName,
+        isolateProcess: true,
+        rootfs: "/var/lib/aurae-rootfs",
+        mounts: [
+            cells.Mount.fromPartial({
+                type: cells.MountType.MOUNT_TYPE_BIND,
+                source: "/srv/data",
+                target: "/data",
+                readonly: true,
+            }),
+            cells.Mount.fromPartial({
+                type: cells.MountType.MOUNT_TYPE_TMPFS,
+                target: "/tmp",
+                size: 67108864,
+            }),
+        ],
+    })
+});
+console.log(allocated)
+
+// [ Start ]
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "ls / /data && touch /data/denied",
+        description: "Lists the root filesystem of the cell",
+        name: "ls-root"
+    })
+})
+console.log(started)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
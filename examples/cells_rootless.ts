@@ -0,0 +1,80 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-rootless-cell";
+
+// [ Allocate ]
+// Root in the cell is uid 100000 on the host. The host ids must be delegated
+// to the user running auraed, e.g. with "root:100000:65536" in both
+// /etc/subuid and /etc/subgid.
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        name: cellName,
+        isolateProcess: true,
+        isolateUser: true,
+        uidMap: [
+            cells.IdMapping.fromPartial({
+                insideId: 0,
+                outsideId: 100000,
+                count: 65536,
+            }),
+        ],
+        gidMap: [
+            cells.IdMapping.fromPartial({
+                insideId: 0,
+                outsideId: 100000,
+                count: 65536,
+            }),
+        ],
+    })
+});
+console.log(allocated)
+
+// [ Start ]
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "id && cat /proc/self/uid_map",
+        description: "Prints the ids of root in the cell",
+        name: "whoami"
+    })
+})
+console.log(started)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
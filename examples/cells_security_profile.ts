@@ -0,0 +1,88 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-security-profile-cell";
+
+// [ Allocate ]
+// Executables in the cell may only bind to privileged ports, can't gain
+// privileges through setuid binaries, and get EPERM for syscalls that are
+// not on the allow list.
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        name: cellName,
+        securityProfile: cells.SecurityProfile.fromPartial({
+            capabilities: cells.Capabilities.fromPartial({
+                bounding: ["CAP_NET_BIND_SERVICE"],
+                ambient: ["CAP_NET_BIND_SERVICE"],
+            }),
+            noNewPrivs: true,
+            seccomp: JSON.stringify({
+                defaultAction: "SCMP_ACT_ERRNO",
+                syscalls: [{
+                    names: [
+                        "accept4", "arch_prctl", "bind", "brk", "close",
+                        "execve", "exit_group", "fcntl", "fstat", "futex",
+                        "getpid", "listen", "mmap", "mprotect", "munmap",
+                        "newfstatat", "openat", "read", "rt_sigaction",
+                        "rt_sigprocmask", "rt_sigreturn", "set_tid_address",
+                        "setsockopt", "socket", "write",
+                    ],
+                    action: "SCMP_ACT_ALLOW",
+                }],
+            }),
+        }),
+    })
+});
+console.log(allocated)
+
+// [ Start ]
+// Runs as nobody, which keeps CAP_NET_BIND_SERVICE through the ambient set.
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        args: ["/usr/bin/nc", "-l", "-p", "80"],
+        description: "Listens on a privileged port without being root",
+        name: "nc-listen"
+    }),
+    uid: 65534,
+    gid: 65534,
+})
+console.log(started)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
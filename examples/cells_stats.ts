@@ -0,0 +1,89 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-stats-cell";
+
+// [ Allocate ]
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        name: cellName,
+    })
+});
+console.log('Allocated:', allocated)
+
+// [ Start ]
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "yes > /dev/null",
+        description: "Burns some cpu",
+        name: "burner"
+    })
+})
+console.log('Started:', started)
+
+// [ Stats ]
+let stats = await cellService.stats(<cells.CellServiceStatsRequest>{
+    cellName,
+});
+console.log('Stats:', JSON.stringify(stats, null, 2))
+
+// [ Watch stats ]
+let stream = cellService.watchStats(<cells.CellServiceWatchStatsRequest>{
+    cellName,
+    intervalMs: 500,
+});
+let samples = 0;
+for await (const res of stream) {
+    let cell = res.cells[0];
+    console.log(`cpu: ${cell.stats?.cpu?.usageUsec}us memory: ${cell.stats?.memory?.current}B`);
+    if (++samples === 5) {
+        break; // exiting the loop closes the stream
+    }
+}
+
+// [ Stop ]
+let stopped = await cellService.stop(<cells.CellServiceStopRequest>{
+    cellName,
+    executableName: "burner",
+})
+console.log('Stopped:', stopped)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log('Freed:', freed)
\ No newline at end of file
@@ -0,0 +1,92 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+
+let client = await aurae.createClient();
+let cellService = new cells.CellServiceClient(client);
+let cellName = "ae-update-cell";
+
+// [ Allocate ]
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        cpu: cells.CpuController.fromPartial({
+            weight: 2,
+            max: 200 * (10 ** 3), // 0.2 seconds
+        }),
+        memory: cells.MemoryController.fromPartial({
+            max: 64 * (1024 ** 2), // 64 MiB
+        }),
+        name: cellName,
+    })
+});
+console.log(allocated)
+
+// [ Start ]
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "sleep 3000",
+        description: "Keeps running while the cell is updated",
+        name: "sleeper"
+    })
+})
+console.log(started)
+
+// [ Update ]
+// Raises the memory limit of the running cell. The cpu controller is not
+// set, so its weight and max are reset to the kernel defaults.
+let updated = await cellService.update(<cells.CellServiceUpdateRequest>{
+    cellName,
+    memory: cells.MemoryController.fromPartial({
+        max: 128 * (1024 ** 2), // 128 MiB
+    }),
+});
+console.log(updated)
+
+// [ List ]
+let listed = await cellService.list(<cells.CellServiceListRequest>{});
+for (const node of listed.cells) {
+    console.log(node.cell)
+}
+
+// [ Stop ]
+let stopped = await cellService.stop(<cells.CellServiceStopRequest>{
+    cellName,
+    executableName: "sleeper",
+})
+console.log(stopped)
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log(freed)
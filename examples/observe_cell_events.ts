@@ -0,0 +1,79 @@
+#!/usr/bin/env auraescript
+/* -------------------------------------------------------------------------- *\
+ *        Apache 2.0 License Copyright © 2022-2023 The Aurae Authors          *
+ *                                                                            *
+ *                +--------------------------------------------+              *
+ *                |   █████╗ ██╗   ██╗██████╗  █████╗ ███████╗ |              *
+ *                |  ██╔══██╗██║   ██║██╔══██╗██╔══██╗██╔════╝ |              *
+ *                |  ███████║██║   ██║██████╔╝███████║█████╗   |              *
+ *                |  ██╔══██║██║   ██║██╔══██╗██╔══██║██╔══╝   |              *
+ *                |  ██║  ██║╚██████╔╝██║  ██║██║  ██║███████╗ |              *
+ *                |  ╚═╝  ╚═╝ ╚═════╝ ╚═╝  ╚═╝╚═╝  ╚═╝╚══════╝ |              *
+ *                +--------------------------------------------+              *
+ *                                                                            *
+ *                         Distributed Systems Runtime                        *
+ *                                                                            *
+ * -------------------------------------------------------------------------- *
+ *                                                                            *
+ *   Licensed under the Apache License, Version 2.0 (the "License");          *
+ *   you may not use this file except in compliance with the License.         *
+ *   You may obtain a copy of the License at                                  *
+ *                                                                            *
+ *       http://www.apache.org/licenses/LICENSE-2.0                           *
+ *                                                                            *
+ *   Unless required by applicable law or agreed to in writing, software      *
+ *   distributed under the License is distributed on an "AS IS" BASIS,        *
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. *
+ *   See the License for the specific language governing permissions and      *
+ *   limitations under the License.                                           *
+ *                                                                            *
+\* -------------------------------------------------------------------------- */
+import * as aurae from "../auraescript/gen/aurae.ts";
+import * as cells from "../auraescript/gen/cells.ts";
+import * as observe from "../auraescript/gen/observe.ts";
+
+let client = await aurae.createClient();
+
+let cellService = new cells.CellServiceClient(client);
+let observeService = new observe.ObserveServiceClient(client);
+let cellName = "ae-oom-cell";
+
+// [ Allocate ]
+let allocated = await cellService.allocate(<cells.CellServiceAllocateRequest>{
+    cell: cells.Cell.fromPartial({
+        memory: cells.MemoryController.fromPartial({
+            max: 32 * (1024 ** 2), // 32 MiB
+        }),
+        name: cellName,
+    })
+});
+console.log('Allocated:', allocated)
+
+// [ Watch events ]
+let stream = observeService.getCellEventsStream(<observe.GetCellEventsStreamRequest>{
+    cellName,
+});
+
+// [ Start ]
+let started = await cellService.start(<cells.CellServiceStartRequest>{
+    cellName,
+    executable: cells.Executable.fromPartial({
+        command: "dd if=/dev/zero of=/dev/null bs=128M count=1",
+        description: "Uses more memory than the cell allows",
+        name: "hog"
+    })
+})
+console.log('Started:', started)
+
+for await (const res of stream) {
+    console.log(`${res.event?.cellName}: ${res.event?.eventType} (${res.event?.count})`);
+    if (res.event?.eventType === observe.CellEventType.CELL_EVENT_TYPE_OOM_KILL) {
+        break; // exiting the loop closes the stream
+    }
+}
+
+// [ Free ]
+let freed = await cellService.free(<cells.CellServiceFreeRequest>{
+    cellName
+});
+console.log('Freed:', freed)